@@ -0,0 +1,374 @@
+use crate::{
+  cli::output::OutputFormat,
+  config::{ bundle, edit, layers::{ Layer, Origin }, manager::ConfigurationManager, migrations },
+  error::*,
+  utils::diff::{ DiffLine, diff_lines, unified_diff },
+};
+use console::{ Emoji, style };
+use std::path::PathBuf;
+
+static SUCCESS: Emoji<'_, '_> = Emoji("✅ ", "");
+static INFO: Emoji<'_, '_> = Emoji("📦 ", "");
+static WARNING: Emoji<'_, '_> = Emoji("⚠️ ", "");
+
+fn format_time(millis: i64) -> String {
+  chrono::DateTime
+    ::from_timestamp_millis(millis)
+    .unwrap_or_default()
+    .format("%Y-%m-%d %H:%M:%S UTC")
+    .to_string()
+}
+
+fn format_value(value: &serde_yaml::Value) -> String {
+  match value {
+    serde_yaml::Value::String(s) => s.clone(),
+    _ => serde_json::to_string(value).unwrap_or_default(),
+  }
+}
+
+pub async fn handle_show(origin: bool, output: OutputFormat) -> Result<()> {
+  let config_manager = ConfigurationManager::new()?;
+  let resolved = config_manager.resolve().await?;
+
+  if !origin {
+    if output.emit(resolved.document())? {
+      return Ok(());
+    }
+    let yaml = serde_yaml
+      ::to_string(resolved.document())
+      .map_err(|e| ClaudeCodeError::Generic(e.to_string()))?;
+    print!("{}", yaml);
+    return Ok(());
+  }
+
+  let values = resolved.values();
+  if output.emit(&values)? {
+    return Ok(());
+  }
+
+  let width = values
+    .iter()
+    .map(|v| v.path.len())
+    .max()
+    .unwrap_or(0);
+  for value in &values {
+    println!(
+      "{}  {}  {}",
+      style(format!("{:<width$}", value.path, width = width)).bold(),
+      format_value(&value.value),
+      style(&value.origin).dim()
+    );
+  }
+
+  Ok(())
+}
+
+pub async fn handle_get(path: String, output: OutputFormat) -> Result<()> {
+  let config_manager = ConfigurationManager::new()?;
+  let value = config_manager.get_value(&path).await?;
+
+  if output.emit(&value)? {
+    return Ok(());
+  }
+
+  match &value {
+    serde_yaml::Value::String(s) => println!("{}", s),
+    serde_yaml::Value::Null => {}
+    _ => {
+      let yaml = serde_yaml
+        ::to_string(&value)
+        .map_err(|e| ClaudeCodeError::Generic(e.to_string()))?;
+      print!("{}", yaml);
+    }
+  }
+
+  Ok(())
+}
+
+pub async fn handle_set(path: String, value: String) -> Result<()> {
+  let config_manager = ConfigurationManager::new()?;
+  let in_place = config_manager.set_value(&path, &value).await?;
+
+  let written = edit::config_value(&config_manager.load_file().await?)?;
+  let shown = edit
+    ::get_path(&written, &edit::parse_path(&path)?)
+    .map(format_value)
+    .unwrap_or(value);
+  println!("{}Set {} = {}", SUCCESS, style(&path).bold(), shown);
+  warn_if_rewritten(&config_manager, in_place);
+  let resolved = config_manager.resolve().await?;
+  warn_if_overridden(&path, resolved.origin(&path));
+
+  Ok(())
+}
+
+pub async fn handle_unset(path: String) -> Result<()> {
+  let config_manager = ConfigurationManager::new()?;
+  let in_place = config_manager.unset_value(&path).await?;
+
+  println!("{}Unset {}", SUCCESS, style(&path).bold());
+  warn_if_rewritten(&config_manager, in_place);
+  let resolved = config_manager.resolve().await?;
+  warn_if_overridden(&path, resolved.origin(&path));
+
+  Ok(())
+}
+
+fn warn_if_overridden(path: &str, origin: Option<&Origin>) {
+  if let Some(origin) = origin.filter(|o| o.layer > Layer::User) {
+    println!(
+      "{}{}",
+      WARNING,
+      style(format!("{} is overridden by {}", path, origin)).yellow()
+    );
+  }
+}
+
+fn warn_if_rewritten(config_manager: &ConfigurationManager, in_place: bool) {
+  if !in_place {
+    println!(
+      "{}{}",
+      WARNING,
+      style(
+        format!(
+          "{} could not be edited in place and was rewritten without comments; \
+           the previous file is in 'config backups'",
+          config_manager.config_path().display()
+        )
+      ).yellow()
+    );
+  }
+}
+
+pub async fn handle_backups(output: OutputFormat) -> Result<()> {
+  let config_manager = ConfigurationManager::new()?;
+  let store = config_manager.backups();
+  let backups = store.list()?;
+
+  if output.emit(&backups)? {
+    return Ok(());
+  }
+
+  if backups.is_empty() {
+    println!("{}", style("No configuration backups yet").dim());
+    return Ok(());
+  }
+
+  println!("{}Configuration backups in {}", INFO, store.dir().display());
+  let current = std::fs::read_to_string(config_manager.config_path()).unwrap_or_default();
+  for backup in &backups {
+    let content = backup.read_config().unwrap_or_default();
+    let (added, removed) = diff_lines(&content, &current)
+      .iter()
+      .fold((0, 0), |(added, removed), line| {
+        match line {
+          DiffLine::Added(_) => (added + 1, removed),
+          DiffLine::Removed(_) => (added, removed + 1),
+          DiffLine::Same(_) => (added, removed),
+        }
+      });
+
+    let state = if backup.has_state { " +sync state" } else { "" };
+    println!(
+      "  {}  {}{}  {}",
+      style(&backup.id).bold(),
+      format_time(backup.created_at),
+      style(state).dim(),
+      style(format!("(+{} -{} lines to current)", added, removed)).dim()
+    );
+  }
+
+  Ok(())
+}
+
+pub async fn handle_restore(id: String, with_state: bool) -> Result<()> {
+  let config_manager = ConfigurationManager::new()?;
+  let backup = config_manager.restore_backup(&id, with_state).await?;
+
+  println!(
+    "{}Restored configuration from {} ({})",
+    SUCCESS,
+    style(&backup.id).bold(),
+    format_time(backup.created_at)
+  );
+  if with_state && backup.has_state {
+    println!("{}Restored sync state", SUCCESS);
+  }
+  println!(
+    "{}",
+    style("The replaced configuration was backed up; undo with 'config restore latest'").dim()
+  );
+
+  Ok(())
+}
+
+pub async fn handle_diff(id: String) -> Result<()> {
+  let config_manager = ConfigurationManager::new()?;
+  let backup = config_manager.backups().get(&id)?;
+  let current = std::fs::read_to_string(config_manager.config_path()).unwrap_or_default();
+
+  let diff = unified_diff(
+    &backup.read_config()?,
+    &current,
+    &backup.id,
+    &config_manager.config_path().display().to_string(),
+    3
+  );
+  if diff.is_empty() {
+    println!("{}", style(format!("No changes since {}", backup.id)).dim());
+    return Ok(());
+  }
+
+  print_diff(&diff);
+  Ok(())
+}
+
+pub async fn handle_migrate(dry_run: bool) -> Result<()> {
+  let config_manager = ConfigurationManager::new()?;
+  let plan = config_manager.plan_migration().await?;
+  let Some(migrated) = &plan.migrated else {
+    println!(
+      "{}",
+      style(format!("Configuration is at schema version {}", migrations::CURRENT_VERSION)).dim()
+    );
+    return Ok(());
+  };
+
+  println!(
+    "{}Schema version {} -> {}",
+    INFO,
+    plan.from,
+    style(migrations::CURRENT_VERSION).bold()
+  );
+  for migration in &plan.applied {
+    println!("  {} {}", style("•").dim(), migration.description);
+  }
+
+  let path = config_manager.config_path().display().to_string();
+  let current = std::fs::read_to_string(config_manager.config_path())?;
+  print_diff(&unified_diff(&current, migrated, &path, &path, 3));
+
+  if dry_run {
+    println!("{}", style("Dry run, nothing written").dim());
+    return Ok(());
+  }
+  config_manager.apply_migration(&plan).await?;
+  println!("{}Migrated {}", SUCCESS, style(path).bold());
+  Ok(())
+}
+
+pub async fn handle_export(file: Option<PathBuf>) -> Result<()> {
+  let config_manager = ConfigurationManager::new()?;
+  let (bundle, excluded) = bundle::export(&config_manager.load_file().await?)?;
+  let yaml = serde_yaml::to_string(&bundle).map_err(|e| ClaudeCodeError::Generic(e.to_string()))?;
+  let content = format!(
+    "# claude-code-toolkit configuration bundle\n\
+     # Import with: claude-code-toolkit config import <file or URL>\n{}",
+    yaml
+  );
+
+  // Progress goes to stderr, so the bundle can be piped
+  match &file {
+    Some(file) => {
+      std::fs::write(file, content)?;
+      eprintln!("{}Exported configuration to {}", SUCCESS, style(file.display()).bold());
+    }
+    None => print!("{}", content),
+  }
+  if !excluded.is_empty() {
+    eprintln!("{}Left out as secret or machine-specific:", INFO);
+    for path in &excluded {
+      eprintln!("  {} {}", style("•").dim(), style(path).dim());
+    }
+  }
+  Ok(())
+}
+
+pub async fn handle_import(
+  source: String,
+  overwrite: bool,
+  dry_run: bool,
+  output: OutputFormat
+) -> Result<()> {
+  let config_manager = ConfigurationManager::new()?;
+  let incoming = bundle::parse(&bundle::read(&source).await?)?;
+  let (merge, in_place) = config_manager.import_bundle(&incoming, overwrite, dry_run).await?;
+
+  if output.emit(&merge)? {
+    return Ok(());
+  }
+  if merge.changes.is_empty() && merge.conflicts.is_empty() {
+    println!("{}", style("Configuration already matches the bundle").dim());
+    return Ok(());
+  }
+
+  for change in &merge.changes {
+    match &change.replaced {
+      Some(replaced) =>
+        println!(
+          "  {} {}: {} -> {}",
+          style("~").yellow(),
+          change.path,
+          format_value(replaced),
+          format_value(&change.value)
+        ),
+      None => println!("  {} {}: {}", style("+").green(), change.path, format_value(&change.value)),
+    }
+  }
+  for conflict in &merge.conflicts {
+    println!(
+      "  {} {}: kept {}, bundle has {}",
+      style("!").red(),
+      conflict.path,
+      format_value(&conflict.local),
+      format_value(&conflict.incoming)
+    );
+  }
+
+  if !merge.conflicts.is_empty() {
+    println!(
+      "{}{}",
+      WARNING,
+      style(
+        format!(
+          "{} conflicts kept their local value; --overwrite takes the bundle's",
+          merge.conflicts.len()
+        )
+      ).yellow()
+    );
+  }
+  for change in merge.changes.iter().filter(|c| c.path.starts_with("profiles.")) {
+    if change.path.split('.').count() == 2 {
+      println!(
+        "{}Set {}.file_path to the credentials file of the new profile",
+        INFO,
+        style(&change.path).bold()
+      );
+    }
+  }
+
+  if dry_run {
+    println!("{}", style("Dry run, nothing written").dim());
+  } else if !merge.changes.is_empty() {
+    println!("{}Imported {} changes from {}", SUCCESS, merge.changes.len(), style(&source).bold());
+    warn_if_rewritten(&config_manager, in_place);
+  }
+  Ok(())
+}
+
+fn print_diff(diff: &str) {
+  for line in diff.lines() {
+    let styled = if line.starts_with("+++") || line.starts_with("---") {
+      style(line).bold()
+    } else if line.starts_with('+') {
+      style(line).green()
+    } else if line.starts_with('-') {
+      style(line).red()
+    } else if line.starts_with("@@") {
+      style(line).cyan()
+    } else {
+      style(line)
+    };
+    println!("{}", styled);
+  }
+}
@@ -1,7 +1,11 @@
+pub mod config;
 pub mod configure;
 pub mod org;
 pub mod repo;
+pub mod revoke;
 pub mod service;
 pub mod status;
 pub mod sync;
+pub mod target;
 pub mod timer;
+pub mod validate;
@@ -1,4 +1,9 @@
-use crate::{ config::manager::ConfigurationManager, error::*, providers::github::GitHubManager };
+use crate::{
+  cli::output::{ ExitStatus, OutputFormat, TargetReport },
+  config::manager::ConfigurationManager,
+  error::*,
+  providers::github::GitHubManager,
+};
 use console::{ Emoji, style };
 
 static SUCCESS: Emoji<'_, '_> = Emoji("✅ ", "");
@@ -39,7 +44,7 @@ pub async fn handle_add_org(name: String) -> Result<()> {
   Ok(())
 }
 
-pub async fn handle_remove_org(name: String) -> Result<()> {
+pub async fn handle_remove_org(name: String, purge: bool) -> Result<()> {
   println!("{}Removing organization {}", INFO, style(&name).bold());
 
   let config_manager = ConfigurationManager::new()?;
@@ -47,23 +52,44 @@ pub async fn handle_remove_org(name: String) -> Result<()> {
 
   println!("{}Successfully removed organization {}", SUCCESS, style(&name).bold());
 
+  if purge {
+    super::target::purge_removed_target("github", "organization", &name).await?;
+  }
+
   Ok(())
 }
 
-pub async fn handle_list_orgs() -> Result<()> {
+pub async fn handle_list_orgs(output: OutputFormat) -> Result<()> {
   let config_manager = ConfigurationManager::new()?;
   let config = config_manager.load_config().await?;
 
-  if config.github.organizations.is_empty() {
+  let organizations: Vec<_> = config
+    .targets_for("github")
+    .filter(|t| t.kind == "organization")
+    .collect();
+
+  let reports: Vec<TargetReport> = organizations
+    .iter()
+    .map(|t| super::target::target_report(&config, t))
+    .collect();
+  if output.emit(&reports)? {
+    if reports.is_empty() {
+      ExitStatus::NotConfigured.exit();
+    }
+    return Ok(());
+  }
+
+  if organizations.is_empty() {
     println!("{}", style("No organizations configured").yellow());
     println!("{}", style("Use 'claude-code org add <name>' to add an organization").dim());
+    ExitStatus::NotConfigured.exit();
     return Ok(());
   }
 
   println!("{}", style("Configured Organizations:").bold());
   println!();
 
-  for org in &config.github.organizations {
+  for org in &organizations {
     println!("  {}", style(&org.name).cyan());
     let secret_names: Vec<String> = config.credentials.field_mappings.values().cloned().collect();
     println!("    Secrets: {}", style(secret_names.join(", ")).dim());
@@ -83,7 +109,7 @@ pub async fn handle_list_orgs() -> Result<()> {
         println!("  {}", style("No organizations found").dim());
       } else {
         for org in available_orgs {
-          let is_configured = config.github.organizations.iter().any(|o| o.name == org);
+          let is_configured = organizations.iter().any(|o| o.name == org);
           if is_configured {
             println!("  {} {}", style("✓").green(), org);
           } else {
@@ -1,4 +1,8 @@
-use crate::{ config::manager::ConfigurationManager, error::* };
+use crate::{
+  cli::output::{ ExitStatus, OutputFormat, TargetReport },
+  config::manager::ConfigurationManager,
+  error::*,
+};
 use console::{ Emoji, style };
 
 static SUCCESS: Emoji<'_, '_> = Emoji("✅ ", "");
@@ -20,7 +24,7 @@ pub async fn handle_add_repo(repo: String) -> Result<()> {
   Ok(())
 }
 
-pub async fn handle_remove_repo(repo: String) -> Result<()> {
+pub async fn handle_remove_repo(repo: String, purge: bool) -> Result<()> {
   println!("{}Removing repository {}", INFO, style(&repo).bold());
 
   let config_manager = ConfigurationManager::new()?;
@@ -28,24 +32,45 @@ pub async fn handle_remove_repo(repo: String) -> Result<()> {
 
   println!("{}Successfully removed repository {}", SUCCESS, style(&repo).bold());
 
+  if purge {
+    super::target::purge_removed_target("github", "repository", &repo).await?;
+  }
+
   Ok(())
 }
 
-pub async fn handle_list_repos() -> Result<()> {
+pub async fn handle_list_repos(output: OutputFormat) -> Result<()> {
   let config_manager = ConfigurationManager::new()?;
   let config = config_manager.load_config().await?;
 
-  if config.github.repositories.is_empty() {
+  let repositories: Vec<_> = config
+    .targets_for("github")
+    .filter(|t| t.kind == "repository")
+    .collect();
+
+  let reports: Vec<TargetReport> = repositories
+    .iter()
+    .map(|t| super::target::target_report(&config, t))
+    .collect();
+  if output.emit(&reports)? {
+    if reports.is_empty() {
+      ExitStatus::NotConfigured.exit();
+    }
+    return Ok(());
+  }
+
+  if repositories.is_empty() {
     println!("{}", style("No repositories configured").yellow());
     println!("{}", style("Use 'claude-code repo add <owner/repo>' to add a repository").dim());
+    ExitStatus::NotConfigured.exit();
     return Ok(());
   }
 
   println!("{}", style("Configured Repositories:").bold());
   println!();
 
-  for repo in &config.github.repositories {
-    println!("  {}", style(&repo.repo).cyan());
+  for repo in &repositories {
+    println!("  {}", style(&repo.name).cyan());
     let secret_names: Vec<String> = config.credentials.field_mappings.values().cloned().collect();
     println!("    Secrets: {}", style(secret_names.join(", ")).dim());
   }
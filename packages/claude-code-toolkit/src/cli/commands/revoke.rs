@@ -0,0 +1,111 @@
+use crate::{
+  cli::output::{ ExitStatus, OutputFormat },
+  error::*,
+  sync::{ SyncService, revoke::TOMBSTONE },
+};
+use console::{ Emoji, Term, style };
+
+static SUCCESS: Emoji<'_, '_> = Emoji("✅ ", "");
+static FAILURE: Emoji<'_, '_> = Emoji("❌ ", "");
+static WARNING: Emoji<'_, '_> = Emoji("⚠️ ", "");
+
+pub async fn handle_revoke(
+  tombstone: bool,
+  yes: bool,
+  report_path: Option<String>,
+  output: OutputFormat
+) -> Result<()> {
+  let action = if tombstone {
+    format!("overwrite every synced secret with '{}'", TOMBSTONE)
+  } else {
+    "delete every synced secret".to_string()
+  };
+
+  // Keep stdout clean for structured output
+  let term = if output.is_text() { Term::stdout() } else { Term::stderr() };
+  term.write_line(
+    &format!("{}{}", WARNING, style(format!("This will {} on every target", action)).bold())
+  )?;
+
+  if !yes {
+    if !term.is_term() {
+      return Err(
+        ClaudeCodeError::Validation("Refusing to revoke without a terminal; pass --yes".to_string())
+      );
+    }
+    term.write_str("Type 'revoke' to continue: ")?;
+    if term.read_line()?.trim() != "revoke" {
+      term.write_line(&style("Aborted, nothing was changed").dim().to_string())?;
+      return Ok(());
+    }
+  }
+
+  // Revoke has to work even when the configuration does not load
+  let mut sync_service = SyncService::new()?;
+  let report = sync_service.revoke(tombstone).await?;
+
+  if let Some(path) = &report_path {
+    let path = shellexpand::tilde(path).to_string();
+    std::fs::write(&path, serde_json::to_string_pretty(&report)?)?;
+    term.write_line(&format!("Report written to {}", path))?;
+  }
+
+  if !output.emit(&report)? {
+    println!();
+    if report.targets.is_empty() {
+      println!("{}", style("No synced secrets found").dim());
+    }
+    for target in &report.targets {
+      let icon = if target.failed.is_empty() { SUCCESS } else { FAILURE };
+      println!("{}{}", icon, style(&target.target).bold());
+      if !target.removed.is_empty() {
+        let verb = if tombstone { "Tombstoned" } else { "Removed" };
+        println!("   {}: {}", verb, target.removed.join(", "));
+      }
+      if !target.failed.is_empty() {
+        println!("   {}: {}", style("Failed").red(), target.failed.join(", "));
+      }
+      for error in &target.errors {
+        println!("   {}", style(error).red());
+      }
+    }
+
+    if !report.untracked.is_empty() {
+      println!();
+      println!(
+        "{}{}",
+        WARNING,
+        style("No synced secrets are recorded for these targets; revoke them by hand:").yellow()
+      );
+      for target in &report.untracked {
+        println!("   {}", target);
+      }
+    }
+
+    println!();
+    println!("Removed: {}, Failed: {}", report.removed_count(), report.failed_count());
+    println!(
+      "{}",
+      style(
+        "Syncing is disarmed. Run 'claude-code-toolkit rearm' once credentials are safe"
+      ).yellow()
+    );
+  }
+
+  if report.failed_count() > 0 || !report.untracked.is_empty() {
+    ExitStatus::Error.exit();
+  }
+
+  Ok(())
+}
+
+pub async fn handle_rearm() -> Result<()> {
+  if SyncService::rearm()? {
+    println!("{}Syncing re-armed", SUCCESS);
+    println!("{}", style("Run 'claude-code-toolkit sync' to sync immediately").dim());
+  } else {
+    println!("{}", style("Syncing is not disarmed").dim());
+  }
+
+  Ok(())
+}
@@ -17,10 +17,13 @@ pub async fn handle_install() -> Result<()> {
   // Install systemd service
   let systemd_manager = SystemdManager::new()?;
 
-  // Check if service is already running
+  // Check if service is already running, with the unit this version would install
   if systemd_manager.is_running().await.unwrap_or(false) {
-    println!("{}Service is already installed and running", style("⚠️").yellow());
-    return Ok(());
+    if !systemd_manager.is_outdated().await? {
+      println!("{}Service is already installed and running", style("⚠️").yellow());
+      return Ok(());
+    }
+    println!("{}Updating the installed service", RESTART);
   }
 
   systemd_manager.install().await?;
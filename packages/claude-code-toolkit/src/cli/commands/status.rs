@@ -1,4 +1,12 @@
 use crate::{
+  cli::output::{
+    ConfigReport,
+    OutputFormat,
+    ServiceReport,
+    SessionReport,
+    StatusReport,
+    SyncStatusReport,
+  },
   config::{ credentials::CredentialsManager, manager::ConfigurationManager },
   error::*,
   utils::systemd::SystemdManager,
@@ -10,63 +18,140 @@ static CROSS: Emoji<'_, '_> = Emoji("❌ ", "");
 static WARNING: Emoji<'_, '_> = Emoji("⚠️ ", "");
 static INFO: Emoji<'_, '_> = Emoji("📊 ", "");
 
-pub async fn handle_status() -> Result<()> {
+pub async fn handle_status(output: OutputFormat) -> Result<()> {
+  let report = collect_status().await;
+
+  if !output.emit(&report)? {
+    print_status(&report);
+  }
+
+  report.exit_status().exit();
+  Ok(())
+}
+
+async fn collect_status() -> StatusReport {
+  let config_manager = ConfigurationManager::new();
+  let config = match &config_manager {
+    Ok(config_manager) => config_manager.load_config().await,
+    Err(e) => Err(ClaudeCodeError::Generic(e.to_string())),
+  };
+
+  // Session info, one entry per credential profile
+  let profiles = config.as_ref().cloned().unwrap_or_default();
+  let mut sessions = Vec::new();
+  for profile in profiles.profile_names() {
+    let Some(profile_config) = profiles.profile(profile) else {
+      continue;
+    };
+
+    let session = CredentialsManager::from_config(profile_config).get_session_info().await;
+    sessions.push(match session {
+      Ok(session_info) =>
+        SessionReport {
+          profile: profile.to_string(),
+          subscription_type: Some(session_info.subscription_type),
+          expires_at: Some(session_info.expires_at),
+          time_remaining_ms: Some(session_info.time_remaining),
+          expired: session_info.is_expired,
+          error: None,
+        },
+      Err(e) =>
+        SessionReport {
+          profile: profile.to_string(),
+          subscription_type: None,
+          expires_at: None,
+          time_remaining_ms: None,
+          expired: false,
+          error: Some(e.to_string()),
+        },
+    });
+  }
+
+  let service = match SystemdManager::new() {
+    Ok(systemd_manager) =>
+      match systemd_manager.is_running().await {
+        Ok(is_running) => ServiceReport { running: Some(is_running), error: None },
+        Err(e) => ServiceReport { running: None, error: Some(e.to_string()) },
+      }
+    Err(e) => ServiceReport { running: None, error: Some(e.to_string()) },
+  };
+
+  let sync = SyncStatusReport::from(crate::sync::state::load());
+
+  let mut config_report = ConfigReport {
+    path: config_manager
+      .as_ref()
+      .ok()
+      .map(|m| m.config_path().display().to_string()),
+    targets: std::collections::BTreeMap::new(),
+    error: None,
+  };
+  match &config {
+    Ok(config) => {
+      for target in &config.targets {
+        *config_report.targets.entry(target.provider.clone()).or_insert(0) += 1;
+      }
+    }
+    Err(e) => {
+      config_report.error = Some(e.to_string());
+    }
+  }
+
+  StatusReport { sessions, service, sync, config: config_report }
+}
+
+fn print_status(report: &StatusReport) {
   println!("{} {}", INFO, style("Claude Code Status").bold());
   println!();
 
-  // Session info
+  // Session info, one block per credential profile
   println!("{}", style("Session Information:").bold());
-  match CredentialsManager::new() {
-    Ok(credentials_manager) =>
-      match credentials_manager.get_session_info().await {
-        Ok(session_info) => {
-          println!("  Subscription: {}", style(&session_info.subscription_type).cyan());
-
-          if session_info.is_expired {
-            println!("  Status: {}", style("Expired").red());
-          } else {
-            println!("  Status: {}", style("Active").green());
-            println!(
-              "  Time Remaining: {}",
-              style(CredentialsManager::format_time_remaining(session_info.time_remaining)).yellow()
-            );
-          }
-
-          let expires_at = chrono::DateTime
-            ::from_timestamp(session_info.expires_at / 1000, 0)
-            .unwrap_or_default()
-            .format("%Y-%m-%d %H:%M:%S UTC");
-          println!("  Expires At: {}", expires_at);
-        }
-        Err(e) => {
-          println!("  {}", style(format!("Error reading session: {}", e)).red());
-        }
-      }
-    Err(e) => {
-      println!("  {}", style(format!("Error initializing credentials manager: {}", e)).red());
+  for session in &report.sessions {
+    let indent = if report.sessions.len() > 1 {
+      println!("  {}", style(&session.profile).bold());
+      "    "
+    } else {
+      "  "
+    };
+
+    if let Some(error) = &session.error {
+      println!("{}{}", indent, style(format!("Error reading session: {}", error)).red());
+      continue;
+    }
+
+    let subscription_type = session.subscription_type.as_deref().unwrap_or_default();
+    println!("{}Subscription: {}", indent, style(subscription_type).cyan());
+
+    if session.expired {
+      println!("{}Status: {}", indent, style("Expired").red());
+    } else {
+      println!("{}Status: {}", indent, style("Active").green());
+      println!(
+        "{}Time Remaining: {}",
+        indent,
+        style(
+          CredentialsManager::format_time_remaining(session.time_remaining_ms.unwrap_or_default())
+        ).yellow()
+      );
     }
+
+    let expires_at = chrono::DateTime
+      ::from_timestamp(session.expires_at.unwrap_or_default() / 1000, 0)
+      .unwrap_or_default()
+      .format("%Y-%m-%d %H:%M:%S UTC");
+    println!("{}Expires At: {}", indent, expires_at);
   }
 
   println!();
 
   // Daemon status
   println!("{}", style("Daemon Status:").bold());
-  match SystemdManager::new() {
-    Ok(systemd_manager) =>
-      match systemd_manager.is_running().await {
-        Ok(is_running) => {
-          if is_running {
-            println!("  Status: {}Running", CHECKMARK);
-          } else {
-            println!("  Status: {}Stopped", CROSS);
-          }
-        }
-        Err(e) => {
-          println!("  Status: {}Error checking status: {}", WARNING, e);
-        }
-      }
-    Err(e) => {
-      println!("  Status: {}Error initializing systemd manager: {}", WARNING, e);
+  match (&report.service.running, &report.service.error) {
+    (Some(true), _) => println!("  Status: {}Running", CHECKMARK),
+    (Some(false), _) => println!("  Status: {}Stopped", CROSS),
+    (None, error) => {
+      let error = error.as_deref().unwrap_or_default();
+      println!("  Status: {}Error checking status: {}", WARNING, error);
     }
   }
 
@@ -74,47 +159,44 @@ pub async fn handle_status() -> Result<()> {
 
   // Sync status
   println!("{}", style("Sync Status:").bold());
-  match ConfigurationManager::new() {
-    Ok(config_manager) => {
-      match config_manager.load_state().await {
-        Ok(state) => {
-          if state.last_sync == 0 {
-            println!("  Last Sync: {}", style("Never").yellow());
-          } else {
-            let last_sync = chrono::DateTime
-              ::from_timestamp(state.last_sync, 0)
-              .unwrap_or_default()
-              .format("%Y-%m-%d %H:%M:%S UTC");
-            println!("  Last Sync: {}", last_sync);
-          }
-
-          if state.targets.is_empty() {
-            println!("  Targets: {}", style("None configured").yellow());
-          } else {
-            println!("  Targets: {} configured", state.targets.len());
-
-            // Show target status
-            for target in &state.targets {
-              let icon = match target.last_sync_status {
-                crate::types::SyncStatus::Success => CHECKMARK,
-                crate::types::SyncStatus::Failure => CROSS,
-              };
-              let target_name = format!("{}:{}", target.target_type, target.name);
-              println!("    {}{}", icon, target_name);
-
-              if let Some(ref error) = target.last_error {
-                println!("       {}", style(error).red());
-              }
-            }
-          }
-        }
-        Err(e) => {
-          println!("  {}", style(format!("Error reading sync state: {}", e)).red());
-        }
-      }
+  let sync = &report.sync;
+  match sync.last_sync {
+    None => println!("  Last Sync: {}", style("Never").yellow()),
+    Some(last_sync) => {
+      let last_sync = chrono::DateTime
+        ::from_timestamp(last_sync, 0)
+        .unwrap_or_default()
+        .format("%Y-%m-%d %H:%M:%S UTC");
+      println!("  Last Sync: {}", last_sync);
     }
-    Err(e) => {
-      println!("  {}", style(format!("Error initializing config manager: {}", e)).red());
+  }
+
+  if sync.disarmed {
+    println!("  {}", style("Disarmed after revoke (run 'rearm' to resume)").red());
+  }
+
+  if sync.targets.is_empty() {
+    println!("  Targets: {}", style("None configured").yellow());
+  } else {
+    println!("  Targets: {} configured", sync.targets.len());
+
+    // Show target status
+    for target in &sync.targets {
+      let icon = match target.last_sync_status {
+        crate::types::SyncStatus::Success => CHECKMARK,
+        crate::types::SyncStatus::Failure => CROSS,
+      };
+      let target_name = format!(
+        "{}:{}:{}",
+        target.provider,
+        target.target_type.to_lowercase(),
+        target.name
+      );
+      println!("    {}{}", icon, target_name);
+
+      if let Some(ref error) = target.last_error {
+        println!("       {}", style(error).red());
+      }
     }
   }
 
@@ -122,24 +204,15 @@ pub async fn handle_status() -> Result<()> {
 
   // Configuration
   println!("{}", style("Configuration:").bold());
-  match ConfigurationManager::new() {
-    Ok(config_manager) =>
-      match config_manager.load_config().await {
-        Ok(config) => {
-          println!("  Config Path: {}", config_manager.config_path().display());
-          println!("  Organizations: {}", config.github.organizations.len());
-          println!("  Repositories: {}", config.github.repositories.len());
-          let total_targets = config.github.organizations.len() + config.github.repositories.len();
-          println!("  Total Targets: {}", total_targets);
-        }
-        Err(e) => {
-          println!("  {}", style(format!("Error reading config: {}", e)).red());
-        }
-      }
-    Err(e) => {
-      println!("  {}", style(format!("Error initializing config manager: {}", e)).red());
-    }
+  if let Some(error) = &report.config.error {
+    println!("  {}", style(format!("Error reading config: {}", error)).red());
+    return;
   }
-
-  Ok(())
+  if let Some(path) = &report.config.path {
+    println!("  Config Path: {}", path);
+  }
+  for (provider, count) in &report.config.targets {
+    println!("  {}: {}", provider, count);
+  }
+  println!("  Total Targets: {}", report.config.targets.values().sum::<usize>());
 }
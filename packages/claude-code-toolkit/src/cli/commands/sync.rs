@@ -1,7 +1,16 @@
 use crate::{
+  cli::output::{
+    ExitStatus,
+    OutputFormat,
+    PruneReport,
+    StaleTargetReport,
+    SyncReport,
+    SyncStatusReport,
+  },
   config::manager::ConfigurationManager,
   error::*,
   sync::SyncService,
+  traits::SyncResult,
   utils::systemd::SystemdManager,
 };
 use console::{ Emoji, style };
@@ -11,100 +20,151 @@ static SUCCESS: Emoji<'_, '_> = Emoji("✅ ", "");
 static FAILURE: Emoji<'_, '_> = Emoji("❌ ", "");
 static INFO: Emoji<'_, '_> = Emoji("📊 ", "");
 static LOGS: Emoji<'_, '_> = Emoji("📜 ", "");
+static PRUNE: Emoji<'_, '_> = Emoji("🧹 ", "");
 
-pub async fn handle_sync_now() -> Result<()> {
-  println!("{}Starting sync...", SYNC);
+pub async fn handle_sync_now(output: OutputFormat) -> Result<()> {
+  if output.is_text() {
+    println!("{}Starting sync...", SYNC);
+  }
 
   let mut sync_service = SyncService::new_with_config().await?;
 
   match sync_service.check_and_sync_if_needed().await {
-    Ok(()) => {
-      println!("{}Sync operation completed", SUCCESS);
+    Ok(result) => {
+      let report = SyncReport {
+        synced: result.is_some(),
+        result: result.unwrap_or(SyncResult {
+          succeeded: 0,
+          failed: 0,
+          errors: Vec::new(),
+        }),
+      };
+      if !output.emit(&report)? {
+        println!("{}Sync operation completed", SUCCESS);
+        if report.synced {
+          println!("  Succeeded: {}, Failed: {}", report.result.succeeded, report.result.failed);
+        }
+      }
+      sync_exit_status(&report.result).exit();
     }
     Err(e) => {
       eprintln!("{}Sync failed: {}", FAILURE, e);
-      std::process::exit(1);
+      sync_error_exit_status(&e).exit();
     }
   }
 
   Ok(())
 }
 
-pub async fn handle_sync_force() -> Result<()> {
-  println!("{}Starting forced sync...", SYNC);
+pub async fn handle_sync_force(output: OutputFormat) -> Result<()> {
+  if output.is_text() {
+    println!("{}Starting forced sync...", SYNC);
+  }
 
   let mut sync_service = SyncService::new_with_config().await?;
 
   match sync_service.force_sync().await {
     Ok(result) => {
-      println!("{}Sync completed successfully", SUCCESS);
-      println!("  Succeeded: {}, Failed: {}", result.succeeded, result.failed);
+      let report = SyncReport { synced: true, result };
+      if !output.emit(&report)? {
+        println!("{}Sync completed successfully", SUCCESS);
+        println!("  Succeeded: {}, Failed: {}", report.result.succeeded, report.result.failed);
+      }
+      sync_exit_status(&report.result).exit();
     }
     Err(e) => {
       eprintln!("{}Sync failed: {}", FAILURE, e);
-      std::process::exit(1);
+      sync_error_exit_status(&e).exit();
     }
   }
 
   Ok(())
 }
 
-pub async fn handle_sync_status() -> Result<()> {
-  println!("{} {}", INFO, style("Sync Status").bold());
-  println!();
+/// Exit code after a completed sync run
+fn sync_exit_status(result: &SyncResult) -> ExitStatus {
+  if result.failed > 0 { ExitStatus::SyncFailed } else { ExitStatus::Success }
+}
 
+/// Exit code after a sync run that could not complete
+fn sync_error_exit_status(error: &ClaudeCodeError) -> ExitStatus {
+  match ExitStatus::from_error(error) {
+    ExitStatus::Error => ExitStatus::SyncFailed,
+    status => status,
+  }
+}
+
+pub async fn handle_sync_status(output: OutputFormat) -> Result<()> {
   let config_manager = ConfigurationManager::new()?;
-  let state = config_manager.load_state().await?;
+  let report = SyncStatusReport::from(config_manager.load_state().await?);
 
-  if state.last_sync == 0 {
-    println!("Last Sync: {}", style("Never").yellow());
+  let exit_status = if report.targets.is_empty() {
+    ExitStatus::NotConfigured
+  } else if report.failed_targets() > 0 {
+    ExitStatus::SyncFailed
   } else {
-    let last_sync = chrono::DateTime
-      ::from_timestamp(state.last_sync, 0)
-      .unwrap_or_default()
-      .format("%Y-%m-%d %H:%M:%S UTC");
-    let time_since_sync = chrono::Utc::now().timestamp() - state.last_sync;
-    let minutes_ago = time_since_sync / 60;
-
-    println!("Last Sync: {} ({} minutes ago)", last_sync, minutes_ago);
+    ExitStatus::Success
+  };
+
+  if !output.emit(&report)? {
+    print_sync_status(&report);
   }
 
+  exit_status.exit();
+  Ok(())
+}
+
+fn print_sync_status(report: &SyncStatusReport) {
+  println!("{} {}", INFO, style("Sync Status").bold());
   println!();
 
-  if state.targets.is_empty() {
+  match report.last_sync {
+    None => println!("Last Sync: {}", style("Never").yellow()),
+    Some(last_sync_time) => {
+      let last_sync = chrono::DateTime
+        ::from_timestamp(last_sync_time, 0)
+        .unwrap_or_default()
+        .format("%Y-%m-%d %H:%M:%S UTC");
+      let time_since_sync = chrono::Utc::now().timestamp() - last_sync_time;
+      let minutes_ago = time_since_sync / 60;
+
+      println!("Last Sync: {} ({} minutes ago)", last_sync, minutes_ago);
+    }
+  }
+
+  if report.disarmed {
+    println!("{}", style("Syncing is disarmed after revoke (run 'rearm' to resume)").red());
+  }
+
+  println!();
+
+  if report.targets.is_empty() {
     println!("{}", style("No sync targets configured").yellow());
     println!(
       "{}",
-      style("Use 'claude-code org add' or 'claude-code repo add' to add targets").dim()
+      style("Use 'claude-code target add <provider> <kind> <name>' to add targets").dim()
     );
   } else {
     println!("{}", style("Target Status:").bold());
     println!();
 
-    let success_count = state.targets
-      .iter()
-      .filter(|t| matches!(t.last_sync_status, crate::types::SyncStatus::Success))
-      .count();
-    let failure_count = state.targets.len() - success_count;
+    let failure_count = report.failed_targets();
+    let success_count = report.targets.len() - failure_count;
 
-    println!("Total Targets: {}", state.targets.len());
+    println!("Total Targets: {}", report.targets.len());
     println!("Successful: {}", style(success_count.to_string()).green());
     println!("Failed: {}", style(failure_count.to_string()).red());
 
     println!();
 
     // Show individual target status
-    for target in &state.targets {
+    for target in &report.targets {
       let icon = match target.last_sync_status {
         crate::types::SyncStatus::Success => SUCCESS,
         crate::types::SyncStatus::Failure => FAILURE,
       };
 
-      let target_type = match target.target_type {
-        crate::types::TargetType::Organization => "Organization",
-        crate::types::TargetType::Repository => "Repository",
-      };
-
+      let target_type = format!("{} {}", target.provider, target.target_type.to_lowercase());
       println!("{}{}: {}", icon, style(target_type).bold(), target.name);
 
       if target.last_sync_time > 0 {
@@ -122,7 +182,59 @@ pub async fn handle_sync_status() -> Result<()> {
       println!();
     }
   }
+}
+
+pub async fn handle_sync_prune(dry_run: bool, output: OutputFormat) -> Result<()> {
+  let mut sync_service = SyncService::new_with_config().await?;
+  let stale = sync_service.stale_secrets().await?;
+
+  let mut report = PruneReport {
+    dry_run,
+    stale: stale
+      .iter()
+      .map(|(target, names)| StaleTargetReport {
+        target: target.to_string(),
+        secrets: names.clone(),
+      })
+      .collect(),
+    result: None,
+  };
+
+  if output.is_text() {
+    if report.stale.is_empty() {
+      println!("{}No stale secrets to prune", SUCCESS);
+      return Ok(());
+    }
+
+    println!("{} {}", PRUNE, style("Stale secrets").bold());
+    for stale in &report.stale {
+      println!("  {}: {}", style(&stale.target).bold(), stale.secrets.join(", "));
+    }
+    println!();
+
+    if dry_run {
+      println!("{}", style("Dry run: nothing was deleted").dim());
+    }
+  }
+
+  if !dry_run && !report.stale.is_empty() {
+    report.result = Some(sync_service.prune().await?);
+  }
+
+  if !output.emit(&report)? {
+    if let Some(result) = &report.result {
+      if result.failed == 0 {
+        println!("{}Deleted {} secret(s)", SUCCESS, result.succeeded);
+      } else {
+        for error in &result.errors {
+          eprintln!("  {}", style(error).red());
+        }
+        eprintln!("{}Prune finished with {} failure(s)", FAILURE, result.failed);
+      }
+    }
+  }
 
+  report.result.as_ref().map_or(ExitStatus::Success, sync_exit_status).exit();
   Ok(())
 }
 
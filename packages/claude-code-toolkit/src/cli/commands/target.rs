@@ -0,0 +1,260 @@
+use crate::{
+  cli::output::{ ExitStatus, OutputFormat, TargetReport },
+  config::manager::ConfigurationManager,
+  error::*,
+  providers::ProviderFactory,
+  sync::SyncService,
+  traits::SecretMapping,
+  types::{ MappingOverrides, TargetConfig },
+};
+use console::{ Emoji, style };
+
+static SUCCESS: Emoji<'_, '_> = Emoji("✅ ", "");
+static INFO: Emoji<'_, '_> = Emoji("ℹ️ ", "");
+
+/// Target identity and settings given on the command line
+#[derive(Debug)]
+pub struct TargetArgs {
+  pub provider: String,
+  pub kind: String,
+  pub name: String,
+  pub options: Vec<String>,
+  pub profile: Option<String>,
+}
+
+/// Secret mapping overrides given on the command line
+#[derive(Debug, Default)]
+pub struct MappingArgs {
+  pub field_mappings: Vec<String>,
+  pub templates: Vec<String>,
+  pub include: Vec<String>,
+  pub exclude: Vec<String>,
+  pub prefix: Option<String>,
+  pub suffix: Option<String>,
+}
+
+impl MappingArgs {
+  fn into_overrides(self) -> Result<MappingOverrides> {
+    let templates: std::collections::HashMap<String, String> = parse_options(&self.templates)?
+      .into_iter()
+      .collect();
+    for template in templates.values() {
+      crate::utils::template::validate(template)?;
+    }
+
+    Ok(MappingOverrides {
+      field_mappings: parse_options(&self.field_mappings)?.into_iter().collect(),
+      templates,
+      include: self.include,
+      exclude: self.exclude,
+      prefix: self.prefix,
+      suffix: self.suffix,
+    })
+  }
+}
+
+/// Parse `key=value` option arguments
+fn parse_options(options: &[String]) -> Result<Vec<(String, String)>> {
+  options
+    .iter()
+    .map(|option| {
+      option
+        .split_once('=')
+        .filter(|(key, _)| !key.trim().is_empty())
+        .map(|(key, value)| (key.trim().to_string(), value.to_string()))
+        .ok_or_else(|| {
+          ClaudeCodeError::Validation(format!("Invalid option '{}', expected KEY=VALUE", option))
+        })
+    })
+    .collect()
+}
+
+pub async fn handle_add_target(args: TargetArgs, mapping: MappingArgs) -> Result<()> {
+  let TargetArgs { provider, kind, name, options, profile } = args;
+  let factory = ProviderFactory::new();
+  let mut available = factory.available_providers();
+  if !available.contains(&provider.as_str()) {
+    available.sort();
+    return Err(
+      ClaudeCodeError::Validation(
+        format!("Unknown provider '{}' (available: {})", provider, available.join(", "))
+      )
+    );
+  }
+
+  let mut target = TargetConfig::new(&provider, &kind, &name);
+  target.options.extend(parse_options(&options)?);
+  target.mapping = mapping.into_overrides()?;
+
+  let config_manager = ConfigurationManager::new()?;
+  if let Some(profile) = profile {
+    let config = config_manager.load_config().await?;
+    if config.profile(&profile).is_none() {
+      let available = config.profile_names().join(", ");
+      return Err(
+        ClaudeCodeError::Validation(
+          format!("Unknown profile '{}' (available: {})", profile, available)
+        )
+      );
+    }
+    target.profile = Some(profile);
+  }
+
+  println!("{}Adding {} {} {} for Claude secret sync", INFO, provider, kind, style(&name).bold());
+
+  config_manager.add_target(target.clone()).await?;
+
+  println!("{}Successfully added {} {} {}", SUCCESS, provider, kind, style(&name).bold());
+  // Show which secrets will be synced to this target
+  let config = config_manager.load_config().await?;
+  let secret_names = target_secret_names(&config, &target);
+  println!("{}", style(format!("Will sync: {}", secret_names.join(", "))).dim());
+  println!("{}", style("Run 'claude-code sync force' to sync immediately").dim());
+
+  Ok(())
+}
+
+/// Secret names a target receives after its mapping overrides are applied
+fn target_secret_names(config: &crate::types::Config, target: &TargetConfig) -> Vec<String> {
+  let Some(profile) = config.profile(target.profile_name()) else {
+    return Vec::new();
+  };
+
+  SecretMapping::from_config(profile).with_overrides(&target.mapping).secret_names()
+}
+
+/// Structured view of a configured target
+pub fn target_report(config: &crate::types::Config, target: &TargetConfig) -> TargetReport {
+  TargetReport {
+    provider: target.provider.clone(),
+    kind: target.kind.clone(),
+    name: target.name.clone(),
+    profile: target.profile_name().to_string(),
+    options: target.options.clone().into_iter().collect(),
+    secrets: target_secret_names(config, target),
+  }
+}
+
+pub async fn handle_remove_target(
+  provider: String,
+  kind: String,
+  name: String,
+  purge: bool
+) -> Result<()> {
+  println!("{}Removing {} {} {}", INFO, provider, kind, style(&name).bold());
+
+  let config_manager = ConfigurationManager::new()?;
+  config_manager.remove_target(&provider, &kind, &name).await?;
+
+  println!("{}Successfully removed {} {} {}", SUCCESS, provider, kind, style(&name).bold());
+
+  if purge {
+    purge_removed_target(&provider, &kind, &name).await?;
+  }
+
+  Ok(())
+}
+
+/// Delete the secrets sync has written to a target that was just removed
+pub async fn purge_removed_target(provider: &str, kind: &str, name: &str) -> Result<()> {
+  let mut sync_service = SyncService::new_with_config().await?;
+  let result = sync_service.purge_target(provider, kind, name).await?;
+
+  if result.succeeded == 0 && result.failed == 0 {
+    println!("{}", style("No synced secrets recorded for this target").dim());
+  } else if result.failed == 0 {
+    println!("{}Deleted {} synced secret(s)", SUCCESS, result.succeeded);
+  } else {
+    for error in &result.errors {
+      eprintln!("  {}", style(error).red());
+    }
+    return Err(
+      ClaudeCodeError::Provider(
+        "Failed to delete synced secrets; run 'claude-code sync prune' to retry".to_string()
+      )
+    );
+  }
+
+  Ok(())
+}
+
+pub async fn handle_list_targets(provider: Option<String>, output: OutputFormat) -> Result<()> {
+  let config_manager = ConfigurationManager::new()?;
+  let config = config_manager.load_config().await?;
+
+  let mut targets: Vec<&TargetConfig> = config.targets
+    .iter()
+    .filter(|t| provider.as_ref().is_none_or(|p| &t.provider == p))
+    .collect();
+  targets.sort_by(|a, b| (&a.provider, &a.kind, &a.name).cmp(&(&b.provider, &b.kind, &b.name)));
+
+  let reports: Vec<TargetReport> = targets
+    .iter()
+    .map(|t| target_report(&config, t))
+    .collect();
+  if output.emit(&reports)? {
+    if reports.is_empty() {
+      ExitStatus::NotConfigured.exit();
+    }
+    return Ok(());
+  }
+
+  if targets.is_empty() {
+    println!("{}", style("No targets configured").yellow());
+    println!(
+      "{}",
+      style("Use 'claude-code target add <provider> <kind> <name>' to add a target").dim()
+    );
+    ExitStatus::NotConfigured.exit();
+    return Ok(());
+  }
+
+  println!("{}", style("Configured Targets:").bold());
+
+  let mut current_provider = "";
+  for target in targets {
+    if target.provider != current_provider {
+      current_provider = &target.provider;
+      println!();
+      println!("  {}", style(current_provider).bold());
+    }
+
+    println!("    {} {}", style(&target.kind).dim(), style(&target.name).cyan());
+
+    if let Some(profile) = &target.profile {
+      println!("      {}", style(format!("profile={}", profile)).dim());
+    }
+
+    let mut options: Vec<_> = target.options.iter().collect();
+    options.sort();
+    for (key, value) in options {
+      println!("      {}", style(format!("{}={}", key, value)).dim());
+    }
+
+    if !target.mapping.is_empty() {
+      let secret_names = target_secret_names(&config, target);
+      println!("      {}", style(format!("Secrets: {}", secret_names.join(", "))).dim());
+    }
+  }
+
+  println!();
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_options() {
+    let options = vec!["method=PUT".to_string(), "header.X-Team=a=b".to_string()];
+    assert_eq!(parse_options(&options).unwrap(), vec![
+      ("method".to_string(), "PUT".to_string()),
+      ("header.X-Team".to_string(), "a=b".to_string())
+    ]);
+
+    assert!(parse_options(&["novalue".to_string()]).is_err());
+    assert!(parse_options(&["=value".to_string()]).is_err());
+  }
+}
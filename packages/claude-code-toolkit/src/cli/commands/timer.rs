@@ -1,13 +1,25 @@
-use crate::{ config::credentials::CredentialsManager, error::* };
+use crate::{
+  config::{ credentials::CredentialsManager, manager::ConfigurationManager },
+  error::*,
+};
 use console::{ Term, style };
 use std::time::Duration;
 use tokio::time::sleep;
 
-pub async fn handle_timer() -> Result<()> {
+pub async fn handle_timer(profile: Option<String>) -> Result<()> {
   let term = Term::stdout();
-  let credentials_manager = CredentialsManager::new()?;
+  let config = ConfigurationManager::new()?.load_config().await.unwrap_or_default();
+  let profile = profile.as_deref().unwrap_or(crate::types::DEFAULT_PROFILE);
+  let profile_config = config
+    .profile(profile)
+    .ok_or_else(|| ClaudeCodeError::InvalidConfig(format!("Unknown profile '{}'", profile)))?;
+  let credentials_manager = CredentialsManager::from_config(profile_config);
 
-  println!("{}", style("⏱️  Claude Code Session Timer").bold());
+  if profile == crate::types::DEFAULT_PROFILE {
+    println!("{}", style("⏱️  Claude Code Session Timer").bold());
+  } else {
+    println!("{}", style(format!("⏱️  Claude Code Session Timer ({})", profile)).bold());
+  }
   println!();
   println!("{}", style("Press Ctrl+C to exit").dim());
   println!();
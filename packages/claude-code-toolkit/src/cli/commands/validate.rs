@@ -0,0 +1,94 @@
+use crate::{
+  cli::output::{ ExitStatus, OutputFormat, ValidationReport },
+  config::manager::ConfigurationManager,
+  error::*,
+  traits::{ ValidationService, validation::{ ValidationSeverity, ValidationSummary } },
+  validation::ConfigValidator,
+};
+use console::{ Emoji, style };
+
+static CHECKMARK: Emoji<'_, '_> = Emoji("✅ ", "");
+static CROSS: Emoji<'_, '_> = Emoji("❌ ", "");
+static WARNING: Emoji<'_, '_> = Emoji("⚠️ ", "");
+static INFO: Emoji<'_, '_> = Emoji("ℹ️ ", "");
+
+pub async fn handle_validate(fix: bool, output: OutputFormat) -> Result<()> {
+  let config_manager = ConfigurationManager::new()?;
+  let validator = ConfigValidator::with_default_rules();
+
+  let mut fixed = Vec::new();
+  if fix {
+    // Fixes go to the configuration file; other layers are left to their owners
+    let mut config = config_manager.load_file().await?;
+    fixed = validator.auto_fix(&mut config).await?;
+    if !fixed.is_empty() {
+      config_manager.save_in_place(&config).await?;
+    }
+  }
+
+  let config = config_manager.load_config().await?;
+  let issues = validator.validate(&config).await?;
+  let report = ValidationReport {
+    summary: ValidationSummary::from_errors(&issues),
+    issues,
+    fixed,
+  };
+
+  if !output.emit(&report)? {
+    print_report(&report);
+  }
+
+  if !report.summary.is_valid {
+    ExitStatus::Error.exit();
+  }
+
+  Ok(())
+}
+
+fn print_report(report: &ValidationReport) {
+  for change in &report.fixed {
+    println!("{}{}", CHECKMARK, style(format!("Fixed: {}", change)).green());
+  }
+  if !report.fixed.is_empty() {
+    println!();
+  }
+
+  for issue in &report.issues {
+    let (icon, field) = match issue.severity {
+      ValidationSeverity::Error => (CROSS, style(&issue.field).red().bold()),
+      ValidationSeverity::Warning => (WARNING, style(&issue.field).yellow().bold()),
+      ValidationSeverity::Info => (INFO, style(&issue.field).bold()),
+    };
+    let fixable = if issue.auto_fixable {
+      style(" (fixable)").dim().to_string()
+    } else {
+      String::new()
+    };
+    println!("{}{}: {}{}", icon, field, issue.message, fixable);
+    if let Some(suggestion) = &issue.suggestion {
+      println!("   {}", style(suggestion).dim());
+    }
+  }
+
+  let summary = &report.summary;
+  if summary.total_issues == 0 {
+    println!("{}Configuration is valid", CHECKMARK);
+    return;
+  }
+
+  println!();
+  println!(
+    "{} errors, {} warnings, {} info",
+    summary.errors,
+    summary.warnings,
+    summary.info
+  );
+  if summary.auto_fixable > 0 {
+    println!(
+      "{}",
+      style(
+        format!("{} fixable, run 'claude-code-toolkit validate --fix'", summary.auto_fixable)
+      ).dim()
+    );
+  }
+}
@@ -12,9 +12,16 @@
 //! - **Status & Monitoring**: `status`, `timer` - Session information and real-time monitoring
 //! - **Organization Management**: `org add/remove/list` - GitHub organization configuration
 //! - **Repository Management**: `repo add/remove/list` - Individual repository setup
+//! - **Target Management**: `target add/remove/list` - Sync targets for any provider
 //! - **Synchronization**: `sync`, `sync force`, `sync status` - Credential sync operations
 //! - **Service Management**: `service install/start/stop/restart` - Daemon lifecycle
-//! - **Configuration**: `configure` - Interactive setup wizard
+//! - **Configuration**: `configure` - Interactive setup wizard; `validate`;
+//!   `config show/get/set/unset` - Configuration values; `config backups/restore/diff` -
+//!   Configuration snapshots
+//!
+//! Reporting commands accept the global `--output json|yaml` flag and exit with
+//! distinct codes for expired sessions, failed syncs and missing configuration
+//! (see [`output`]).
 //!
 //! ## Usage Examples
 //!
@@ -42,6 +49,25 @@
 //! claude-code-toolkit repo list
 //! ```
 //!
+//! ### Targets for Other Providers
+//!
+//! ```bash
+//! # Add an AWS Secrets Manager secret
+//! claude-code-toolkit target add aws secret claude/credentials
+//!
+//! # Add a webhook endpoint with per-target options
+//! claude-code-toolkit target add webhook endpoint https://broker.internal/v1/credentials \
+//!   --option method=PUT --option hmac_secret_env=BROKER_HMAC_KEY
+//!
+//! # Per-target secret names: only the access token, renamed
+//! claude-code-toolkit target add github organization other-org \
+//!   --map accessToken=ANTHROPIC_OAUTH_TOKEN --include accessToken
+//!
+//! # List and remove targets
+//! claude-code-toolkit target list --provider aws
+//! claude-code-toolkit target remove aws secret claude/credentials
+//! ```
+//!
 //! ### Credential Synchronization
 //!
 //! ```bash
@@ -84,8 +110,10 @@
 //! ```
 
 pub mod commands;
+pub mod output;
 
 use clap::{ Parser, Subcommand };
+use output::OutputFormat;
 
 /// Main CLI structure for the Claude Code Toolkit.
 ///
@@ -96,6 +124,18 @@ use clap::{ Parser, Subcommand };
 #[command(about = "Claude Code Toolkit for credential sync and session monitoring")]
 #[command(version = env!("CARGO_PKG_VERSION"))]
 pub struct Cli {
+  /// Output format of reporting commands (status, sync, list, revoke)
+  #[arg(long, global = true, value_enum, default_value_t)]
+  pub output: OutputFormat,
+
+  /// Configuration file to use instead of the XDG default
+  #[arg(long, global = true, value_name = "FILE", env = "CCT_CONFIG")]
+  pub config: Option<std::path::PathBuf>,
+
+  /// Directory for the sync state instead of the XDG default
+  #[arg(long, global = true, value_name = "DIR", env = "CCT_STATE_DIR")]
+  pub state_dir: Option<std::path::PathBuf>,
+
   /// The subcommand to execute
   #[command(subcommand)]
   pub command: Commands,
@@ -122,13 +162,19 @@ pub enum Commands {
   /// Displays a live countdown timer showing time remaining
   /// in the current Claude Code session. Updates every second
   /// until session expires.
-  Timer,
+  Timer {
+    /// Credential profile to watch (defaults to the `credentials` section)
+    #[arg(short, long)]
+    profile: Option<String>,
+  },
 
   /// Run as daemon (used by systemd)
   ///
   /// Starts the background daemon service for automatic
   /// credential synchronization. This command is typically
-  /// called by systemd and not run directly by users.
+  /// called by systemd and not run directly by users. The
+  /// daemon reloads its configuration when config.yml changes
+  /// and on SIGHUP.
   Daemon,
 
   /// Organization management
@@ -145,6 +191,13 @@ pub enum Commands {
   #[command(subcommand)]
   Repo(RepoCommands),
 
+  /// Sync target management
+  ///
+  /// Commands for managing sync targets of any registered provider
+  /// (GitHub, Bitbucket, AWS, keyring, webhook, plugins).
+  #[command(subcommand)]
+  Target(Box<TargetCommands>),
+
   /// Sync credentials to all configured targets (smart - only if changed)
   ///
   /// Performs credential synchronization with intelligent change detection.
@@ -162,11 +215,100 @@ pub enum Commands {
   #[command(subcommand)]
   Service(ServiceCommands),
 
+  /// Emergency: delete every synced secret on every target
+  ///
+  /// For a lost laptop or a leaked token. Disarms syncing first, so the
+  /// daemon stops re-syncing, then deletes (or tombstones) every secret
+  /// recorded in the sync state, and reports what was removed, what failed
+  /// and which configured targets have no recorded secrets to revoke.
+  /// Syncing stays disarmed until 'rearm'.
+  Revoke {
+    /// Overwrite secrets with a tombstone value instead of deleting them
+    #[arg(long)]
+    tombstone: bool,
+
+    /// Skip the confirmation prompt
+    #[arg(short, long)]
+    yes: bool,
+
+    /// Also write the report as JSON to this file
+    #[arg(long)]
+    report: Option<String>,
+  },
+
+  /// Re-enable syncing after 'revoke'
+  Rearm,
+
+  /// Read and edit configuration values, and manage snapshots
+  ///
+  /// Every configuration change first snapshots the previous
+  /// configuration and sync state under backups/ in the config directory.
+  #[command(subcommand)]
+  Config(ConfigCommands),
+
+  /// Check the configuration and credentials for problems
+  ///
+  /// Reports unknown mapping fields, duplicate or invalid secret names,
+  /// unreachable targets, expired credentials, missing scopes and an
+  /// unknown log level, each with a suggestion. Exits with 1 when any
+  /// error is found.
+  Validate {
+    /// Fix what can be fixed automatically and save the configuration
+    #[arg(long)]
+    fix: bool,
+  },
+
   /// Interactive configuration wizard
   ///
   /// Launches an interactive setup process to configure
   /// GitHub integration, sync settings, and service options.
-  Configure,
+  /// With --non-interactive, settings come from flags and a seed
+  /// file instead; rerunning converges rather than failing.
+  Configure {
+    /// Never prompt: apply flags and --seed, failing on invalid input
+    #[arg(long)]
+    non_interactive: bool,
+
+    /// Only run these wizard steps (prerequisites, credentials, targets, mappings, daemon)
+    #[arg(long = "step", value_name = "STEP", conflicts_with = "non_interactive")]
+    steps: Vec<String>,
+
+    /// Start over instead of resuming an interrupted wizard
+    #[arg(long, conflicts_with = "non_interactive")]
+    restart: bool,
+
+    /// Seed file: YAML, or KEY=VALUE lines when named *.env
+    #[arg(long, value_name = "FILE", requires = "non_interactive")]
+    seed: Option<String>,
+
+    /// GitHub organization to sync to (repeatable)
+    #[arg(long = "org", value_name = "NAME", requires = "non_interactive")]
+    orgs: Vec<String>,
+
+    /// GitHub repository to sync to, as owner/repo (repeatable)
+    #[arg(long = "repo", value_name = "OWNER/REPO", requires = "non_interactive")]
+    repos: Vec<String>,
+
+    /// Credential field to secret name mapping (repeatable)
+    #[arg(long = "map", value_name = "FIELD=SECRET", requires = "non_interactive")]
+    field_mappings: Vec<String>,
+
+    /// Credential file to read
+    #[arg(long, value_name = "PATH", requires = "non_interactive")]
+    credentials_file: Option<String>,
+
+    /// Daemon log level (trace, debug, info, warn, error)
+    #[arg(long, requires = "non_interactive")]
+    log_level: Option<String>,
+
+    /// Seconds the daemon waits after token expiry before syncing
+    #[arg(long, value_name = "SECONDS", requires = "non_interactive")]
+    sync_delay: Option<u64>,
+
+    /// Install and start the sync daemon
+    #[arg(long, requires = "non_interactive")]
+    install_daemon: bool,
+  },
 }
 
 /// GitHub organization management commands.
@@ -192,6 +334,10 @@ pub enum OrgCommands {
   Remove {
     /// GitHub organization name to remove
     name: String,
+
+    /// Also delete the secrets previously synced to the organization
+    #[arg(long)]
+    purge: bool,
   },
 
   /// List configured organizations
@@ -223,6 +369,10 @@ pub enum RepoCommands {
   Remove {
     /// Repository in format "owner/repository-name" to remove
     repo: String,
+
+    /// Also delete the secrets previously synced to the repository
+    #[arg(long)]
+    purge: bool,
   },
 
   /// List configured repositories
@@ -232,6 +382,83 @@ pub enum RepoCommands {
   List,
 }
 
+/// Provider-agnostic sync target management commands.
+///
+/// Targets are identified by provider, kind and name, e.g.
+/// `github organization my-org` or `aws secret claude/credentials`.
+#[derive(Subcommand)]
+pub enum TargetCommands {
+  /// Add a sync target for any registered provider
+  ///
+  /// The kind is provider specific (organization, repository, workspace,
+  /// deployment, secret, parameter, collection, endpoint, ...).
+  Add {
+    /// Provider name (e.g., "github", "bitbucket", "aws", or a plugin name)
+    provider: String,
+
+    /// Target kind (e.g., "repository", "secret", "endpoint")
+    kind: String,
+
+    /// Target name (e.g., "owner/repo", "claude/credentials", a URL)
+    name: String,
+
+    /// Provider-specific option in key=value form (repeatable)
+    #[arg(short, long = "option", value_name = "KEY=VALUE")]
+    options: Vec<String>,
+
+    /// Credential profile synced to this target (defaults to the `credentials` section)
+    #[arg(long)]
+    profile: Option<String>,
+
+    /// Override the secret name for a credential field (repeatable)
+    #[arg(long = "map", value_name = "FIELD=SECRET_NAME")]
+    field_mappings: Vec<String>,
+
+    /// Add a templated secret, e.g. 'CLAUDE_EXPIRES_ISO={{ expiresAt | iso8601 }}' (repeatable)
+    #[arg(long = "template", value_name = "SECRET_NAME=TEMPLATE")]
+    templates: Vec<String>,
+
+    /// Only sync these credential fields or templated secrets (repeatable)
+    #[arg(long, value_name = "FIELD")]
+    include: Vec<String>,
+
+    /// Never sync these credential fields or templated secrets (repeatable)
+    #[arg(long, value_name = "FIELD")]
+    exclude: Vec<String>,
+
+    /// Prefix added to every secret name for this target
+    #[arg(long)]
+    prefix: Option<String>,
+
+    /// Suffix added to every secret name for this target
+    #[arg(long)]
+    suffix: Option<String>,
+  },
+
+  /// Remove a sync target
+  Remove {
+    /// Provider name
+    provider: String,
+
+    /// Target kind
+    kind: String,
+
+    /// Target name
+    name: String,
+
+    /// Also delete the secrets previously synced to the target
+    #[arg(long)]
+    purge: bool,
+  },
+
+  /// List configured sync targets
+  List {
+    /// Only show targets for this provider
+    #[arg(short, long)]
+    provider: Option<String>,
+  },
+}
+
 /// Credential synchronization commands.
 ///
 /// These commands control when and how credentials are synchronized
@@ -263,6 +490,17 @@ pub enum SyncCommands {
     #[arg(short, long, default_value = "50")]
     lines: usize,
   },
+
+  /// Delete previously synced secrets that are no longer wanted
+  ///
+  /// Removes secrets from targets that were dropped from the configuration,
+  /// and secrets whose names are no longer produced by a target's mapping.
+  /// Only secrets recorded in the sync state are ever deleted.
+  Prune {
+    /// Only list what would be deleted
+    #[arg(long)]
+    dry_run: bool,
+  },
 }
 
 /// Background service management commands.
@@ -321,3 +559,109 @@ pub enum ServiceCommands {
   /// The service can still be started manually when needed.
   Disable,
 }
+
+/// Configuration value and snapshot commands.
+///
+/// Values are addressed by dotted paths such as `daemon.log_level`, with
+/// indexes for targets (`targets.0.profile`). Reads show the effective
+/// configuration; edits change the user config.yml only, keeping its
+/// comments and key order. Snapshots are taken automatically before every
+/// configuration change and kept within the limits of the `backups`
+/// configuration section.
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+  /// Print the effective configuration
+  ///
+  /// The effective configuration merges built-in defaults, the system file,
+  /// the user file, a project .claude-code-toolkit.yml and CCT_* environment
+  /// variables, in that order.
+  Show {
+    /// List every value with the layer and file or variable it came from
+    #[arg(long)]
+    origin: bool,
+  },
+
+  /// Print a configuration value
+  Get {
+    /// Dotted path, e.g. notifications.session_warnings
+    path: String,
+  },
+
+  /// Set a configuration value
+  ///
+  /// The value is read as YAML, so numbers, booleans and lists such as
+  /// '[30, 15, 5]' keep their type. The change is validated before saving.
+  Set {
+    /// Dotted path, e.g. daemon.log_level
+    path: String,
+
+    /// New value
+    value: String,
+  },
+
+  /// Remove a configuration value, restoring its default where it has one
+  Unset {
+    /// Dotted path, e.g. providers.aws.region
+    path: String,
+  },
+
+  /// List configuration snapshots, newest last
+  Backups,
+
+  /// Restore the configuration from a snapshot
+  ///
+  /// The current configuration is snapshotted first, so the restore
+  /// itself can be undone with 'config restore latest'.
+  Restore {
+    /// Snapshot id (see 'config backups'), or 'latest'
+    id: String,
+
+    /// Also restore the sync state recorded with the snapshot
+    #[arg(long)]
+    state: bool,
+  },
+
+  /// Show the changes from a snapshot to the current configuration
+  Diff {
+    /// Snapshot id (see 'config backups'), or 'latest'
+    id: String,
+  },
+
+  /// Upgrade config.yml to the current schema version
+  ///
+  /// Older files are upgraded when they are loaded as well; this command
+  /// shows the changes first and also records the version in files that
+  /// are current but lack a 'version:' key.
+  Migrate {
+    /// Show the changes without writing them
+    #[arg(long)]
+    dry_run: bool,
+  },
+
+  /// Write a portable bundle of the configuration for teammates
+  ///
+  /// The bundle has the targets, mappings, templates, provider settings,
+  /// sync delay and notifications, but no credential file paths, local
+  /// paths or secrets.
+  Export {
+    /// Bundle file to write (standard output when omitted)
+    file: Option<std::path::PathBuf>,
+  },
+
+  /// Merge a bundle from a file or URL into the configuration
+  ///
+  /// Settings and targets the configuration lacks are added. Values that
+  /// differ are reported as conflicts and keep their local value.
+  Import {
+    /// Bundle file, e.g. from a team repository, or https URL
+    source: String,
+
+    /// Take the bundle's value on conflicts
+    #[arg(long)]
+    overwrite: bool,
+
+    /// Show the changes without writing them
+    #[arg(long)]
+    dry_run: bool,
+  },
+}
@@ -0,0 +1,244 @@
+//! Machine-readable command output and process exit codes.
+//!
+//! Reporting commands (`status`, `sync`, `sync status`, `sync prune`, `org list`,
+//! `repo list`, `target list`, `revoke`, `validate`) build one of the report types below. With
+//! `--output json` or `--output yaml` the report is printed as-is, so scripts and
+//! dashboards never have to scrape the styled text. The field names are stable.
+//!
+//! ## Exit Codes
+//!
+//! | Code | Meaning                                                      |
+//! |------|--------------------------------------------------------------|
+//! | 0    | Success                                                      |
+//! | 1    | Any other error, or `validate` found errors                  |
+//! | 2    | A Claude session is expired                                  |
+//! | 3    | A sync (or a target's last sync) failed                      |
+//! | 4    | Not configured: no targets, or credentials cannot be read    |
+
+use crate::error::{ ClaudeCodeError, Result };
+use crate::traits::SyncResult;
+use crate::traits::validation::{ ValidationError, ValidationSummary };
+use crate::types::{ SyncState, SyncStatus, TargetStatus };
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Output format selected with the global `--output` flag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum OutputFormat {
+  /// Human-readable, styled text
+  #[default]
+  Text,
+  Json,
+  Yaml,
+}
+
+impl OutputFormat {
+  pub fn is_text(self) -> bool {
+    self == OutputFormat::Text
+  }
+
+  /// Print a report as JSON or YAML; returns false in text mode, where the caller renders it
+  pub fn emit<T: Serialize>(self, report: &T) -> Result<bool> {
+    match self {
+      OutputFormat::Text => Ok(false),
+      OutputFormat::Json => {
+        println!("{}", serde_json::to_string_pretty(report)?);
+        Ok(true)
+      }
+      OutputFormat::Yaml => {
+        print!("{}", serde_yaml::to_string(report)?);
+        Ok(true)
+      }
+    }
+  }
+}
+
+/// Process exit codes, see the module documentation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitStatus {
+  Success = 0,
+  Error = 1,
+  Expired = 2,
+  SyncFailed = 3,
+  NotConfigured = 4,
+}
+
+impl ExitStatus {
+  pub fn code(self) -> i32 {
+    self as i32
+  }
+
+  /// Exit code for an error that aborted a command
+  pub fn from_error(error: &ClaudeCodeError) -> Self {
+    match error {
+      ClaudeCodeError::CredentialsNotFound { .. } | ClaudeCodeError::TargetNotFound { .. } => {
+        ExitStatus::NotConfigured
+      }
+      _ => ExitStatus::Error,
+    }
+  }
+
+  /// Terminate the process unless this is [`ExitStatus::Success`]
+  pub fn exit(self) {
+    if self != ExitStatus::Success {
+      std::process::exit(self.code());
+    }
+  }
+}
+
+/// Session of one credential profile
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionReport {
+  pub profile: String,
+  pub subscription_type: Option<String>,
+  /// Expiry in milliseconds since the epoch
+  pub expires_at: Option<i64>,
+  pub time_remaining_ms: Option<i64>,
+  pub expired: bool,
+  pub error: Option<String>,
+}
+
+/// Background daemon state
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceReport {
+  pub running: Option<bool>,
+  pub error: Option<String>,
+}
+
+/// A configured sync target
+#[derive(Debug, Clone, Serialize)]
+pub struct TargetReport {
+  pub provider: String,
+  pub kind: String,
+  pub name: String,
+  pub profile: String,
+  pub options: BTreeMap<String, String>,
+  pub secrets: Vec<String>,
+}
+
+/// Persisted sync state
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncStatusReport {
+  /// Seconds since the epoch, absent if never synced
+  pub last_sync: Option<i64>,
+  /// Syncing is disarmed after a `revoke`
+  pub disarmed: bool,
+  pub targets: Vec<TargetStatus>,
+}
+
+impl From<SyncState> for SyncStatusReport {
+  fn from(state: SyncState) -> Self {
+    Self {
+      last_sync: Some(state.last_sync).filter(|t| *t > 0),
+      disarmed: state.revoked_at.is_some(),
+      targets: state.targets,
+    }
+  }
+}
+
+impl SyncStatusReport {
+  pub fn failed_targets(&self) -> usize {
+    self.targets
+      .iter()
+      .filter(|t| matches!(t.last_sync_status, SyncStatus::Failure))
+      .count()
+  }
+}
+
+/// Configuration summary
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigReport {
+  pub path: Option<String>,
+  /// Number of targets per provider
+  pub targets: BTreeMap<String, usize>,
+  pub error: Option<String>,
+}
+
+/// Everything `status` shows
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusReport {
+  pub sessions: Vec<SessionReport>,
+  pub service: ServiceReport,
+  pub sync: SyncStatusReport,
+  pub config: ConfigReport,
+}
+
+impl StatusReport {
+  pub fn exit_status(&self) -> ExitStatus {
+    let total_targets: usize = self.config.targets.values().sum();
+    if total_targets == 0 || self.sessions.iter().any(|s| s.error.is_some()) {
+      ExitStatus::NotConfigured
+    } else if self.sessions.iter().any(|s| s.expired) {
+      ExitStatus::Expired
+    } else if self.sync.failed_targets() > 0 {
+      ExitStatus::SyncFailed
+    } else {
+      ExitStatus::Success
+    }
+  }
+}
+
+/// Outcome of `sync` and `sync force`
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncReport {
+  /// False when credentials were unchanged or syncing is disarmed
+  pub synced: bool,
+  #[serde(flatten)]
+  pub result: SyncResult,
+}
+
+/// Secrets `sync prune` deletes (or would delete) from one target
+#[derive(Debug, Clone, Serialize)]
+pub struct StaleTargetReport {
+  pub target: String,
+  pub secrets: Vec<String>,
+}
+
+/// Outcome of `sync prune`
+#[derive(Debug, Clone, Serialize)]
+pub struct PruneReport {
+  pub dry_run: bool,
+  pub stale: Vec<StaleTargetReport>,
+  pub result: Option<SyncResult>,
+}
+
+/// Outcome of `validate`
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationReport {
+  #[serde(flatten)]
+  pub summary: ValidationSummary,
+  pub issues: Vec<ValidationError>,
+  /// Changes made by `--fix`
+  pub fixed: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_status_exit_codes() {
+    let session = SessionReport {
+      profile: "default".to_string(),
+      subscription_type: Some("pro".to_string()),
+      expires_at: Some(0),
+      time_remaining_ms: Some(0),
+      expired: true,
+      error: None,
+    };
+    let mut report = StatusReport {
+      sessions: vec![session],
+      service: ServiceReport { running: Some(true), error: None },
+      sync: SyncState::default().into(),
+      config: ConfigReport { path: None, targets: BTreeMap::new(), error: None },
+    };
+    assert_eq!(report.exit_status(), ExitStatus::NotConfigured);
+
+    report.config.targets.insert("github".to_string(), 1);
+    assert_eq!(report.exit_status(), ExitStatus::Expired);
+    assert_eq!(ExitStatus::Expired.code(), 2);
+
+    report.sessions[0].expired = false;
+    assert_eq!(report.exit_status(), ExitStatus::Success);
+  }
+}
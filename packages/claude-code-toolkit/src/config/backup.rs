@@ -0,0 +1,221 @@
+//! Versioned snapshots of the configuration and sync state
+//!
+//! Before every configuration change the current `config.yml` (and the sync state, when
+//! there is one) is copied verbatim into `backups/<id>/` next to the configuration file.
+//! Snapshot ids are `backup_<milliseconds since the epoch>`, so they sort by age. The
+//! `backups` section of the configuration limits how many snapshots are kept:
+//!
+//! ```yaml
+//! backups:
+//!   keep: 20          # 0 disables snapshots
+//!   max_age_days: 90  # 0 keeps snapshots regardless of age
+//! ```
+
+use crate::error::{ ClaudeCodeError, Result };
+use crate::types::BackupConfig;
+use serde::Serialize;
+use std::path::{ Path, PathBuf };
+use tracing::debug;
+
+/// Directory holding the snapshots, next to the configuration file
+pub const BACKUPS_DIR: &str = "backups";
+
+/// Alias for the newest snapshot
+pub const LATEST: &str = "latest";
+
+const CONFIG_FILE: &str = "config.yml";
+const STATE_FILE: &str = "sync-state.json";
+const ID_PREFIX: &str = "backup_";
+
+/// One snapshot
+#[derive(Debug, Clone, Serialize)]
+pub struct Backup {
+  pub id: String,
+  /// Milliseconds since the epoch
+  pub created_at: i64,
+  /// The snapshot includes the sync state
+  pub has_state: bool,
+  #[serde(skip)]
+  pub path: PathBuf,
+}
+
+impl Backup {
+  pub fn config_path(&self) -> PathBuf {
+    self.path.join(CONFIG_FILE)
+  }
+
+  pub fn state_path(&self) -> PathBuf {
+    self.path.join(STATE_FILE)
+  }
+
+  /// The configuration file as it was
+  pub fn read_config(&self) -> Result<String> {
+    Ok(std::fs::read_to_string(self.config_path())?)
+  }
+}
+
+/// Snapshots in one directory
+pub struct BackupStore {
+  dir: PathBuf,
+}
+
+impl BackupStore {
+  pub fn new(dir: PathBuf) -> Self {
+    Self { dir }
+  }
+
+  pub fn dir(&self) -> &Path {
+    &self.dir
+  }
+
+  /// All snapshots, oldest first
+  pub fn list(&self) -> Result<Vec<Backup>> {
+    let entries = match std::fs::read_dir(&self.dir) {
+      Ok(entries) => entries,
+      Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+        return Ok(Vec::new());
+      }
+      Err(e) => {
+        return Err(e.into());
+      }
+    };
+
+    let mut backups: Vec<Backup> = entries
+      .filter_map(|entry| entry.ok())
+      .filter_map(|entry| {
+        let id = entry.file_name().to_string_lossy().to_string();
+        let created_at = id.strip_prefix(ID_PREFIX)?.parse().ok()?;
+        let path = entry.path();
+        path.join(CONFIG_FILE).is_file().then(|| Backup {
+          has_state: path.join(STATE_FILE).is_file(),
+          id,
+          created_at,
+          path,
+        })
+      })
+      .collect();
+    backups.sort_by_key(|b| b.created_at);
+    Ok(backups)
+  }
+
+  /// Snapshot by id, or the newest one for [`LATEST`]
+  pub fn get(&self, id: &str) -> Result<Backup> {
+    let backups = self.list()?;
+    let backup = if id == LATEST {
+      backups.into_iter().last()
+    } else {
+      backups.into_iter().find(|b| b.id == id)
+    };
+
+    backup.ok_or_else(|| {
+      ClaudeCodeError::Validation(
+        format!("No backup '{}', see 'claude-code-toolkit config backups'", id)
+      )
+    })
+  }
+
+  /// Copy the configuration file (and sync state) into a new snapshot
+  ///
+  /// Nothing is written when the configuration file does not exist or is unchanged since
+  /// the newest snapshot; `None` is returned then.
+  pub fn snapshot(&self, config_path: &Path, state_path: Option<&Path>) -> Result<Option<Backup>> {
+    let Ok(content) = std::fs::read_to_string(config_path) else {
+      return Ok(None);
+    };
+    if let Some(latest) = self.list()?.pop() {
+      if latest.read_config().ok().as_deref() == Some(content.as_str()) {
+        debug!("Configuration unchanged since backup {}", latest.id);
+        return Ok(None);
+      }
+    }
+
+    let mut created_at = chrono::Utc::now().timestamp_millis();
+    let path = loop {
+      let path = self.dir.join(format!("{}{}", ID_PREFIX, created_at));
+      if !path.exists() {
+        break path;
+      }
+      created_at += 1;
+    };
+
+    std::fs::create_dir_all(&path)?;
+    std::fs::write(path.join(CONFIG_FILE), &content)?;
+    let has_state = match state_path {
+      Some(state_path) if state_path.is_file() => {
+        std::fs::copy(state_path, path.join(STATE_FILE))?;
+        true
+      }
+      _ => false,
+    };
+
+    let backup = Backup {
+      id: format!("{}{}", ID_PREFIX, created_at),
+      created_at,
+      has_state,
+      path,
+    };
+    debug!("Backed up configuration as {}", backup.id);
+    Ok(Some(backup))
+  }
+
+  /// Delete snapshots beyond the retention limits, returning their ids
+  ///
+  /// The newest snapshot is never deleted for its age.
+  pub fn prune(&self, retention: &BackupConfig) -> Result<Vec<String>> {
+    let mut backups = self.list()?;
+    let cutoff = (retention.max_age_days > 0).then(|| {
+      chrono::Utc::now().timestamp_millis() - (retention.max_age_days as i64) * 86_400_000
+    });
+
+    let keep_from = backups.len().saturating_sub(retention.keep);
+    let newest = backups.pop();
+    let mut pruned = Vec::new();
+    for (i, backup) in backups.into_iter().enumerate() {
+      if i < keep_from || cutoff.is_some_and(|cutoff| backup.created_at < cutoff) {
+        std::fs::remove_dir_all(&backup.path)?;
+        pruned.push(backup.id);
+      }
+    }
+    if retention.keep == 0 {
+      if let Some(newest) = newest {
+        std::fs::remove_dir_all(&newest.path)?;
+        pruned.push(newest.id);
+      }
+    }
+
+    if !pruned.is_empty() {
+      debug!("Pruned backups {}", pruned.join(", "));
+    }
+    Ok(pruned)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use tempfile::TempDir;
+
+  #[test]
+  fn test_snapshot_and_retention() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.yml");
+    let store = BackupStore::new(temp_dir.path().join(BACKUPS_DIR));
+
+    assert!(store.snapshot(&config_path, None).unwrap().is_none());
+
+    for version in 1..=4 {
+      std::fs::write(&config_path, format!("# version {}\n", version)).unwrap();
+      assert!(store.snapshot(&config_path, None).unwrap().is_some());
+      // Unchanged configuration is not snapshotted twice
+      assert!(store.snapshot(&config_path, None).unwrap().is_none());
+    }
+
+    let retention = BackupConfig { keep: 2, max_age_days: 0 };
+    assert_eq!(store.prune(&retention).unwrap().len(), 2);
+    let backups = store.list().unwrap();
+    assert_eq!(backups.len(), 2);
+    assert_eq!(store.get(LATEST).unwrap().read_config().unwrap(), "# version 4\n");
+    assert_eq!(store.get(&backups[0].id).unwrap().read_config().unwrap(), "# version 3\n");
+    assert!(store.get("backup_0").is_err());
+  }
+}
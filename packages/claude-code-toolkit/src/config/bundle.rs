@@ -0,0 +1,446 @@
+//! Portable configuration bundles for sharing a setup with teammates
+//!
+//! A bundle is a YAML file with the parts of `config.yml` that mean the same on every
+//! machine: targets, secret mappings and templates, provider settings, the sync delay
+//! and notifications. It is marked with a `bundle:` format key and the schema `version:`.
+//!
+//! Left out are the paths of credential files, backup retention, the log level, and
+//! provider settings or target options that hold secrets or local paths: keys such as
+//! `command`, `client_key` or `header.Authorization`, and values such as `~/certs/ca.pem`.
+//! Settings naming an environment variable (`*_env`) are kept, the variable itself is
+//! not. The same parts are left out of an imported file, so a whole `config.yml`
+//! checked into a team repository can be imported as well.
+//!
+//! [`merge`] adds what the bundle has and the local configuration lacks or leaves at its
+//! default. Values both set but differ in are conflicts: the local value is kept unless
+//! the import overwrites.
+
+use crate::config::{ edit, migrations };
+use crate::error::{ ClaudeCodeError, Result };
+use crate::types::{ Config, TargetConfig };
+use serde::Serialize;
+use serde_yaml::{ Mapping, Value };
+
+/// Format of bundles written by this build
+pub const BUNDLE_FORMAT: u32 = 1;
+
+/// Sections of the configuration a bundle carries
+const SECTIONS: [&str; 6] = [
+  "daemon",
+  "notifications",
+  "credentials",
+  "profiles",
+  "providers",
+  "targets",
+];
+
+/// Settings that only apply to one machine
+const LOCAL_PATHS: [&str; 2] = ["daemon.log_level", "credentials.file_path"];
+
+/// Provider settings and target options that only apply to one machine
+const LOCAL_KEYS: [&str; 5] = ["command", "profile", "client_cert", "client_key", "ca_cert"];
+
+/// Parts of option and setting keys that suggest a secret value
+const SECRET_WORDS: [&str; 6] = ["authorization", "cookie", "key", "password", "secret", "token"];
+
+/// A setting or target the import adds or changes
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportChange {
+  pub path: String,
+  pub value: Value,
+  /// Local value the change replaces
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub replaced: Option<Value>,
+  #[serde(skip)]
+  keys: Vec<String>,
+}
+
+/// A setting or target whose local value differs from the bundle's
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportConflict {
+  pub path: String,
+  pub local: Value,
+  pub incoming: Value,
+}
+
+/// Local configuration with a bundle merged in
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Merge {
+  pub changes: Vec<ImportChange>,
+  /// Conflicts that kept their local value
+  pub conflicts: Vec<ImportConflict>,
+  /// Merged targets, when they changed
+  #[serde(skip)]
+  targets: Option<Value>,
+}
+
+impl Merge {
+  /// Edits that apply the changes to a raw document, such as the contents of `config.yml`
+  pub fn edits(&self) -> Vec<(Vec<String>, Value)> {
+    let mut edits: Vec<(Vec<String>, Value)> = self.changes
+      .iter()
+      .filter(|c| c.keys != ["targets"])
+      .map(|c| (c.keys.clone(), c.value.clone()))
+      .collect();
+    if let Some(targets) = &self.targets {
+      edits.push((vec!["targets".to_string()], targets.clone()));
+    }
+    edits
+  }
+}
+
+/// Bundle of the portable parts of `config`
+///
+/// Returns the bundle and the paths of the settings left out.
+pub fn export(config: &Config) -> Result<(Value, Vec<String>)> {
+  let mut document = serde_yaml::to_value(config)?;
+  let excluded = portable(&mut document);
+
+  let mut bundle = Mapping::new();
+  bundle.insert(Value::from("bundle"), Value::from(BUNDLE_FORMAT));
+  bundle.insert(Value::from("version"), Value::from(config.version));
+  if let Value::Mapping(sections) = document {
+    for section in SECTIONS {
+      if let Some(value) = sections.get(section).filter(|v| !is_empty(v)) {
+        bundle.insert(Value::from(section), value.clone());
+      }
+    }
+  }
+  Ok((Value::Mapping(bundle), excluded))
+}
+
+/// Portable parts of a bundle or configuration file, upgraded to the current schema
+pub fn parse(content: &str) -> Result<Value> {
+  let mut document: Value = serde_yaml
+    ::from_str(content)
+    .map_err(|e| ClaudeCodeError::InvalidConfig(format!("Invalid bundle: {}", e)))?;
+  let Some(root) = document.as_mapping_mut() else {
+    return Err(ClaudeCodeError::InvalidConfig("Invalid bundle: not a mapping".to_string()));
+  };
+
+  if let Some(format) = root.remove("bundle") {
+    let format = format.as_u64().unwrap_or(u64::MAX);
+    if format > u64::from(BUNDLE_FORMAT) {
+      return Err(
+        ClaudeCodeError::InvalidConfig(
+          format!("Bundle format {} is newer than this build supports ({})", format, BUNDLE_FORMAT)
+        )
+      );
+    }
+  }
+  migrations::check_version(migrations::version_of(&document)?)?;
+  migrations::migrate(&mut document)?;
+
+  let mut sections = Mapping::new();
+  if let Value::Mapping(root) = document {
+    for section in SECTIONS {
+      if let Some(value) = root.get(section) {
+        sections.insert(Value::from(section), value.clone());
+      }
+    }
+  }
+  let mut document = Value::Mapping(sections);
+  portable(&mut document);
+
+  serde_yaml
+    ::from_value::<Config>(document.clone())
+    .map_err(|e| ClaudeCodeError::InvalidConfig(format!("Invalid bundle: {}", e)))?;
+  Ok(document)
+}
+
+/// Read a bundle from a file or an `https://` URL
+///
+/// Bundles can add targets that receive credentials, so they are never fetched over plain
+/// HTTP, redirects included.
+pub async fn read(source: &str) -> Result<String> {
+  if source.starts_with("http://") {
+    return Err(
+      ClaudeCodeError::InvalidConfig(
+        format!("Refusing to import {} over plain HTTP; use an https:// URL", source)
+      )
+    );
+  }
+  if source.starts_with("https://") {
+    let client = reqwest::Client::builder().https_only(true).build()?;
+    let response = client.get(source).send().await?.error_for_status()?;
+    return Ok(response.text().await?);
+  }
+  let path = shellexpand::tilde(source).to_string();
+  tokio::fs
+    ::read_to_string(&path).await
+    .map_err(|e| ClaudeCodeError::InvalidConfig(format!("Cannot read {}: {}", source, e)))
+}
+
+/// Merge `incoming` into the local configuration `local` (see [`edit::config_value`])
+///
+/// Targets are matched by provider, kind and name; everything else by path. With
+/// `overwrite`, conflicts take the incoming value.
+pub fn merge(local: &mut Value, incoming: &Value, overwrite: bool) -> Merge {
+  let mut merge = Merge::default();
+  let defaults = edit::config_value(&Config::default()).unwrap_or_default();
+  let (Some(local), Some(incoming)) = (local.as_mapping_mut(), incoming.as_mapping()) else {
+    return merge;
+  };
+
+  let mut sections = incoming.clone();
+  let incoming_targets = sections.remove("targets");
+  merge_mapping(&[], local, &sections, defaults.as_mapping(), overwrite, &mut merge);
+
+  let incoming_targets: Vec<TargetConfig> = incoming_targets
+    .and_then(|targets| serde_yaml::from_value(targets).ok())
+    .unwrap_or_default();
+  let mut targets: Vec<TargetConfig> = local
+    .get("targets")
+    .and_then(|targets| serde_yaml::from_value(targets.clone()).ok())
+    .unwrap_or_default();
+  for target in incoming_targets {
+    let path = format!("targets[{}]", target);
+    let value = serde_yaml::to_value(&target).unwrap_or_default();
+    let keys = vec!["targets".to_string()];
+    match targets.iter().position(|t| t.matches(&target.provider, &target.kind, &target.name)) {
+      None => {
+        merge.changes.push(ImportChange { path, value, replaced: None, keys });
+        targets.push(target);
+      }
+      Some(index) if targets[index] == target => {}
+      Some(index) => {
+        let local = serde_yaml::to_value(&targets[index]).unwrap_or_default();
+        if overwrite {
+          merge.changes.push(ImportChange { path, value, replaced: Some(local), keys });
+          targets[index] = target;
+        } else {
+          merge.conflicts.push(ImportConflict { path, local, incoming: value });
+        }
+      }
+    }
+  }
+  if merge.changes.iter().any(|c| c.keys == ["targets"]) {
+    let targets = serde_yaml::to_value(&targets).unwrap_or_default();
+    local.insert(Value::from("targets"), targets.clone());
+    merge.targets = Some(targets);
+  }
+  merge
+}
+
+/// Merge `incoming` into `local`, where `defaults` are the default values at the same path
+fn merge_mapping(
+  path: &[String],
+  local: &mut Mapping,
+  incoming: &Mapping,
+  defaults: Option<&Mapping>,
+  overwrite: bool,
+  merge: &mut Merge
+) {
+  for (key, value) in incoming {
+    let Some(name) = key.as_str() else {
+      continue;
+    };
+    let mut keys = path.to_vec();
+    keys.push(name.to_string());
+    let default = defaults.and_then(|defaults| defaults.get(key));
+
+    match local.get_mut(key) {
+      Some(existing) if existing == value => {}
+      Some(Value::Mapping(existing)) if value.is_mapping() => {
+        let defaults = default.and_then(Value::as_mapping);
+        merge_mapping(&keys, existing, value.as_mapping().unwrap(), defaults, overwrite, merge);
+      }
+      Some(existing) if !existing.is_null() && Some(&*existing) != default => {
+        if overwrite {
+          let replaced = std::mem::replace(existing, value.clone());
+          merge.changes.push(ImportChange {
+            path: keys.join("."),
+            value: value.clone(),
+            replaced: Some(replaced),
+            keys,
+          });
+        } else {
+          merge.conflicts.push(ImportConflict {
+            path: keys.join("."),
+            local: existing.clone(),
+            incoming: value.clone(),
+          });
+        }
+      }
+      // Unset, or left at its default
+      _ => {
+        local.insert(key.clone(), value.clone());
+        merge.changes.push(ImportChange {
+          path: keys.join("."),
+          value: value.clone(),
+          replaced: None,
+          keys,
+        });
+      }
+    }
+  }
+}
+
+/// Remove what only applies to this machine or is secret, returning the paths removed
+fn portable(document: &mut Value) -> Vec<String> {
+  let mut excluded = Vec::new();
+  let Some(root) = document.as_mapping_mut() else {
+    return excluded;
+  };
+
+  for key in root.keys().filter_map(Value::as_str).map(String::from).collect::<Vec<_>>() {
+    if !SECTIONS.contains(&key.as_str()) {
+      root.remove(key.as_str());
+      // The bundle has its own
+      if key != "version" {
+        excluded.push(key);
+      }
+    }
+  }
+  for path in LOCAL_PATHS {
+    let keys: Vec<String> = path.split('.').map(String::from).collect();
+    if edit::remove_path(document, &keys).is_some() {
+      excluded.push(path.to_string());
+    }
+  }
+
+  if let Some(profiles) = document.get_mut("profiles").and_then(Value::as_mapping_mut) {
+    for (name, profile) in profiles.iter_mut() {
+      if let Some(profile) = profile.as_mapping_mut() {
+        if profile.remove("file_path").is_some() {
+          excluded.push(format!("profiles.{}.file_path", name.as_str().unwrap_or_default()));
+        }
+      }
+    }
+  }
+  if let Some(providers) = document.get_mut("providers").and_then(Value::as_mapping_mut) {
+    for (name, settings) in providers.iter_mut() {
+      let prefix = format!("providers.{}", name.as_str().unwrap_or_default());
+      if let Some(settings) = settings.as_mapping_mut() {
+        retain_portable(&prefix, settings, &mut excluded);
+      }
+    }
+  }
+  if let Some(targets) = document.get_mut("targets").and_then(Value::as_sequence_mut) {
+    for target in targets.iter_mut() {
+      let prefix = format!(
+        "targets[{}:{}:{}].options",
+        target.get("provider").and_then(Value::as_str).unwrap_or_default(),
+        target.get("kind").and_then(Value::as_str).unwrap_or_default(),
+        target.get("name").and_then(Value::as_str).unwrap_or_default()
+      );
+      if let Some(options) = target.get_mut("options").and_then(Value::as_mapping_mut) {
+        retain_portable(&prefix, options, &mut excluded);
+        if options.is_empty() {
+          if let Some(target) = target.as_mapping_mut() {
+            target.remove("options");
+          }
+        }
+      }
+    }
+  }
+  excluded
+}
+
+/// Keep the settings or options that are neither secret nor local
+fn retain_portable(prefix: &str, settings: &mut Mapping, excluded: &mut Vec<String>) {
+  settings.retain(|key, value| {
+    let key = key.as_str().unwrap_or_default();
+    let portable = is_portable(key, value);
+    if !portable {
+      excluded.push(format!("{}.{}", prefix, key));
+    }
+    portable
+  });
+}
+
+fn is_portable(key: &str, value: &Value) -> bool {
+  // Names of environment variables, not their values
+  if key.ends_with("_env") {
+    return true;
+  }
+  let key = key.to_lowercase();
+  let local_path = value
+    .as_str()
+    .is_some_and(|v| ["/", "~", "./", "../"].iter().any(|p| v.starts_with(p)));
+  !LOCAL_KEYS.contains(&key.as_str()) &&
+    !SECRET_WORDS.iter().any(|word| key.contains(word)) &&
+    !local_path
+}
+
+fn is_empty(value: &Value) -> bool {
+  match value {
+    Value::Null => true,
+    Value::Mapping(mapping) => mapping.is_empty(),
+    Value::Sequence(items) => items.is_empty(),
+    _ => false,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_export_and_merge_bundle() {
+    let yaml =
+      r#"
+daemon:
+  log_level: debug
+  sync_delay_after_expiry: 90
+credentials:
+  file_path: ~/work/credentials.json
+  field_mappings:
+    accessToken: CLAUDE_TOKEN
+providers:
+  exec:
+    command: ~/bin/provider
+  bitbucket:
+    access_token_env: BITBUCKET_TOKEN
+targets:
+  - provider: github
+    kind: organization
+    name: team
+  - provider: webhook
+    kind: endpoint
+    name: https://hooks.example.com
+    options:
+      method: PUT
+      header.Authorization: Bearer abc
+      client_cert: /etc/certs/client.pem
+"#;
+    let config: Config = serde_yaml::from_str(yaml).unwrap();
+    let (bundle, excluded) = export(&config).unwrap();
+    let text = serde_yaml::to_string(&bundle).unwrap();
+    for secret in ["debug", "~/work", "~/bin", "Bearer", "/etc/certs", "backups"] {
+      assert!(!text.contains(secret), "{} leaked into {}", secret, text);
+    }
+    assert!(text.contains("BITBUCKET_TOKEN") && text.contains("method: PUT"));
+    assert!(excluded.contains(&"credentials.file_path".to_string()));
+    assert_eq!(excluded.len(), 5);
+
+    // Into a configuration that already has one of the targets and another mapping
+    let incoming = parse(&text).unwrap();
+    let mut local_config = Config::default();
+    local_config.targets.push(TargetConfig::new("github", "organization", "team"));
+    local_config.credentials.field_mappings.insert("accessToken".into(), "OTHER".into());
+    let mut local = edit::config_value(&local_config).unwrap();
+
+    let merged = merge(&mut local, &incoming, false);
+    let paths: Vec<&str> = merged.changes
+      .iter()
+      .map(|c| c.path.as_str())
+      .collect();
+    assert!(paths.contains(&"daemon.sync_delay_after_expiry"));
+    assert!(paths.contains(&"targets[webhook:endpoint:https://hooks.example.com]"));
+    assert_eq!(merged.conflicts.len(), 1);
+    assert_eq!(merged.conflicts[0].path, "credentials.field_mappings.accessToken");
+
+    let config: Config = serde_yaml::from_value(local.clone()).unwrap();
+    assert_eq!(config.targets.len(), 2);
+    assert_eq!(config.credentials.field_mappings["accessToken"], "OTHER");
+    assert!(merge(&mut local, &incoming, true).conflicts.is_empty());
+    assert_eq!(local["credentials"]["field_mappings"]["accessToken"], "CLAUDE_TOKEN");
+  }
+
+  #[tokio::test]
+  async fn test_read_refuses_plain_http() {
+    let error = read("http://example.com/bundle.yml").await.unwrap_err();
+    assert!(error.to_string().contains("plain HTTP"), "{}", error);
+  }
+}
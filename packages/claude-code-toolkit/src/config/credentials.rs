@@ -1,31 +1,43 @@
 use crate::{ error::*, types::* };
-use dirs::home_dir;
-use std::path::PathBuf;
+use serde_json::Value;
+use std::path::{ Path, PathBuf };
 use tokio::fs;
 use tracing::{ debug, warn };
 
+/// Credential object of Claude Code's own credentials file
+const CLAUDE_JSON_PATH: &str = "claudeAiOauth";
+
 pub struct CredentialsManager {
   credentials_path: PathBuf,
+  json_path: String,
 }
 
 impl CredentialsManager {
   pub fn new() -> Result<Self> {
-    // Default path for backward compatibility
-    let credentials_path = home_dir()
-      .ok_or("Could not determine home directory")?
-      .join(".claude")
-      .join(".credentials.json");
-
-    Ok(Self { credentials_path })
+    Ok(Self::with_path(crate::utils::paths::get().credentials_file()))
   }
 
   pub fn with_path(path: PathBuf) -> Self {
     Self {
       credentials_path: path,
+      json_path: CLAUDE_JSON_PATH.to_string(),
     }
   }
 
-  pub async fn read_credentials(&self) -> Result<ClaudeCredentials> {
+  /// Credentials manager for a credential profile
+  pub fn from_config(config: &CredentialsConfig) -> Self {
+    let expanded_path = shellexpand::tilde(&config.file_path);
+    Self {
+      credentials_path: PathBuf::from(expanded_path.as_ref()),
+      json_path: config.json_path.clone(),
+    }
+  }
+
+  /// Read the credential file as a generic document
+  ///
+  /// `.yml`/`.yaml` files are parsed as YAML, `.toml` files as TOML and anything else
+  /// as JSON, so credential files of other CLIs can be synced as well.
+  pub async fn read_document(&self) -> Result<Value> {
     if !self.credentials_path.exists() {
       return Err(ClaudeCodeError::CredentialsNotFound {
         path: self.credentials_path.display().to_string(),
@@ -33,8 +45,33 @@ impl CredentialsManager {
     }
 
     let content = fs::read_to_string(&self.credentials_path).await?;
+    let document = parse_document(&self.credentials_path, &content).map_err(|e| {
+      ClaudeCodeError::InvalidCredentials(
+        format!("{}: {}", self.credentials_path.display(), e)
+      )
+    })?;
+
+    debug!("Successfully read credentials from {}", self.credentials_path.display());
+    Ok(document)
+  }
+
+  /// Read the credential object at `json_path` (see [`resolve_json_path`])
+  pub async fn read_credential_object(&self, json_path: &str) -> Result<Value> {
+    let document = self.read_document().await?;
+    resolve_json_path(&document, json_path)
+      .filter(|value| value.is_object())
+      .cloned()
+      .ok_or_else(|| {
+        ClaudeCodeError::InvalidCredentials(
+          format!("Could not find '{}' in credentials file", json_path)
+        )
+      })
+  }
+
+  /// Read the credential file as Claude Code credentials
+  pub async fn read_credentials(&self) -> Result<ClaudeCredentials> {
     let credentials: ClaudeCredentials = serde_json
-      ::from_str(&content)
+      ::from_value(self.read_document().await?)
       .map_err(|e| ClaudeCodeError::InvalidCredentials(e.to_string()))?;
 
     debug!("Successfully read Claude credentials");
@@ -42,21 +79,33 @@ impl CredentialsManager {
   }
 
   pub async fn get_access_token(&self) -> Result<String> {
-    let credentials = self.read_credentials().await?;
-    Ok(credentials.claude_ai_oauth.access_token)
+    let credentials = self.read_generic_credentials().await?;
+    Ok(credentials.access_token)
+  }
+
+  /// Read the configured credential object as generic credentials
+  pub async fn read_generic_credentials(&self) -> Result<crate::traits::Credentials> {
+    let object = self.read_credential_object(&self.json_path).await?;
+    Ok(crate::traits::Credentials::from_source(object, &[]))
   }
 
   pub async fn get_session_info(&self) -> Result<SessionInfo> {
-    let credentials = self.read_credentials().await?;
-    let oauth = credentials.claude_ai_oauth;
+    let credentials = self.read_generic_credentials().await?;
+    let expires_at = credentials.expires_at.ok_or_else(|| {
+      ClaudeCodeError::InvalidCredentials(
+        format!("No expiresAt in '{}' of {}", self.json_path, self.credentials_path.display())
+      )
+    })?;
     let now = chrono::Utc::now().timestamp() * 1000; // Convert to milliseconds
-    let time_remaining = oauth.expires_at - now;
+    let time_remaining = expires_at - now;
 
     let session_info = SessionInfo {
-      expires_at: oauth.expires_at,
+      expires_at,
       time_remaining,
       is_expired: time_remaining <= 0,
-      subscription_type: oauth.subscription_type,
+      subscription_type: credentials
+        .field("subscriptionType")
+        .unwrap_or_else(|| "unknown".to_string()),
     };
 
     if session_info.is_expired {
@@ -69,8 +118,7 @@ impl CredentialsManager {
   }
 
   pub async fn get_expiry_time(&self) -> Result<i64> {
-    let credentials = self.read_credentials().await?;
-    Ok(credentials.claude_ai_oauth.expires_at)
+    Ok(self.get_session_info().await?.expires_at)
   }
 
   pub fn format_time_remaining(milliseconds: i64) -> String {
@@ -99,6 +147,29 @@ impl CredentialsManager {
   }
 }
 
+fn parse_document(path: &Path, content: &str) -> std::result::Result<Value, String> {
+  let extension = path
+    .extension()
+    .and_then(|e| e.to_str())
+    .map(|e| e.to_ascii_lowercase());
+
+  match extension.as_deref() {
+    Some("yml" | "yaml") => serde_yaml::from_str(content).map_err(|e| e.to_string()),
+    Some("toml") => toml::from_str(content).map_err(|e| e.to_string()),
+    _ => serde_json::from_str(content).map_err(|e| e.to_string()),
+  }
+}
+
+/// Locate the credential object inside a credential document
+///
+/// `path` is a top-level key (`claudeAiOauth`, `github.com`), a dotted path
+/// (`installed.oauth`, `accounts.0`) or an RFC 6901 pointer (`/github.com/oauth_token`).
+/// An empty path or `.` selects the whole document.
+pub fn resolve_json_path<'a>(document: &'a Value, path: &str) -> Option<&'a Value> {
+  // Keys containing dots (host names) take precedence over dotted traversal
+  document.get(path).or_else(|| crate::utils::template::lookup(document, path))
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -117,9 +188,7 @@ mod tests {
 
   #[tokio::test]
   async fn test_read_credentials_file_not_found() {
-    let credentials_manager = CredentialsManager {
-      credentials_path: PathBuf::from("/non/existent/path"),
-    };
+    let credentials_manager = CredentialsManager::with_path(PathBuf::from("/non/existent/path"));
 
     let result = credentials_manager.read_credentials().await;
     assert!(result.is_err());
@@ -145,9 +214,7 @@ mod tests {
 
     temp_file.write_all(credentials_json.as_bytes()).unwrap();
 
-    let credentials_manager = CredentialsManager {
-      credentials_path: temp_file.path().to_path_buf(),
-    };
+    let credentials_manager = CredentialsManager::with_path(temp_file.path().to_path_buf());
 
     let credentials = credentials_manager.read_credentials().await.unwrap();
     assert_eq!(credentials.claude_ai_oauth.access_token, "test-access-token");
@@ -156,14 +223,53 @@ mod tests {
     assert_eq!(credentials.claude_ai_oauth.subscription_type, "max");
   }
 
+  #[tokio::test]
+  async fn test_read_credential_object_from_yaml() {
+    let mut temp_file = tempfile::Builder::new().suffix(".yml").tempfile().unwrap();
+    let hosts_yml =
+      r#"
+github.com:
+  user: octocat
+  oauth_token: gho_example
+  git_protocol: https
+"#;
+    temp_file.write_all(hosts_yml.as_bytes()).unwrap();
+
+    let credentials_manager = CredentialsManager::with_path(temp_file.path().to_path_buf());
+    let object = credentials_manager.read_credential_object("github.com").await.unwrap();
+    assert_eq!(object["oauth_token"], "gho_example");
+
+    assert!(credentials_manager.read_credential_object("/github.com/user").await.is_err());
+    assert!(credentials_manager.read_credentials().await.is_err());
+  }
+
+  #[test]
+  fn test_resolve_json_path() {
+    let document: Value = toml
+      ::from_str(
+        r#"
+[profiles.work]
+token = "abc"
+
+[[accounts]]
+name = "first"
+"#
+      )
+      .unwrap();
+
+    assert_eq!(resolve_json_path(&document, "profiles.work").unwrap()["token"], "abc");
+    assert_eq!(resolve_json_path(&document, "/profiles/work/token").unwrap(), "abc");
+    assert_eq!(resolve_json_path(&document, "accounts.0.name").unwrap(), "first");
+    assert_eq!(resolve_json_path(&document, ".").unwrap(), &document);
+    assert!(resolve_json_path(&document, "profiles.home").is_none());
+  }
+
   #[tokio::test]
   async fn test_invalid_credentials_format() {
     let mut temp_file = NamedTempFile::new().unwrap();
     temp_file.write_all(b"invalid json").unwrap();
 
-    let credentials_manager = CredentialsManager {
-      credentials_path: temp_file.path().to_path_buf(),
-    };
+    let credentials_manager = CredentialsManager::with_path(temp_file.path().to_path_buf());
 
     let result = credentials_manager.read_credentials().await;
     assert!(result.is_err());
@@ -192,9 +298,7 @@ mod tests {
 
     temp_file.write_all(credentials_json.as_bytes()).unwrap();
 
-    let credentials_manager = CredentialsManager {
-      credentials_path: temp_file.path().to_path_buf(),
-    };
+    let credentials_manager = CredentialsManager::with_path(temp_file.path().to_path_buf());
 
     let session_info = credentials_manager.get_session_info().await.unwrap();
     assert_eq!(session_info.expires_at, future_time);
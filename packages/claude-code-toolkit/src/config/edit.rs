@@ -0,0 +1,523 @@
+//! Path-based edits of `config.yml` that keep the user's comments and key order
+//!
+//! A path is a dotted list of keys (`daemon.log_level`); sequence elements are
+//! addressed by index (`targets.0.name`). Edits are first applied to the parsed
+//! document, then written back into the original text: only the lines of the
+//! edited entry change. Where the text cannot be edited in place (flow collections,
+//! sequences of mappings), the smallest enclosing block entry is re-rendered;
+//! [`edit_text`] returns `None` when even that fails, and callers fall back to
+//! serializing the whole document.
+
+use crate::error::{ ClaudeCodeError, Result };
+use crate::types::Config;
+use serde_yaml::{ Mapping, Value };
+
+/// The configuration as a YAML value, including the sections `config.yml` omits while
+/// they are empty or at their defaults
+pub fn config_value(config: &Config) -> Result<Value> {
+  let mut value = serde_yaml
+    ::to_value(config)
+    .map_err(|e| ClaudeCodeError::InvalidConfig(e.to_string()))?;
+  if let Value::Mapping(mapping) = &mut value {
+    let backups = serde_yaml
+      ::to_value(&config.backups)
+      .map_err(|e| ClaudeCodeError::InvalidConfig(e.to_string()))?;
+    mapping.entry("backups".into()).or_insert(backups);
+    for section in ["profiles", "providers"] {
+      mapping.entry(section.into()).or_insert_with(|| Value::Mapping(Mapping::new()));
+    }
+  }
+  Ok(value)
+}
+
+/// Whether `path` names a field of [`Config`] (or an entry of one of its maps)
+///
+/// Unknown keys are ignored when the configuration is deserialized, so a value no field
+/// accepts is put at `path`: deserialization only succeeds when nothing reads it.
+pub fn is_known_path(document: &Value, path: &[String]) -> bool {
+  let mut probe = Mapping::new();
+  probe.insert(Value::Sequence(Vec::new()), Value::Sequence(Vec::new()));
+
+  let mut document = document.clone();
+  set_path(&mut document, path, Value::Mapping(probe)).is_ok() &&
+    serde_yaml::from_value::<Config>(document).is_err()
+}
+
+/// Split a dotted path into its keys
+pub fn parse_path(path: &str) -> Result<Vec<String>> {
+  let keys: Vec<String> = path.split('.').map(String::from).collect();
+  if keys.iter().any(|key| key.is_empty()) {
+    return Err(ClaudeCodeError::Validation(format!("Invalid configuration path '{}'", path)));
+  }
+  Ok(keys)
+}
+
+/// Value at `path`
+pub fn get_path<'a>(value: &'a Value, path: &[String]) -> Option<&'a Value> {
+  path.iter().try_fold(value, |value, key| {
+    match value {
+      Value::Sequence(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+      Value::Mapping(mapping) => mapping.get(key.as_str()),
+      _ => None,
+    }
+  })
+}
+
+/// Set the value at `path`, creating intermediate mappings
+pub fn set_path(value: &mut Value, path: &[String], new_value: Value) -> Result<()> {
+  let Some((key, parents)) = path.split_last() else {
+    *value = new_value;
+    return Ok(());
+  };
+
+  let mut current = value;
+  for parent in parents {
+    if current.is_null() {
+      *current = Value::Mapping(Mapping::new());
+    }
+    current = match current {
+      Value::Sequence(items) => {
+        let index = parent.parse::<usize>().ok();
+        index
+          .and_then(|i| items.get_mut(i))
+          .ok_or_else(|| not_found(path))?
+      }
+      Value::Mapping(mapping) => {
+        mapping
+          .entry(Value::String(parent.clone()))
+          .or_insert_with(|| Value::Mapping(Mapping::new()))
+      }
+      _ => {
+        return Err(not_a_section(path, parent));
+      }
+    };
+  }
+
+  if current.is_null() {
+    *current = Value::Mapping(Mapping::new());
+  }
+  match current {
+    Value::Sequence(items) => {
+      let slot = key
+        .parse::<usize>()
+        .ok()
+        .and_then(|i| items.get_mut(i))
+        .ok_or_else(|| not_found(path))?;
+      *slot = new_value;
+    }
+    Value::Mapping(mapping) => {
+      mapping.insert(Value::String(key.clone()), new_value);
+    }
+    _ => {
+      return Err(not_a_section(path, key));
+    }
+  }
+  Ok(())
+}
+
+/// Remove the value at `path`, returning it
+pub fn remove_path(value: &mut Value, path: &[String]) -> Option<Value> {
+  let (key, parents) = path.split_last()?;
+  let parent = parents.iter().try_fold(value, |value, key| {
+    match value {
+      Value::Sequence(items) => key.parse::<usize>().ok().and_then(|i| items.get_mut(i)),
+      Value::Mapping(mapping) => mapping.get_mut(key.as_str()),
+      _ => None,
+    }
+  })?;
+
+  match parent {
+    Value::Sequence(items) => {
+      let index = key.parse::<usize>().ok().filter(|i| *i < items.len())?;
+      Some(items.remove(index))
+    }
+    Value::Mapping(mapping) => mapping.remove(key.as_str()),
+    _ => None,
+  }
+}
+
+/// Paths at which `after` differs from `before`, with the new value or `None` if removed
+///
+/// Mappings are compared key by key; any other value, lists included, changes as a whole.
+pub fn changes(before: &Value, after: &Value) -> Vec<(Vec<String>, Option<Value>)> {
+  let mut changes = Vec::new();
+  collect_changes(&mut Vec::new(), before, after, &mut changes);
+  changes
+}
+
+fn collect_changes(
+  path: &mut Vec<String>,
+  before: &Value,
+  after: &Value,
+  changes: &mut Vec<(Vec<String>, Option<Value>)>
+) {
+  match (before, after) {
+    (Value::Mapping(before), Value::Mapping(after)) => {
+      for (key, old) in before {
+        let Some(key) = key.as_str() else {
+          continue;
+        };
+        path.push(key.to_string());
+        match after.get(key) {
+          Some(new) => collect_changes(path, old, new, changes),
+          None => changes.push((path.clone(), None)),
+        }
+        path.pop();
+      }
+      for (key, new) in after {
+        let Some(key) = key.as_str().filter(|key| !before.contains_key(*key)) else {
+          continue;
+        };
+        path.push(key.to_string());
+        changes.push((path.clone(), Some(new.clone())));
+        path.pop();
+      }
+    }
+    _ if before != after => changes.push((path.clone(), Some(after.clone()))),
+    _ => {}
+  }
+}
+
+fn not_found(path: &[String]) -> ClaudeCodeError {
+  ClaudeCodeError::Validation(format!("No configuration value at '{}'", path.join(".")))
+}
+
+fn not_a_section(path: &[String], key: &str) -> ClaudeCodeError {
+  ClaudeCodeError::Validation(
+    format!("Cannot set '{}': '{}' is not a section", path.join("."), key)
+  )
+}
+
+/// A `key: value` entry of a block mapping
+struct Entry {
+  /// Line of the key
+  line: usize,
+  /// One past the last line of the entry's value (trailing comments excluded)
+  end: usize,
+  indent: usize,
+  /// The key as written
+  key: String,
+  /// Inline value after the colon, without comment
+  inline: String,
+  /// Trailing comment of the key line, including `#`
+  comment: Option<String>,
+}
+
+fn indent_of(line: &str) -> usize {
+  line.len() - line.trim_start_matches(' ').len()
+}
+
+fn is_content(line: &str) -> bool {
+  let trimmed = line.trim();
+  !trimmed.is_empty() && !trimmed.starts_with('#')
+}
+
+fn is_sequence_item(line: &str) -> bool {
+  let trimmed = line.trim_start();
+  trimmed.starts_with("- ") || trimmed == "-"
+}
+
+/// Position of a ` #` comment outside quotes
+fn comment_start(text: &str) -> Option<usize> {
+  let mut quote = None;
+  let mut previous = ' ';
+  for (i, c) in text.char_indices() {
+    match (quote, c) {
+      (None, '\'' | '"') => {
+        quote = Some(c);
+      }
+      (Some(q), c) if c == q => {
+        quote = None;
+      }
+      (None, '#') if previous.is_whitespace() => {
+        return Some(i);
+      }
+      _ => {}
+    }
+    previous = c;
+  }
+  None
+}
+
+/// Split a mapping line into its key (as written, and unquoted) and the rest after the colon
+fn split_key(line: &str) -> Option<(String, String, &str)> {
+  if is_sequence_item(line) {
+    return None;
+  }
+  let trimmed = line.trim_start();
+
+  let key_end = match trimmed.chars().next()? {
+    quote @ ('\'' | '"') => trimmed[1..].find(quote)? + 2,
+    _ => trimmed.find(": ").or_else(|| trimmed.strip_suffix(':').map(str::len))?,
+  };
+  let written = &trimmed[..key_end];
+  let rest = trimmed[key_end..].strip_prefix(':')?;
+  if !(rest.is_empty() || rest.starts_with(' ')) {
+    return None;
+  }
+
+  let key = serde_yaml::from_str::<String>(written).unwrap_or_else(|_| written.to_string());
+  Some((written.to_string(), key, rest))
+}
+
+/// Entries of the block mapping spanning `lines[start..end]`
+///
+/// Returns `None` when the region holds something other than a block mapping.
+fn entries(lines: &[String], start: usize, end: usize) -> Option<Vec<Entry>> {
+  let Some(first) = (start..end).find(|i| is_content(&lines[*i])) else {
+    return Some(Vec::new());
+  };
+  let indent = indent_of(&lines[first]);
+  if is_sequence_item(&lines[first]) {
+    return None;
+  }
+
+  // Sequence items may sit at the indent of the key they belong to
+  let keys: Vec<usize> = (first..end)
+    .filter(|i| {
+      let line = &lines[*i];
+      is_content(line) &&
+        (indent_of(line) < indent || (indent_of(line) == indent && !is_sequence_item(line)))
+    })
+    .collect();
+  let mut entries = Vec::new();
+  for (n, line) in keys.iter().copied().enumerate() {
+    if indent_of(&lines[line]) != indent {
+      return None;
+    }
+    let (written, _, rest) = split_key(&lines[line])?;
+    let (inline, comment) = match comment_start(rest) {
+      Some(i) => (rest[..i].trim().to_string(), Some(rest[i..].trim().to_string())),
+      None => (rest.trim().to_string(), None),
+    };
+
+    let next = keys.get(n + 1).copied().unwrap_or(end);
+    let end = (line + 1..next)
+      .rev()
+      .find(|i| is_content(&lines[*i]))
+      .map_or(line + 1, |i| i + 1);
+    entries.push(Entry { line, end, indent, key: written, inline, comment });
+  }
+  Some(entries)
+}
+
+/// A scalar on one line
+fn render_scalar(value: &Value, in_flow: bool) -> Option<String> {
+  let rendered = match value {
+    Value::Mapping(_) | Value::Sequence(_) => {
+      return None;
+    }
+    // A JSON string is a valid double-quoted YAML scalar and fits on one line
+    Value::String(s) if in_flow || s.contains('\n') => serde_json::to_string(s).ok()?,
+    _ => serde_yaml::to_string(value).ok()?.trim_end().to_string(),
+  };
+  (!rendered.contains('\n')).then_some(rendered)
+}
+
+/// A collection of scalars in flow style (`[30, 15, 5]`)
+fn render_flow(value: &Value) -> Option<String> {
+  match value {
+    Value::Sequence(items) => {
+      let items: Option<Vec<String>> = items
+        .iter()
+        .map(|item| render_scalar(item, true))
+        .collect();
+      Some(format!("[{}]", items?.join(", ")))
+    }
+    Value::Mapping(mapping) => {
+      let entries: Option<Vec<String>> = mapping
+        .iter()
+        .map(|(k, v)| Some(format!("{}: {}", render_scalar(k, true)?, render_scalar(v, true)?)))
+        .collect();
+      Some(format!("{{{}}}", entries?.join(", ")))
+    }
+    _ => None,
+  }
+}
+
+/// Lines of `key: value` at `indent`
+///
+/// With `flow`, collections of scalars stay on the key's line as they were written.
+fn render_entry(indent: usize, key: &str, value: &Value, flow: bool) -> Option<Vec<String>> {
+  let pad = " ".repeat(indent);
+  let inline = match value {
+    Value::Mapping(mapping) if mapping.is_empty() => Some("{}".to_string()),
+    Value::Sequence(items) if items.is_empty() => Some("[]".to_string()),
+    Value::Mapping(_) | Value::Sequence(_) if flow => render_flow(value),
+    Value::Mapping(_) | Value::Sequence(_) => None,
+    _ => Some(render_scalar(value, false)?),
+  };
+
+  if let Some(inline) = inline {
+    return Some(vec![format!("{}{}: {}", pad, key, inline)]);
+  }
+
+  let yaml = serde_yaml::to_string(value).ok()?;
+  let mut lines = vec![format!("{}{}:", pad, key)];
+  lines.extend(yaml.lines().map(|line| format!("{}  {}", pad, line)));
+  Some(lines)
+}
+
+fn render_key(key: &str) -> String {
+  serde_yaml
+    ::to_string(&Value::String(key.to_string()))
+    .map(|s| s.trim_end().to_string())
+    .unwrap_or_else(|_| key.to_string())
+}
+
+/// Apply an edit of `path` to the text of a YAML document
+///
+/// `edited` is the whole document after the edit; the value at `path` is written, or the
+/// entry removed when `edited` has no value there. Only the entry at `path` (or the
+/// nearest enclosing entry that is not a plain block mapping) is rewritten. Returns `None`
+/// if the result does not parse back to `edited`.
+pub fn edit_text(text: &str, path: &[String], edited: &Value) -> Option<String> {
+  let mut lines: Vec<String> = text.lines().map(String::from).collect();
+  let (mut start, mut end) = (0, lines.len());
+  let mut parent: Option<Entry> = None;
+
+  for depth in 0..path.len() {
+    let prefix = &path[..=depth];
+    let region = entries(&lines, start, end)?;
+    let found = region.iter().position(|e| {
+      split_key(&lines[e.line]).is_some_and(|(_, key, _)| key == path[depth])
+    });
+
+    let Some(index) = found else {
+      // Missing key: insert it after the last entry of the enclosing mapping
+      let value = get_path(edited, prefix)?;
+      let indent = match (region.first(), &parent) {
+        (Some(entry), _) => entry.indent,
+        (None, Some(parent)) => parent.indent + 2,
+        (None, None) => 0,
+      };
+      let at = region.last().map_or(start, |e| e.end);
+      let rendered = render_entry(indent, &render_key(&path[depth]), value, false)?;
+      lines.splice(at..at, rendered);
+      break;
+    };
+
+    let entry = region.into_iter().nth(index)?;
+    let is_last = depth + 1 == path.len();
+    let children_are_mapping =
+      entry.inline.is_empty() &&
+      (entry.line + 1..entry.end).any(|i| is_content(&lines[i])) &&
+      entries(&lines, entry.line + 1, entry.end).is_some();
+
+    if is_last || !children_are_mapping {
+      match get_path(edited, prefix) {
+        Some(value) => {
+          let flow = !entry.inline.is_empty();
+          let mut rendered = render_entry(entry.indent, &entry.key, value, flow)?;
+          // Keep the comment of a value that stays on the key's line
+          if let (Some(comment), [line]) = (&entry.comment, rendered.as_mut_slice()) {
+            if flow {
+              line.push(' ');
+              line.push_str(comment);
+            }
+          }
+          lines.splice(entry.line..entry.end, rendered);
+        }
+        None => {
+          lines.drain(entry.line..entry.end);
+          // An emptied mapping must not turn into null
+          if let Some(parent) = &parent {
+            let parent_end = parent.end - (entry.end - entry.line);
+            if !(parent.line + 1..parent_end).any(|i| is_content(&lines[i])) {
+              let value = get_path(edited, &path[..depth])?;
+              let rendered = render_entry(parent.indent, &parent.key, value, false)?;
+              lines.splice(parent.line..parent_end, rendered);
+            }
+          }
+        }
+      }
+      break;
+    }
+
+    start = entry.line + 1;
+    end = entry.end;
+    parent = Some(entry);
+  }
+
+  let mut result = lines.join("\n");
+  result.push('\n');
+  (serde_yaml::from_str::<Value>(&result).ok()? == *edited).then_some(result)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const CONFIG: &str = "\
+# Claude Code Toolkit
+daemon:
+  log_level: info # debug, info, warn, error
+  sync_delay_after_expiry: 60
+
+notifications:
+  # minutes before expiry
+  session_warnings: [30, 15, 5]
+  sync_failures: true
+targets:
+- provider: github
+  kind: organization
+  name: my-org
+";
+
+  fn edit(text: &str, path: &str, value: Option<&str>) -> String {
+    let path = parse_path(path).unwrap();
+    let mut document: Value = serde_yaml::from_str(text).unwrap();
+    match value {
+      Some(value) => set_path(&mut document, &path, serde_yaml::from_str(value).unwrap()).unwrap(),
+      None => {
+        remove_path(&mut document, &path).unwrap();
+      }
+    }
+    edit_text(text, &path, &document).unwrap()
+  }
+
+  #[test]
+  fn test_edits_keep_comments_and_order() {
+    let edited = edit(CONFIG, "daemon.log_level", Some("debug"));
+    assert_eq!(edited, CONFIG.replace("log_level: info", "log_level: debug"));
+
+    let edited = edit(CONFIG, "notifications.session_warnings", Some("[10, 2]"));
+    assert_eq!(edited, CONFIG.replace("[30, 15, 5]", "[10, 2]"));
+
+    let edited = edit(CONFIG, "daemon", Some("{ log_level: warn }"));
+    assert!(edited.contains("daemon:\n  log_level: warn\n\nnotifications:"));
+
+    let edited = edit(CONFIG, "providers.aws.region", Some("eu-west-1"));
+    assert!(edited.starts_with(CONFIG));
+    assert!(edited.ends_with("providers:\n  aws:\n    region: eu-west-1\n"));
+
+    let edited = edit(CONFIG, "targets.0.name", Some("other-org"));
+    assert!(edited.contains("# Claude Code Toolkit\n") && edited.contains("name: other-org"));
+
+    let edited = edit(CONFIG, "daemon.sync_delay_after_expiry", None);
+    assert_eq!(edited, CONFIG.replace("  sync_delay_after_expiry: 60\n", ""));
+
+    let edited = edit(&edited, "daemon.log_level", None);
+    assert!(edited.starts_with("# Claude Code Toolkit\ndaemon: {}\n\nnotifications:"));
+  }
+
+  #[test]
+  fn test_changes_between_documents() {
+    let before: Value = serde_yaml::from_str(CONFIG).unwrap();
+    let mut after = before.clone();
+    set_path(&mut after, &["daemon".into(), "log_level".into()], "debug".into()).unwrap();
+    remove_path(&mut after, &["daemon".into(), "sync_delay_after_expiry".into()]).unwrap();
+    set_path(&mut after, &["providers".into(), "aws".into()], serde_yaml::from_str("{}").unwrap())
+      .unwrap();
+
+    assert_eq!(
+      changes(&before, &after),
+      vec![
+        (vec!["daemon".to_string(), "log_level".to_string()], Some(Value::from("debug"))),
+        (vec!["daemon".to_string(), "sync_delay_after_expiry".to_string()], None),
+        (vec!["providers".to_string()], Some(serde_yaml::from_str("{aws: {}}").unwrap()))
+      ]
+    );
+    assert!(changes(&before, &before).is_empty());
+  }
+}
@@ -0,0 +1,515 @@
+//! Layered configuration resolution
+//!
+//! The effective configuration is resolved from these layers, lowest precedence first:
+//!
+//! | Layer         | Source                                                      |
+//! |---------------|-------------------------------------------------------------|
+//! | `default`     | Built-in defaults                                           |
+//! | `system`      | `/etc/claude-code-toolkit/config.yml`                       |
+//! | `user`        | `~/.config/claude-code-toolkit/config.yml` (or `--config`)  |
+//! | `project`     | `.claude-code-toolkit.yml` in the working directory or above |
+//! | `environment` | `CCT_*` variables                                           |
+//!
+//! The project file and environment variables come from whatever checkout or shell the
+//! toolkit runs in, so they may only set [`LOCAL_PATHS`]: daemon, notification and backup
+//! settings and the secret mapping. Targets, providers and credential files are only read
+//! from the system and user files; other keys in the local layers are ignored with a
+//! warning.
+//!
+//! Mappings merge key by key and a `null` value removes a key set below; every other
+//! value, lists such as `targets` included, replaces the one below. Environment variable
+//! names map to paths with `__` between keys, matched case-insensitively:
+//! `CCT_DAEMON__LOG_LEVEL=debug` sets `daemon.log_level`. Their values are read as YAML
+//! (`CCT_NOTIFICATIONS__SESSION_WARNINGS='[30, 15]'`) unless they replace a string.
+//!
+//! Only the user file is ever written; edits never copy values from the other layers.
+
+use crate::config::{ edit, migrations };
+use crate::error::{ ClaudeCodeError, Result };
+use crate::types::Config;
+use serde::Serialize;
+use serde_yaml::Value;
+use std::collections::HashMap;
+use std::path::{ Path, PathBuf };
+use tracing::warn;
+
+/// Prefix of environment variables overriding configuration values
+pub const ENV_PREFIX: &str = "CCT_";
+
+/// Separator between keys in environment variable names
+pub const ENV_SEPARATOR: &str = "__";
+
+/// Project-local configuration file, looked up from the working directory upwards
+pub const PROJECT_FILE: &str = ".claude-code-toolkit.yml";
+
+/// Paths the project file and environment variables may set
+///
+/// Everything that decides where credentials are read from or sent to is left out.
+pub const LOCAL_PATHS: &[&str] = &[
+  "daemon",
+  "notifications",
+  "backups",
+  "credentials.field_mappings",
+  "credentials.templates",
+];
+
+/// Whether the project and environment layers may set `path`
+fn is_local_path(path: &[String]) -> bool {
+  LOCAL_PATHS.iter().any(|allowed| {
+    let allowed: Vec<&str> = allowed.split('.').collect();
+    path.len() >= allowed.len() && allowed.iter().zip(path).all(|(a, key)| a == key)
+  })
+}
+
+/// Whether `path` leads to a path of [`LOCAL_PATHS`]
+fn contains_local_path(path: &[String]) -> bool {
+  LOCAL_PATHS.iter().any(|allowed| {
+    let allowed: Vec<&str> = allowed.split('.').collect();
+    path.len() < allowed.len() && path.iter().zip(&allowed).all(|(key, a)| key == a)
+  })
+}
+
+/// Remove everything but [`LOCAL_PATHS`] from a layer, returning the removed paths
+fn restrict_to_local(path: &mut Vec<String>, value: &mut Value) -> Vec<String> {
+  let mut ignored = Vec::new();
+  let Value::Mapping(mapping) = value else {
+    return ignored;
+  };
+  mapping.retain(|key, child| {
+    path.push(key_string(key));
+    let keep = if is_local_path(path) {
+      true
+    } else if contains_local_path(path) && child.is_mapping() {
+      ignored.extend(restrict_to_local(path, child));
+      true
+    } else {
+      ignored.push(path.join("."));
+      false
+    };
+    path.pop();
+    keep
+  });
+  ignored
+}
+
+/// System-wide configuration file
+pub fn system_config_path() -> PathBuf {
+  if cfg!(windows) {
+    std::env
+      ::var_os("ProgramData")
+      .map(PathBuf::from)
+      .unwrap_or_else(|| PathBuf::from(r"C:\ProgramData"))
+      .join("claude-code-toolkit")
+      .join("config.yml")
+  } else {
+    PathBuf::from("/etc/claude-code-toolkit/config.yml")
+  }
+}
+
+/// Nearest project configuration file in `dir` or one of its parents
+pub fn find_project_file(dir: &Path) -> Option<PathBuf> {
+  dir
+    .ancestors()
+    .map(|dir| dir.join(PROJECT_FILE))
+    .find(|path| path.is_file())
+}
+
+/// Configuration layer, in order of precedence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Layer {
+  Default,
+  System,
+  User,
+  Project,
+  Environment,
+}
+
+impl std::fmt::Display for Layer {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let name = match self {
+      Layer::Default => "default",
+      Layer::System => "system",
+      Layer::User => "user",
+      Layer::Project => "project",
+      Layer::Environment => "environment",
+    };
+    write!(f, "{}", name)
+  }
+}
+
+/// Where a value came from
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Origin {
+  pub layer: Layer,
+  /// File path or environment variable
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub source: Option<String>,
+}
+
+impl std::fmt::Display for Origin {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match &self.source {
+      Some(source) => write!(f, "{} ({})", self.layer, source),
+      None => write!(f, "{}", self.layer),
+    }
+  }
+}
+
+/// An effective value and its origin
+#[derive(Debug, Clone, Serialize)]
+pub struct ValueOrigin {
+  pub path: String,
+  pub value: Value,
+  pub origin: Origin,
+}
+
+/// The layers to resolve
+#[derive(Debug, Clone)]
+pub struct ConfigLayers {
+  system: Option<PathBuf>,
+  user: PathBuf,
+  project: Option<PathBuf>,
+  environment: Vec<(String, String)>,
+}
+
+impl ConfigLayers {
+  /// Only the user file
+  pub fn new(user: PathBuf) -> Self {
+    Self {
+      system: None,
+      user,
+      project: None,
+      environment: Vec::new(),
+    }
+  }
+
+  /// The system file, the project file of the working directory and `CCT_*` variables
+  /// around the user file
+  pub fn discover(user: PathBuf) -> Self {
+    let mut environment: Vec<(String, String)> = std::env
+      ::vars()
+      .filter(|(name, _)| name.starts_with(ENV_PREFIX))
+      .collect();
+    environment.sort();
+
+    let project = std::env::current_dir().ok().and_then(|dir| find_project_file(&dir));
+    Self::new(user)
+      .with_system(system_config_path())
+      .with_project(project)
+      .with_environment(environment)
+  }
+
+  pub fn with_system(mut self, path: PathBuf) -> Self {
+    self.system = Some(path);
+    self
+  }
+
+  pub fn with_project(mut self, path: Option<PathBuf>) -> Self {
+    self.project = path;
+    self
+  }
+
+  pub fn with_environment(mut self, variables: Vec<(String, String)>) -> Self {
+    self.environment = variables;
+    self
+  }
+
+  /// Merge the layers
+  pub fn resolve(&self) -> Result<ResolvedConfig> {
+    let mut resolved = ResolvedConfig {
+      document: edit::config_value(&Config::default())?,
+      origins: HashMap::new(),
+    };
+    resolved.origins.insert(String::new(), Origin { layer: Layer::Default, source: None });
+
+    let files = [
+      (Layer::System, self.system.as_ref()),
+      (Layer::User, Some(&self.user)),
+      (Layer::Project, self.project.as_ref()),
+    ];
+    for (layer, path) in files {
+      let Some(path) = path.filter(|path| path.is_file()) else {
+        continue;
+      };
+      let origin = Origin { layer, source: Some(path.display().to_string()) };
+      let content = std::fs::read_to_string(path)?;
+      let mut document: Value = serde_yaml
+        ::from_str(&content)
+        .map_err(|e| ClaudeCodeError::InvalidConfig(format!("{}: {}", origin, e)))?;
+      if document.is_null() {
+        continue;
+      }
+      migrations::migrate(&mut document)?;
+      if layer == Layer::Project {
+        if let Some(root) = document.as_mapping_mut() {
+          root.remove("version");
+        }
+        let ignored = restrict_to_local(&mut Vec::new(), &mut document);
+        if !ignored.is_empty() {
+          warn!(
+            "Ignoring {} from {}: only the system and user files may set them",
+            ignored.join(", "),
+            origin
+          );
+        }
+      }
+      resolved.merge(Vec::new(), document, &origin);
+    }
+
+    let known: Vec<String> = resolved.document
+      .as_mapping()
+      .map(|mapping| mapping.keys().filter_map(Value::as_str).map(String::from).collect())
+      .unwrap_or_default();
+    for (name, raw) in &self.environment {
+      let Some(keys) = name.strip_prefix(ENV_PREFIX) else {
+        continue;
+      };
+      let keys: Vec<&str> = keys.split(ENV_SEPARATOR).collect();
+      if keys.iter().any(|key| key.is_empty()) {
+        continue;
+      }
+      let Some(path) = resolved.match_path(&keys) else {
+        continue;
+      };
+      if !known.contains(&path[0]) {
+        continue;
+      }
+      if !is_local_path(&path) {
+        warn!("Ignoring {}: only the system and user files may set {}", name, path.join("."));
+        continue;
+      }
+
+      let replaces_string = edit::get_path(&resolved.document, &path).is_some_and(Value::is_string);
+      let value = match serde_yaml::from_str::<Value>(raw) {
+        Ok(value) if !replaces_string => value,
+        _ => Value::String(raw.clone()),
+      };
+      let origin = Origin { layer: Layer::Environment, source: Some(name.clone()) };
+      let layer = path
+        .iter()
+        .rev()
+        .fold(value, |value, key| {
+          let mut mapping = serde_yaml::Mapping::new();
+          mapping.insert(Value::String(key.clone()), value);
+          Value::Mapping(mapping)
+        });
+      resolved.merge(Vec::new(), layer, &origin);
+    }
+
+    Ok(resolved)
+  }
+}
+
+/// The merged layers
+#[derive(Debug, Clone)]
+pub struct ResolvedConfig {
+  document: Value,
+  /// Origin of every value set as a whole, by dotted path
+  origins: HashMap<String, Origin>,
+}
+
+impl ResolvedConfig {
+  /// A single layer holding all of `config`
+  pub fn from_config(config: &Config, origin: Origin) -> Result<Self> {
+    let mut origins = HashMap::new();
+    origins.insert(String::new(), origin);
+    Ok(Self { document: edit::config_value(config)?, origins })
+  }
+
+  /// The effective configuration
+  pub fn config(&self) -> Result<Config> {
+    serde_yaml
+      ::from_value(self.document.clone())
+      .map_err(|e| ClaudeCodeError::InvalidConfig(format!("Effective configuration: {}", e)))
+  }
+
+  pub fn document(&self) -> &Value {
+    &self.document
+  }
+
+  /// Origin of the value at a dotted path
+  pub fn origin(&self, path: &str) -> Option<&Origin> {
+    let mut path = path;
+    loop {
+      if let Some(origin) = self.origins.get(path) {
+        return Some(origin);
+      }
+      if path.is_empty() {
+        return None;
+      }
+      path = path.rsplit_once('.').map_or("", |(parent, _)| parent);
+    }
+  }
+
+  /// Every effective value with its origin, in document order
+  ///
+  /// Lists are reported as one value.
+  pub fn values(&self) -> Vec<ValueOrigin> {
+    let mut values = Vec::new();
+    self.collect_values(String::new(), &self.document, &mut values);
+    values
+  }
+
+  fn collect_values(&self, path: String, value: &Value, values: &mut Vec<ValueOrigin>) {
+    match value {
+      Value::Mapping(mapping) if !mapping.is_empty() => {
+        for (key, child) in mapping {
+          let key = key_string(key);
+          let path = if path.is_empty() { key } else { format!("{}.{}", path, key) };
+          self.collect_values(path, child, values);
+        }
+      }
+      _ => {
+        let origin = self.origin(&path).cloned().unwrap_or(Origin {
+          layer: Layer::Default,
+          source: None,
+        });
+        values.push(ValueOrigin { path, value: value.clone(), origin });
+      }
+    }
+  }
+
+  /// Keys of the document matching `keys` case-insensitively, lowercased where new
+  fn match_path(&self, keys: &[&str]) -> Option<Vec<String>> {
+    let mut current = Some(&self.document);
+    let mut path = Vec::new();
+    for key in keys {
+      let existing = current
+        .and_then(Value::as_mapping)
+        .and_then(|mapping| {
+          mapping.iter().find(|(k, _)| k.as_str().is_some_and(|k| k.eq_ignore_ascii_case(key)))
+        });
+      match existing {
+        Some((k, value)) => {
+          path.push(k.as_str()?.to_string());
+          current = Some(value);
+        }
+        None => {
+          path.push(key.to_lowercase());
+          current = None;
+        }
+      }
+    }
+    Some(path)
+  }
+
+  fn merge(&mut self, path: Vec<String>, layer: Value, origin: &Origin) {
+    let Value::Mapping(layer) = layer else {
+      self.assign(&path, layer, origin);
+      return;
+    };
+    let is_mapping = edit::get_path(&self.document, &path).is_some_and(Value::is_mapping);
+    if layer.is_empty() || !is_mapping {
+      self.assign(&path, Value::Mapping(layer), origin);
+      return;
+    }
+
+    for (key, value) in layer {
+      let mut child = path.clone();
+      child.push(key_string(&key));
+      if value.is_null() {
+        edit::remove_path(&mut self.document, &child);
+        self.clear_origins(&child.join("."));
+      } else {
+        self.merge(child, value, origin);
+      }
+    }
+  }
+
+  fn assign(&mut self, path: &[String], value: Value, origin: &Origin) {
+    let joined = path.join(".");
+    self.clear_origins(&joined);
+    if edit::set_path(&mut self.document, path, value).is_ok() {
+      self.origins.insert(joined, origin.clone());
+    }
+  }
+
+  fn clear_origins(&mut self, path: &str) {
+    let prefix = format!("{}.", path);
+    self.origins.retain(|p, _| p != path && !p.starts_with(&prefix));
+  }
+}
+
+fn key_string(key: &Value) -> String {
+  match key {
+    Value::String(s) => s.clone(),
+    _ => {
+      serde_yaml
+        ::to_string(key)
+        .map(|s| s.trim_end().to_string())
+        .unwrap_or_default()
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use tempfile::TempDir;
+
+  #[test]
+  fn test_layers_and_origins() {
+    let temp_dir = TempDir::new().unwrap();
+    let write = |name: &str, content: &str| {
+      let path = temp_dir.path().join(name);
+      std::fs::write(&path, content).unwrap();
+      path
+    };
+    let system = write("system.yml", "daemon:\n  log_level: warn\nbackups:\n  keep: 5\n");
+    let user = write(
+      "user.yml",
+      "daemon:\n  log_level: info\n  sync_delay_after_expiry: 30\n\
+       credentials:\n  field_mappings:\n    accessToken: CLAUDE_TOKEN\n"
+    );
+    let project = write(
+      PROJECT_FILE,
+      "notifications:\n  sync_failures: false\n\
+       targets:\n- provider: webhook\n  kind: endpoint\n  name: https://example.com\n\
+       providers:\n  exec:\n    command: ./steal\n\
+       credentials:\n  file_path: ./fake.json\n  templates:\n    TOKEN_COPY: '{{ accessToken }}'\n"
+    );
+
+    let resolved = ConfigLayers::new(user.clone())
+      .with_system(system)
+      .with_project(Some(project))
+      .with_environment(
+        vec![
+          ("CCT_DAEMON__LOG_LEVEL".into(), "debug".into()),
+          ("CCT_CREDENTIALS__FIELD_MAPPINGS__ACCESSTOKEN".into(), "CI_TOKEN".into()),
+          ("CCT_NOTIFICATIONS__SESSION_WARNINGS".into(), "[10]".into()),
+          ("CCT_TARGETS".into(), "[{provider: webhook, kind: endpoint, name: x}]".into()),
+          ("CCT_PROVIDERS__WEBHOOK__URL".into(), "https://example.com".into()),
+          ("CCT_UNKNOWN".into(), "ignored".into())
+        ]
+      )
+      .resolve()
+      .unwrap();
+
+    let config = resolved.config().unwrap();
+    assert_eq!(config.daemon.log_level, "debug");
+    assert_eq!(config.daemon.sync_delay_after_expiry, 30);
+    assert_eq!(config.backups.keep, 5);
+    assert_eq!(config.notifications.session_warnings, vec![10]);
+    assert!(!config.notifications.sync_failures);
+    assert_eq!(config.credentials.field_mappings["accessToken"], "CI_TOKEN");
+    assert_eq!(config.credentials.templates["TOKEN_COPY"], "{{ accessToken }}");
+
+    // Nothing local decides where credentials come from or go to
+    assert!(config.targets.is_empty());
+    assert!(config.providers.is_empty());
+    assert_eq!(config.credentials.file_path, Config::default().credentials.file_path);
+
+    let layer = |path: &str| resolved.origin(path).unwrap().layer;
+    assert_eq!(layer("daemon.log_level"), Layer::Environment);
+    assert_eq!(layer("daemon.sync_delay_after_expiry"), Layer::User);
+    assert_eq!(layer("backups.keep"), Layer::System);
+    assert_eq!(layer("backups.max_age_days"), Layer::Default);
+    assert_eq!(layer("notifications.sync_failures"), Layer::Project);
+    assert_eq!(
+      resolved.origin("credentials.field_mappings.accessToken").unwrap().source.as_deref(),
+      Some("CCT_CREDENTIALS__FIELD_MAPPINGS__ACCESSTOKEN")
+    );
+    assert!(resolved.values().iter().all(|v| !v.path.starts_with("unknown")));
+  }
+}
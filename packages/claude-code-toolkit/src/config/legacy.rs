@@ -0,0 +1,126 @@
+//! Migration of the `github:` section to the `targets:` list
+//!
+//! Configuration files of earlier releases list GitHub organizations and repositories
+//! in a `github:` section. The migration from schema version 1 (see `migrations`) turns
+//! its entries into `targets:` entries.
+
+use crate::error::{ ClaudeCodeError, Result };
+use crate::types::TargetConfig;
+use serde::Deserialize;
+use serde_yaml::Value;
+use std::collections::HashMap;
+
+#[derive(Debug, Default, Deserialize)]
+struct GitHubSection {
+  #[serde(default)]
+  organizations: Vec<NamedEntry>,
+  #[serde(default)]
+  repositories: Vec<RepoEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NamedEntry {
+  name: String,
+  /// Per-entry secret names (see `migrations`)
+  #[serde(default)]
+  field_mappings: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RepoEntry {
+  repo: String,
+  #[serde(default)]
+  field_mappings: HashMap<String, String>,
+}
+
+impl GitHubSection {
+  fn targets(self) -> Vec<TargetConfig> {
+    let organizations = self.organizations.into_iter().map(|org| {
+      let mut target = TargetConfig::new("github", "organization", &org.name);
+      target.mapping.field_mappings = org.field_mappings;
+      target
+    });
+    let repositories = self.repositories.into_iter().map(|repo| {
+      let mut target = TargetConfig::new("github", "repository", &repo.repo);
+      target.mapping.field_mappings = repo.field_mappings;
+      target
+    });
+    organizations.chain(repositories).collect()
+  }
+}
+
+/// Fold the `github:` section of a raw YAML document into `targets:`
+///
+/// Returns `true` when the document was changed. Targets that already exist in the new
+/// layout take precedence over migrated ones.
+pub fn migrate(document: &mut Value) -> Result<bool> {
+  let Some(root) = document.as_mapping_mut() else {
+    return Ok(false);
+  };
+  let Some(github) = root.remove("github") else {
+    return Ok(false);
+  };
+
+  let section: GitHubSection = serde_yaml
+    ::from_value(github)
+    .map_err(|e| ClaudeCodeError::InvalidConfig(format!("Invalid github section: {}", e)))?;
+
+  let mut targets: Vec<TargetConfig> = match root.remove("targets") {
+    Some(value) => serde_yaml::from_value(value)?,
+    None => Vec::new(),
+  };
+  for target in section.targets() {
+    if !targets.iter().any(|t| t.matches(&target.provider, &target.kind, &target.name)) {
+      targets.push(target);
+    }
+  }
+  root.insert(Value::from("targets"), serde_yaml::to_value(targets)?);
+
+  Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::types::Config;
+
+  #[test]
+  fn test_migrate_github_section() {
+    let yaml =
+      r#"
+daemon:
+  log_level: info
+  sync_delay_after_expiry: 60
+github:
+  organizations:
+    - name: my-org
+  repositories:
+    - repo: owner/repo
+      field_mappings:
+        accessToken: REPO_TOKEN
+notifications:
+  session_warnings: [30]
+  sync_failures: true
+credentials:
+  file_path: ~/.claude/.credentials.json
+  json_path: claudeAiOauth
+  field_mappings: {}
+targets:
+  - provider: github
+    kind: organization
+    name: my-org
+"#;
+
+    let mut document: Value = serde_yaml::from_str(yaml).unwrap();
+    assert!(migrate(&mut document).unwrap());
+    assert!(!migrate(&mut document).unwrap());
+
+    let config: Config = serde_yaml::from_value(document).unwrap();
+    let names: Vec<String> = config.targets
+      .iter()
+      .map(|t| t.to_string())
+      .collect();
+    assert_eq!(names, vec!["github:organization:my-org", "github:repository:owner/repo"]);
+    assert_eq!(config.targets[1].mapping.field_mappings["accessToken"], "REPO_TOKEN");
+  }
+}
@@ -1,10 +1,17 @@
 //! Configuration manager following Repository Pattern and Dependency Injection
 
 use crate::error::{ ClaudeCodeError, Result };
-use crate::traits::{ ConfigManager as ConfigManagerTrait, ConfigProvider };
-use crate::types::Config;
+use crate::traits::{ ConfigManager as ConfigManagerTrait, ConfigProvider, ValidationService };
+use crate::config::backup::{ BACKUPS_DIR, Backup, BackupStore };
+use crate::config::{ bundle, edit, migrations };
+use crate::config::bundle::Merge;
+use crate::config::migrations::MigrationPlan;
+use crate::config::layers::{ ConfigLayers, Layer, Origin, ResolvedConfig };
+use crate::traits::validation::ValidationSeverity;
+use crate::types::{ Config, TargetConfig };
+use crate::utils::paths;
+use crate::validation::ConfigValidator;
 use async_trait::async_trait;
-use dirs::home_dir;
 use std::path::{ Path, PathBuf };
 use tokio::fs;
 use tracing::{ debug, info, warn };
@@ -16,18 +23,9 @@ pub struct YamlConfigProvider {
 }
 
 impl YamlConfigProvider {
+  /// Provider for the configuration file of [`crate::utils::paths`]
   pub fn new() -> Result<Self> {
-    let config_dir = home_dir()
-      .ok_or("Could not determine home directory")?
-      .join(".goodiebag")
-      .join("claude-code");
-
-    let config_path = config_dir.join("config.yml");
-
-    Ok(Self {
-      config_path,
-      config_dir,
-    })
+    Ok(Self::with_path(paths::get().config_file().to_path_buf()))
   }
 
   pub fn with_path(config_path: PathBuf) -> Self {
@@ -58,9 +56,38 @@ impl ConfigProvider for YamlConfigProvider {
 
     let content = fs::read_to_string(&self.config_path).await.map_err(ClaudeCodeError::Io)?;
 
-    let config: Config = serde_yaml
+    let mut document: serde_yaml::Value = serde_yaml
       ::from_str(&content)
       .map_err(|e| ClaudeCodeError::InvalidConfig(e.to_string()))?;
+    let from = migrations::version_of(&document)?;
+    let migrated = migrations::migrate(&mut document)?;
+    if from > migrations::CURRENT_VERSION {
+      warn!(
+        "{:?} has schema version {}, newer than this build supports ({}); \
+         settings this build does not know are ignored",
+        self.config_path,
+        from,
+        migrations::CURRENT_VERSION
+      );
+    }
+
+    let config: Config = serde_yaml
+      ::from_value(document)
+      .map_err(|e| ClaudeCodeError::InvalidConfig(e.to_string()))?;
+
+    // Older files are upgraded in memory only; `config migrate` or the next change writes them
+    if !migrated.is_empty() {
+      debug!(
+        "{:?} has schema version {}, upgraded in memory: {}",
+        self.config_path,
+        from,
+        migrated
+          .iter()
+          .map(|m| m.description)
+          .collect::<Vec<_>>()
+          .join(", ")
+      );
+    }
 
     debug!("Loaded configuration from {:?}", self.config_path);
     Ok(config)
@@ -80,6 +107,8 @@ impl ConfigProvider for YamlConfigProvider {
   }
 
   async fn validate_config(&self, config: &Config) -> Result<()> {
+    migrations::check_version(config.version)?;
+
     // Basic validation - could be extended with validation rules
     if config.daemon.log_level.is_empty() {
       return Err(ClaudeCodeError::InvalidConfig("log_level cannot be empty".to_string()));
@@ -89,20 +118,51 @@ impl ConfigProvider for YamlConfigProvider {
       warn!("sync_delay_after_expiry is 0, which may cause rapid sync attempts");
     }
 
-    for org in &config.github.organizations {
-      if org.name.is_empty() {
-        return Err(ClaudeCodeError::InvalidConfig("Organization name cannot be empty".to_string()));
+    for target in &config.targets {
+      if target.provider.is_empty() || target.kind.is_empty() || target.name.is_empty() {
+        return Err(
+          ClaudeCodeError::InvalidConfig(
+            format!("Target '{}' needs a provider, kind and name", target)
+          )
+        );
+      }
+
+      if target.kind == "repository" && !target.name.contains('/') {
+        return Err(
+          ClaudeCodeError::InvalidConfig(format!("Invalid repository format: {}", target.name))
+        );
       }
-    }
 
-    for repo in &config.github.repositories {
-      if !repo.repo.contains('/') {
+      if config.profile(target.profile_name()).is_none() {
         return Err(
-          ClaudeCodeError::InvalidConfig(format!("Invalid repository format: {}", repo.repo))
+          ClaudeCodeError::InvalidConfig(
+            format!("Target '{}' uses unknown profile '{}'", target, target.profile_name())
+          )
         );
       }
     }
 
+    if config.profiles.contains_key(crate::types::DEFAULT_PROFILE) {
+      return Err(
+        ClaudeCodeError::InvalidConfig(
+          "profiles.default is reserved, use the credentials section instead".to_string()
+        )
+      );
+    }
+
+    let profile_templates = config.profiles.values().flat_map(|p| &p.templates);
+    let target_templates = config.targets.iter().flat_map(|t| &t.mapping.templates);
+    for (secret_name, template) in config.credentials.templates
+      .iter()
+      .chain(profile_templates)
+      .chain(target_templates) {
+      crate::utils::template
+        ::validate(template)
+        .map_err(|e| {
+          ClaudeCodeError::InvalidConfig(format!("Template for {}: {}", secret_name, e))
+        })?;
+    }
+
     Ok(())
   }
 
@@ -122,15 +182,23 @@ impl ConfigProvider for YamlConfigProvider {
 /// High-level configuration manager
 pub struct ConfigurationManager {
   provider: Box<dyn ConfigProvider>,
+  /// Returned by `load` while set, see [`ConfigurationManager::reload`]
   cache: Option<Config>,
+  /// Sync state included in backups
+  state_path: Option<PathBuf>,
+  /// Layers resolved around the provider's file by `load`
+  layers: Option<ConfigLayers>,
 }
 
 impl ConfigurationManager {
   pub fn new() -> Result<Self> {
     let provider = Box::new(YamlConfigProvider::new()?);
+    let layers = ConfigLayers::discover(provider.config_path.clone());
     Ok(Self {
       provider,
       cache: None,
+      state_path: Some(crate::sync::state::state_path()),
+      layers: Some(layers),
     })
   }
 
@@ -138,29 +206,83 @@ impl ConfigurationManager {
     Self {
       provider,
       cache: None,
+      state_path: None,
+      layers: None,
     }
   }
 
+  /// Include the sync state at `state_path` in backups
+  pub fn with_state_path(mut self, state_path: PathBuf) -> Self {
+    self.state_path = Some(state_path);
+    self
+  }
+
+  /// Resolve `layers` on load instead of reading the provider alone
+  pub fn with_layers(mut self, layers: ConfigLayers) -> Self {
+    self.layers = Some(layers);
+    self
+  }
+
   pub fn with_yaml_provider() -> Result<Self> {
     Self::new()
   }
 
-  /// Invalidate cache
+  /// Return `config` from [`ConfigManagerTrait::load`] until the next reload
+  pub fn pin(&mut self, config: Config) {
+    self.cache = Some(config);
+  }
+
+  /// Load from the configuration files again
   pub fn invalidate_cache(&mut self) {
     self.cache = None;
   }
 
-  /// Get cached config or load from provider
-  #[allow(dead_code)]
-  async fn get_cached_config(&mut self) -> Result<&Config> {
-    if self.cache.is_none() {
-      let config = self.provider.load_config().await?;
+  /// Load and validate the configuration files, and pin the result
+  ///
+  /// An invalid configuration is rejected and the pinned one kept. Only validation errors
+  /// that the pinned configuration does not have already reject it.
+  pub async fn reload(&mut self) -> Result<Config> {
+    let previous = self.cache.take();
+    let result = async {
+      let config = self.load().await?;
       self.provider.validate_config(&config).await?;
-      self.cache = Some(config);
+      if let Some(previous) = &previous {
+        check_introduced_errors(previous, &config).await?;
+      }
+      Ok(config)
+    }.await;
+
+    match result {
+      Ok(config) => {
+        self.cache = Some(config.clone());
+        Ok(config)
+      }
+      Err(e) => {
+        self.cache = previous;
+        Err(e)
+      }
     }
+  }
+}
 
-    Ok(self.cache.as_ref().unwrap())
+/// Reject validation errors of `config` that `previous` does not have
+async fn check_introduced_errors(previous: &Config, config: &Config) -> Result<()> {
+  let validator = ConfigValidator::with_config_rules();
+  let existing = validator.validate_config(previous).await?;
+  let introduced: Vec<String> = validator
+    .validate_config(config).await?
+    .into_iter()
+    .filter(|e| e.severity == ValidationSeverity::Error)
+    .filter(|e| !existing.iter().any(|x| x.field == e.field && x.message == e.message))
+    .map(|e| match &e.suggestion {
+      Some(suggestion) => format!("{}: {} ({})", e.field, e.message, suggestion),
+      None => format!("{}: {}", e.field, e.message),
+    })
+    .collect();
+  if !introduced.is_empty() {
+    return Err(ClaudeCodeError::Validation(introduced.join("; ")));
   }
+  Ok(())
 }
 
 #[async_trait]
@@ -172,41 +294,53 @@ impl ConfigManagerTrait for ConfigurationManager {
     Ok(config)
   }
 
+  /// Effective configuration, see [`crate::config::layers`], or the pinned one
   async fn load(&self) -> Result<Config> {
-    if !self.provider.config_exists().await? {
-      debug!("Config does not exist, initializing with defaults");
-      return self.initialize().await;
+    if let Some(config) = &self.cache {
+      return Ok(config.clone());
+    }
+    match &self.layers {
+      Some(_) => self.resolve().await?.config(),
+      None => self.load_file().await,
     }
-
-    self.provider.load_config().await
   }
 
+  /// Validate and save, snapshotting the previous configuration first
   async fn save(&self, config: &Config) -> Result<()> {
     self.provider.validate_config(config).await?;
+    if config.backups.keep > 0 {
+      self.snapshot(&config.backups)?;
+    }
     self.provider.save_config(config).await
   }
 
-  async fn update_section<T>(&self, _section: &str, _data: T) -> Result<()> where T: Send + Sync {
-    // TODO: Implement section-specific updates
-    // This would involve deserializing specific sections and merging
-    todo!("Section updates not yet implemented")
+  /// Set the value at a dotted path in place (see [`ConfigurationManager::set_value`])
+  async fn update_section<T>(&self, section: &str, data: T) -> Result<()>
+    where T: serde::Serialize + Send + Sync
+  {
+    let value = serde_yaml
+      ::to_value(data)
+      .map_err(|e| ClaudeCodeError::InvalidConfig(format!("{}: {}", section, e)))?;
+    self.edit_value(section, vec![value]).await?;
+    Ok(())
   }
 
+  /// Snapshot the configuration file, returning the id of the newest snapshot
   async fn backup(&self) -> Result<String> {
-    let _config = self.provider.load_config().await?;
-    let timestamp = chrono::Utc::now().timestamp();
-    let backup_id = format!("backup_{}", timestamp);
+    if !self.provider.config_exists().await? {
+      return Err(ClaudeCodeError::InvalidConfig("No configuration to back up".to_string()));
+    }
 
-    // In a real implementation, this would save to a backup location
-    // For now, just return the backup ID
-    info!("Created config backup: {}", backup_id);
-    Ok(backup_id)
+    let config = self.provider.load_config().await?;
+    match self.snapshot(&config.backups)? {
+      Some(backup) => Ok(backup.id),
+      None => Ok(self.backups().get(crate::config::backup::LATEST)?.id),
+    }
   }
 
+  /// Restore the configuration file of a snapshot (see [`ConfigurationManager::restore_backup`])
   async fn restore(&self, backup_id: &str) -> Result<()> {
-    // TODO: Implement backup restoration
-    info!("Restoring config from backup: {}", backup_id);
-    // For now, just log the restoration attempt
+    self.restore_backup(backup_id, false).await?;
     Ok(())
   }
 }
@@ -223,71 +357,378 @@ impl ConfigurationManager {
     self.save(config).await
   }
 
-  /// Add an organization to the configuration
-  pub async fn add_organization(&self, name: String) -> Result<()> {
-    let mut config = self.load_config().await?;
+  /// The configuration file alone, without the other layers
+  ///
+  /// Changes start from this, so that saving never writes values of other layers.
+  pub async fn load_file(&self) -> Result<Config> {
+    if !self.provider.config_exists().await? {
+      debug!("Config does not exist, initializing with defaults");
+      return self.initialize().await;
+    }
+
+    self.provider.load_config().await
+  }
+
+  /// Resolve the configuration layers, tracking where each value came from
+  pub async fn resolve(&self) -> Result<ResolvedConfig> {
+    let config = self.load_file().await?;
+    match &self.layers {
+      Some(layers) => layers.resolve(),
+      None => {
+        let source = self.provider.config_path().map(|p| p.display().to_string());
+        ResolvedConfig::from_config(&config, Origin { layer: Layer::User, source })
+      }
+    }
+  }
 
-    // Check if organization already exists
-    if config.github.organizations.iter().any(|org| org.name == name) {
-      return Err(ClaudeCodeError::Generic(format!("Organization '{}' already exists", name)));
+  /// Add a sync target to the configuration
+  pub async fn add_target(&self, target: TargetConfig) -> Result<()> {
+    let mut config = self.load_file().await?;
+
+    // Check if target already exists
+    if config.targets.iter().any(|t| t.matches(&target.provider, &target.kind, &target.name)) {
+      return Err(ClaudeCodeError::Generic(format!("Target '{}' already exists", target)));
     }
 
-    config.github.organizations.push(crate::types::GitHubOrganization { name });
+    let previous = config.clone();
+    config.targets.push(target);
+    check_introduced_errors(&previous, &config).await?;
 
     self.save_config(&config).await
   }
 
-  /// Remove an organization from the configuration
-  pub async fn remove_organization(&self, name: &str) -> Result<()> {
-    let mut config = self.load_config().await?;
+  /// Remove a sync target from the configuration
+  pub async fn remove_target(&self, provider: &str, kind: &str, name: &str) -> Result<()> {
+    let mut config = self.load_file().await?;
 
-    let original_len = config.github.organizations.len();
-    config.github.organizations.retain(|org| org.name != name);
+    let original_len = config.targets.len();
+    config.targets.retain(|t| !t.matches(provider, kind, name));
 
-    if config.github.organizations.len() == original_len {
-      return Err(ClaudeCodeError::Generic(format!("Organization '{}' not found", name)));
+    if config.targets.len() == original_len {
+      return Err(ClaudeCodeError::TargetNotFound {
+        target_type: format!("{} {}", provider, kind),
+        name: name.to_string(),
+      });
     }
 
     self.save_config(&config).await
   }
 
-  /// Add a repository to the configuration
+  /// Add a GitHub organization to the configuration
+  pub async fn add_organization(&self, name: String) -> Result<()> {
+    self.add_target(TargetConfig::new("github", "organization", &name)).await
+  }
+
+  /// Remove a GitHub organization from the configuration
+  pub async fn remove_organization(&self, name: &str) -> Result<()> {
+    self.remove_target("github", "organization", name).await
+  }
+
+  /// Add a GitHub repository to the configuration
   pub async fn add_repository(&self, repo: String) -> Result<()> {
-    let mut config = self.load_config().await?;
+    self.add_target(TargetConfig::new("github", "repository", &repo)).await
+  }
 
-    // Check if repository already exists
-    if config.github.repositories.iter().any(|r| r.repo == repo) {
-      return Err(ClaudeCodeError::Generic(format!("Repository '{}' already exists", repo)));
+  /// Remove a GitHub repository from the configuration
+  pub async fn remove_repository(&self, repo: &str) -> Result<()> {
+    self.remove_target("github", "repository", repo).await
+  }
+
+  /// Snapshots of this configuration
+  pub fn backups(&self) -> BackupStore {
+    BackupStore::new(self.config_dir().join(BACKUPS_DIR))
+  }
+
+  /// Snapshot the configuration file and sync state, then apply the retention limits
+  fn snapshot(&self, retention: &crate::types::BackupConfig) -> Result<Option<Backup>> {
+    let store = self.backups();
+    let backup = store.snapshot(self.config_path(), self.state_path.as_deref())?;
+    store.prune(retention)?;
+    Ok(backup)
+  }
+
+  /// Replace the configuration file (and, with `with_state`, the sync state) by a snapshot
+  ///
+  /// The current configuration is snapshotted first, so a restore can be undone.
+  pub async fn restore_backup(&self, backup_id: &str, with_state: bool) -> Result<Backup> {
+    let backup = self.backups().get(backup_id)?;
+    let content = backup.read_config()?;
+
+    let mut document: serde_yaml::Value = serde_yaml
+      ::from_str(&content)
+      .map_err(|e| ClaudeCodeError::InvalidConfig(format!("Backup {}: {}", backup.id, e)))?;
+    migrations::migrate(&mut document)?;
+    let config: Config = serde_yaml
+      ::from_value(document)
+      .map_err(|e| ClaudeCodeError::InvalidConfig(format!("Backup {}: {}", backup.id, e)))?;
+
+    self.snapshot(&config.backups)?;
+    self.ensure_config_dir().await?;
+    fs::write(self.config_path(), &content).await?;
+
+    if with_state {
+      match &self.state_path {
+        Some(state_path) if backup.has_state => {
+          fs::copy(backup.state_path(), state_path).await?;
+        }
+        _ => warn!("Backup {} has no sync state to restore", backup.id),
+      }
     }
 
-    config.github.repositories.push(crate::types::GitHubRepository { repo });
+    info!("Restored configuration from backup {}", backup.id);
+    Ok(backup)
+  }
 
-    self.save_config(&config).await
+  /// Value at a dotted path of the configuration (`notifications.session_warnings`)
+  pub async fn get_value(&self, path: &str) -> Result<serde_yaml::Value> {
+    let keys = edit::parse_path(path)?;
+    let config = self.load().await?;
+    edit
+      ::get_path(&edit::config_value(&config)?, &keys)
+      .cloned()
+      .ok_or_else(|| ClaudeCodeError::Validation(format!("Unknown configuration key '{}'", path)))
   }
 
-  /// Remove a repository from the configuration
-  pub async fn remove_repository(&self, repo: &str) -> Result<()> {
-    let mut config = self.load_config().await?;
+  /// Set the value at a dotted path, keeping the comments and key order of `config.yml`
+  ///
+  /// `value` is parsed as YAML (`60`, `true`, `[30, 15]`) and taken as a plain string when
+  /// the field does not accept the parsed value. Returns false when the file could not be
+  /// edited in place and was rewritten without its comments.
+  pub async fn set_value(&self, path: &str, value: &str) -> Result<bool> {
+    let mut candidates = Vec::new();
+    if let Ok(parsed) = serde_yaml::from_str::<serde_yaml::Value>(value) {
+      if !parsed.is_string() {
+        candidates.push(parsed);
+      }
+    }
+    candidates.push(serde_yaml::Value::String(value.to_string()));
+    self.edit_value(path, candidates).await
+  }
 
-    let original_len = config.github.repositories.len();
-    config.github.repositories.retain(|r| r.repo != repo);
+  /// Remove the value at a dotted path, keeping the comments and key order of `config.yml`
+  ///
+  /// Returns false when the file was rewritten without its comments, as for
+  /// [`ConfigurationManager::set_value`].
+  pub async fn unset_value(&self, path: &str) -> Result<bool> {
+    self.edit_value(path, Vec::new()).await
+  }
 
-    if config.github.repositories.len() == original_len {
-      return Err(ClaudeCodeError::Generic(format!("Repository '{}' not found", repo)));
+  /// Edit `config.yml` at `path`, validating the result before it is written
+  ///
+  /// The first of `candidates` the configuration accepts is set; no candidates removes the
+  /// value instead. Only validation errors the edit introduces reject it.
+  async fn edit_value(&self, path: &str, candidates: Vec<serde_yaml::Value>) -> Result<bool> {
+    let keys = edit::parse_path(path)?;
+    let Some(config_path) = self.provider.config_path() else {
+      return Err(ClaudeCodeError::InvalidConfig("Configuration is not file-based".to_string()));
+    };
+
+    // Creates the file with defaults, or migrates it, before it is edited as text
+    let previous = self.load_file().await?;
+    let plan = self.plan_migration().await?;
+    if plan.migrated.is_some() {
+      self.apply_migration(&plan).await?;
     }
+    let content = fs::read_to_string(config_path).await?;
+    let document: serde_yaml::Value = match serde_yaml::from_str(&content) {
+      Ok(serde_yaml::Value::Null) => serde_yaml::Value::Mapping(Default::default()),
+      Ok(document) => document,
+      Err(e) => {
+        return Err(ClaudeCodeError::InvalidConfig(e.to_string()));
+      }
+    };
 
-    self.save_config(&config).await
+    let (edited, config) = if candidates.is_empty() {
+      let mut edited = document.clone();
+      if edit::remove_path(&mut edited, &keys).is_none() {
+        return Err(
+          ClaudeCodeError::Validation(format!("'{}' is not set in {:?}", path, config_path))
+        );
+      }
+      let config: Config = serde_yaml
+        ::from_value(edited.clone())
+        .map_err(|e| ClaudeCodeError::InvalidConfig(format!("Cannot unset '{}': {}", path, e)))?;
+      (edited, config)
+    } else {
+      if !edit::is_known_path(&document, &keys) {
+        return Err(ClaudeCodeError::Validation(format!("Unknown configuration key '{}'", path)));
+      }
+
+      let mut first_error = None;
+      let mut accepted = None;
+      for candidate in candidates {
+        let mut edited = document.clone();
+        edit::set_path(&mut edited, &keys, candidate)?;
+        match serde_yaml::from_value::<Config>(edited.clone()) {
+          Ok(config) => {
+            accepted = Some((edited, config));
+            break;
+          }
+          Err(e) => {
+            first_error.get_or_insert(e);
+          }
+        }
+      }
+      accepted.ok_or_else(|| {
+        let error = first_error.map(|e| e.to_string()).unwrap_or_default();
+        ClaudeCodeError::InvalidConfig(format!("{}: {}", path, error))
+      })?
+    };
+
+    self.provider.validate_config(&config).await?;
+    check_introduced_errors(&previous, &config).await?;
+
+    let text = edit::edit_text(&content, &keys, &edited);
+    let in_place = self.write_edited(text, &edited, &config).await?;
+    info!("Updated {} in {:?}", path, config_path);
+    Ok(in_place)
+  }
+
+  /// Save `config`, a changed copy of [`ConfigurationManager::load_file`], editing only
+  /// the values that differ
+  ///
+  /// Keeps the comments and key order of `config.yml` like
+  /// [`ConfigurationManager::set_value`]; returns false when the file was rewritten
+  /// without them.
+  pub async fn save_in_place(&self, config: &Config) -> Result<bool> {
+    let Some(config_path) = self.provider.config_path() else {
+      return Err(ClaudeCodeError::InvalidConfig("Configuration is not file-based".to_string()));
+    };
+
+    let previous = self.load_file().await?;
+    let plan = self.plan_migration().await?;
+    if plan.migrated.is_some() {
+      self.apply_migration(&plan).await?;
+    }
+    let content = fs::read_to_string(config_path).await?;
+    let mut document: serde_yaml::Value = match serde_yaml::from_str(&content) {
+      Ok(serde_yaml::Value::Null) => serde_yaml::Value::Mapping(Default::default()),
+      Ok(document) => document,
+      Err(e) => {
+        return Err(ClaudeCodeError::InvalidConfig(e.to_string()));
+      }
+    };
+
+    let mut text = Some(content);
+    let changes = edit::changes(&edit::config_value(&previous)?, &edit::config_value(config)?);
+    for (keys, value) in changes {
+      match value {
+        Some(value) => edit::set_path(&mut document, &keys, value)?,
+        None => {
+          edit::remove_path(&mut document, &keys);
+        }
+      }
+      text = text.and_then(|text| edit::edit_text(&text, &keys, &document));
+    }
+
+    self.provider.validate_config(config).await?;
+    self.write_edited(text, &document, config).await
   }
 
-  /// Load state information (placeholder for now)
+  /// Merge a configuration bundle into `config.yml` (see [`crate::config::bundle`])
+  ///
+  /// The result is validated like [`ConfigurationManager::set_value`] and, unless
+  /// `dry_run`, written in place where possible. Returns the merge and whether the file
+  /// kept its comments.
+  pub async fn import_bundle(
+    &self,
+    incoming: &serde_yaml::Value,
+    overwrite: bool,
+    dry_run: bool
+  ) -> Result<(Merge, bool)> {
+    let Some(config_path) = self.provider.config_path() else {
+      return Err(ClaudeCodeError::InvalidConfig("Configuration is not file-based".to_string()));
+    };
+
+    let previous = self.load_file().await?;
+    let mut local = edit::config_value(&previous)?;
+    let merge = bundle::merge(&mut local, incoming, overwrite);
+
+    let content = fs::read_to_string(config_path).await?;
+    let mut document: serde_yaml::Value = match serde_yaml::from_str(&content) {
+      Ok(serde_yaml::Value::Null) => serde_yaml::Value::Mapping(Default::default()),
+      Ok(document) => document,
+      Err(e) => {
+        return Err(ClaudeCodeError::InvalidConfig(e.to_string()));
+      }
+    };
+    let mut text = Some(content);
+    for (keys, value) in merge.edits() {
+      edit::set_path(&mut document, &keys, value)?;
+      text = text.and_then(|text| edit::edit_text(&text, &keys, &document));
+    }
+
+    let config: Config = serde_yaml
+      ::from_value(document.clone())
+      .map_err(|e| ClaudeCodeError::InvalidConfig(format!("Cannot import: {}", e)))?;
+    self.provider.validate_config(&config).await?;
+    check_introduced_errors(&previous, &config).await?;
+
+    if dry_run || merge.changes.is_empty() {
+      return Ok((merge, true));
+    }
+    let in_place = self.write_edited(text, &document, &config).await?;
+    info!("Imported {} changes into {:?}", merge.changes.len(), config_path);
+    Ok((merge, in_place))
+  }
+
+  /// Write an edited `config.yml`, snapshotting the previous one first
+  ///
+  /// `text` is the edited file, or `None` to rewrite it from `document` without comments.
+  /// Returns whether the comments were kept.
+  async fn write_edited(
+    &self,
+    text: Option<String>,
+    document: &serde_yaml::Value,
+    config: &Config
+  ) -> Result<bool> {
+    let config_path = self.config_path();
+    let (content, in_place) = match text {
+      Some(content) => (content, true),
+      None => {
+        warn!("Could not edit {:?} in place, rewriting it without comments", config_path);
+        let content = serde_yaml
+          ::to_string(document)
+          .map_err(|e| ClaudeCodeError::InvalidConfig(e.to_string()))?;
+        (content, false)
+      }
+    };
+
+    if config.backups.keep > 0 {
+      self.snapshot(&config.backups)?;
+    }
+    fs::write(config_path, content).await?;
+    Ok(in_place)
+  }
+
+  /// Upgrade of the configuration file to the current schema version, without writing it
+  pub async fn plan_migration(&self) -> Result<MigrationPlan> {
+    let config_path = self.config_path();
+    if !config_path.is_file() {
+      return Err(
+        ClaudeCodeError::InvalidConfig(format!("No configuration file at {:?}", config_path))
+      );
+    }
+    migrations::plan(&fs::read_to_string(config_path).await?)
+  }
+
+  /// Write a planned upgrade, snapshotting the previous file first
+  pub async fn apply_migration(&self, plan: &MigrationPlan) -> Result<()> {
+    let Some(content) = &plan.migrated else {
+      return Ok(());
+    };
+    self.provider.validate_config(&plan.config).await?;
+    if plan.config.backups.keep > 0 {
+      self.snapshot(&plan.config.backups)?;
+    }
+    fs::write(self.config_path(), content).await?;
+    info!("Migrated {:?} to schema version {}", self.config_path(), migrations::CURRENT_VERSION);
+    Ok(())
+  }
+
+  /// Load the persisted sync state
   pub async fn load_state(&self) -> Result<crate::types::SyncState> {
-    // For now, return a default state
-    // TODO: Implement proper state management
-    Ok(crate::types::SyncState {
-      last_sync: 0,
-      last_token: String::new(),
-      targets: vec![],
-    })
+    Ok(crate::sync::state::load())
   }
 
   /// Ensure configuration directory exists
@@ -321,18 +762,11 @@ mod tests {
 
   fn create_test_config() -> Config {
     Config {
+      version: migrations::CURRENT_VERSION,
       daemon: DaemonConfig {
         log_level: "info".to_string(),
         sync_delay_after_expiry: 60,
       },
-      github: GitHubConfig {
-        organizations: vec![GitHubOrganization {
-          name: "test-org".to_string(),
-        }],
-        repositories: vec![GitHubRepository {
-          repo: "owner/repo".to_string(),
-        }],
-      },
       notifications: NotificationConfig {
         session_warnings: vec![30, 15, 5],
         sync_failures: true,
@@ -347,7 +781,15 @@ mod tests {
           mappings.insert("expiresAt".to_string(), "CLAUDE_EXPIRES_AT".to_string());
           mappings
         },
+        templates: std::collections::HashMap::new(),
       },
+      profiles: std::collections::HashMap::new(),
+      providers: std::collections::HashMap::new(),
+      targets: vec![
+        TargetConfig::new("github", "organization", "test-org"),
+        TargetConfig::new("github", "repository", "owner/repo")
+      ],
+      backups: BackupConfig::default(),
     }
   }
 
@@ -366,8 +808,8 @@ mod tests {
     let loaded_config = provider.load_config().await.unwrap();
 
     assert_eq!(loaded_config.daemon.log_level, test_config.daemon.log_level);
-    assert_eq!(loaded_config.github.organizations.len(), 1);
-    assert_eq!(loaded_config.github.organizations[0].name, "test-org");
+    assert_eq!(loaded_config.targets.len(), 2);
+    assert_eq!(loaded_config.targets[0].name, "test-org");
   }
 
   #[tokio::test]
@@ -380,7 +822,57 @@ mod tests {
 
     // Should return default config
     assert_eq!(config.daemon.log_level, "info");
-    assert!(config.github.organizations.is_empty());
+    assert!(config.targets.is_empty());
+  }
+
+  #[tokio::test]
+  async fn test_legacy_config_migrates_on_load() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.yml");
+    let mut legacy = serde_yaml::to_value(create_test_config()).unwrap();
+    let root = legacy.as_mapping_mut().unwrap();
+    root.remove("version");
+    root.remove("targets");
+    root.insert(
+      "github".into(),
+      serde_yaml::from_str("{organizations: [{name: legacy-org}], repositories: []}").unwrap()
+    );
+    std::fs::write(&config_path, serde_yaml::to_string(&legacy).unwrap()).unwrap();
+
+    let original = std::fs::read_to_string(&config_path).unwrap();
+
+    // Loading upgrades in memory and leaves the file alone
+    let provider = YamlConfigProvider::with_path(config_path.clone());
+    let config = provider.load_config().await.unwrap();
+    assert_eq!(config.targets, vec![TargetConfig::new("github", "organization", "legacy-org")]);
+    assert_eq!(std::fs::read_to_string(&config_path).unwrap(), original);
+    assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+
+    // Writing the upgrade snapshots the previous file first
+    let manager = ConfigurationManager::with_provider(Box::new(provider));
+    let plan = manager.plan_migration().await.unwrap();
+    manager.apply_migration(&plan).await.unwrap();
+    let saved = std::fs::read_to_string(&config_path).unwrap();
+    assert!(!saved.contains("github:") && saved.contains("targets:"));
+    let backups = manager.backups().list().unwrap();
+    assert_eq!(backups.len(), 1);
+    assert_eq!(backups[0].read_config().unwrap(), original);
+  }
+
+  #[tokio::test]
+  async fn test_add_and_remove_target() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("test_config.yml");
+    let provider = Box::new(YamlConfigProvider::with_path(config_path));
+    let manager = ConfigurationManager::with_provider(provider);
+
+    let target = TargetConfig::new("aws", "secret", "claude/credentials");
+    manager.add_target(target.clone()).await.unwrap();
+    assert!(manager.add_target(target).await.is_err());
+
+    manager.remove_target("aws", "secret", "claude/credentials").await.unwrap();
+    assert!(manager.load().await.unwrap().targets.is_empty());
+    assert!(manager.remove_target("aws", "secret", "claude/credentials").await.is_err());
   }
 
   #[tokio::test]
@@ -400,8 +892,16 @@ mod tests {
 
     // Test invalid config - bad repo format
     let mut invalid_config = create_test_config();
-    invalid_config.github.repositories[0].repo = "invalid-repo".to_string();
+    invalid_config.targets[1].name = "invalid-repo".to_string();
     assert!(provider.validate_config(&invalid_config).await.is_err());
+
+    // Test profiles - targets must reference a configured profile
+    let mut profile_config = create_test_config();
+    profile_config.targets[0].profile = Some("team".to_string());
+    assert!(provider.validate_config(&profile_config).await.is_err());
+    let team = profile_config.credentials.clone();
+    profile_config.profiles.insert("team".to_string(), team);
+    assert!(provider.validate_config(&profile_config).await.is_ok());
   }
 
   #[tokio::test]
@@ -438,13 +938,124 @@ mod tests {
     let config = create_test_config();
     manager.save(&config).await.unwrap();
 
-    // Test backup
     let backup_id = manager.backup().await.unwrap();
     assert!(backup_id.starts_with("backup_"));
+    assert_eq!(manager.backup().await.unwrap(), backup_id);
+
+    // Every save snapshots the configuration it replaces
+    let mut changed = config.clone();
+    changed.targets.clear();
+    manager.save(&changed).await.unwrap();
+    assert!(manager.load().await.unwrap().targets.is_empty());
+
+    manager.restore(&backup_id).await.unwrap();
+    assert_eq!(manager.load().await.unwrap().targets.len(), 2);
+
+    // The restore itself is undoable
+    let backups = manager.backups().list().unwrap();
+    assert_eq!(backups.len(), 2);
+    manager.restore("latest").await.unwrap();
+    assert!(manager.load().await.unwrap().targets.is_empty());
+    assert!(manager.restore("backup_0").await.is_err());
+  }
 
-    // Test restore (currently just logs, doesn't fail)
-    let result = manager.restore(&backup_id).await;
-    // Should succeed for now (returns Ok(()))
-    assert!(result.is_ok());
+  #[tokio::test]
+  async fn test_set_and_unset_values() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.yml");
+    let provider = Box::new(YamlConfigProvider::with_path(config_path.clone()));
+    let manager = ConfigurationManager::with_provider(provider);
+
+    let mut content = serde_yaml::to_string(&create_test_config()).unwrap();
+    content = content.replace("log_level: info", "log_level: info # keep me");
+    std::fs::write(&config_path, format!("# Hand-written\n{}", content)).unwrap();
+
+    assert!(manager.set_value("daemon.log_level", "debug").await.unwrap());
+    assert!(manager.set_value("notifications.session_warnings", "[10]").await.unwrap());
+    manager.update_section("daemon.sync_delay_after_expiry", 120).await.unwrap();
+    let written = std::fs::read_to_string(&config_path).unwrap();
+    assert!(written.starts_with("# Hand-written\n"));
+    assert!(written.contains("log_level: debug # keep me\n"));
+
+    let config = manager.load().await.unwrap();
+    assert_eq!(config.daemon.sync_delay_after_expiry, 120);
+    assert_eq!(config.notifications.session_warnings, vec![10]);
+    assert_eq!(manager.get_value("backups.keep").await.unwrap(), serde_yaml::Value::from(20));
+
+    // Typed, known and valid values only
+    assert!(manager.set_value("daemon.sync_delay_after_expiry", "soon").await.is_err());
+    assert!(manager.set_value("daemon.log_levl", "debug").await.is_err());
+    assert!(manager.set_value("daemon.log_level", "verbose").await.is_err());
+    assert!(manager.set_value("targets.0.kind", "").await.is_err());
+
+    assert!(manager.set_value("providers.aws.region", "eu-west-1").await.unwrap());
+    assert!(manager.unset_value("providers.aws.region").await.unwrap());
+    assert!(manager.unset_value("providers.aws.region").await.is_err());
+    // Missing fields take their defaults
+    assert!(manager.unset_value("daemon.log_level").await.unwrap());
+    assert_eq!(manager.load().await.unwrap().daemon.log_level, "info");
+  }
+
+  #[tokio::test]
+  async fn test_save_in_place_keeps_comments() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.yml");
+    let provider = Box::new(YamlConfigProvider::with_path(config_path.clone()));
+    let manager = ConfigurationManager::with_provider(provider);
+
+    let content = serde_yaml::to_string(&create_test_config()).unwrap();
+    let content = content.replace("log_level: info", "log_level: WARNING # keep me");
+    std::fs::write(&config_path, format!("# Hand-written\n{}", content)).unwrap();
+
+    let mut config = manager.load_file().await.unwrap();
+    config.daemon.log_level = "warn".to_string();
+    config.credentials.field_mappings.remove("refreshToken");
+    assert!(manager.save_in_place(&config).await.unwrap());
+
+    let written = std::fs::read_to_string(&config_path).unwrap();
+    assert!(written.starts_with("# Hand-written\n"));
+    assert!(written.contains("log_level: warn # keep me\n"));
+    let saved = manager.load_file().await.unwrap();
+    assert_eq!(saved.credentials.field_mappings, config.credentials.field_mappings);
+  }
+
+  #[tokio::test]
+  async fn test_reload_keeps_pinned_config_when_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.yml");
+    let provider = Box::new(YamlConfigProvider::with_path(config_path.clone()));
+    let mut manager = ConfigurationManager::with_provider(provider);
+    std::fs::write(&config_path, serde_yaml::to_string(&create_test_config()).unwrap()).unwrap();
+    manager.reload().await.unwrap();
+
+    // Pinned until the next reload
+    let content = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(&config_path, content.replace("log_level: info", "log_level: debug")).unwrap();
+    assert_eq!(manager.load().await.unwrap().daemon.log_level, "info");
+    assert_eq!(manager.reload().await.unwrap().daemon.log_level, "debug");
+
+    std::fs::write(&config_path, content.replace("log_level: info", "log_level: verbose")).unwrap();
+    assert!(manager.reload().await.is_err());
+    assert_eq!(manager.load().await.unwrap().daemon.log_level, "debug");
+
+    manager.invalidate_cache();
+    assert_eq!(manager.load().await.unwrap().daemon.log_level, "verbose");
+  }
+
+  #[tokio::test]
+  async fn test_first_reload_validates_without_writing() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.yml");
+    let provider = Box::new(YamlConfigProvider::with_path(config_path.clone()));
+    let mut manager = ConfigurationManager::with_provider(provider);
+
+    let legacy = "github:\n  organizations:\n  - name: legacy-org\n";
+    std::fs::write(&config_path, legacy).unwrap();
+    assert_eq!(manager.reload().await.unwrap().targets.len(), 1);
+    assert_eq!(std::fs::read_to_string(&config_path).unwrap(), legacy);
+
+    manager.invalidate_cache();
+    std::fs::write(&config_path, "version: 99\n").unwrap();
+    assert!(manager.reload().await.is_err());
   }
 }
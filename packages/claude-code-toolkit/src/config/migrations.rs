@@ -0,0 +1,283 @@
+//! Schema versions of `config.yml` and the migrations between them
+//!
+//! | Version | Shape                                                                       |
+//! |---------|-----------------------------------------------------------------------------|
+//! | 0       | A `secret_name` per GitHub organization and repository, no `field_mappings` |
+//! | 1       | `credentials.field_mappings` and a `github:` section                        |
+//! | 2       | `targets:` list, `providers:` settings and a `version:` key                 |
+//!
+//! Files without a `version:` key start at 0. Every migration leaves shapes it does not
+//! recognise alone, so an unversioned file of any version ends up current. Loading only
+//! upgrades in memory; the file is written by `config migrate` (see [`plan`]) or the next
+//! change, after a backup. Files of a
+//! newer version load with their unknown settings ignored, but are not saved over (see
+//! [`check_version`]).
+
+use crate::config::{ edit, legacy };
+use crate::error::{ ClaudeCodeError, Result };
+use crate::types::Config;
+use serde_yaml::{ Mapping, Value };
+use std::collections::HashMap;
+
+/// Schema version written by this build
+pub const CURRENT_VERSION: u32 = 2;
+
+/// Secret name of the access token before `field_mappings`
+const LEGACY_SECRET_NAME: &str = "CLAUDE_CODE_TOKEN";
+
+/// Upgrade of a raw document from one version to the next
+pub struct Migration {
+  /// Version the migration upgrades from
+  pub from: u32,
+  pub description: &'static str,
+  /// Returns whether the document changed
+  apply: fn(&mut Value) -> Result<bool>,
+}
+
+/// Every migration, in order
+pub const MIGRATIONS: &[Migration] = &[
+  Migration {
+    from: 0,
+    description: "secret_name keys become credentials.field_mappings",
+    apply: migrate_secret_names,
+  },
+  Migration {
+    from: 1,
+    description: "the github section becomes targets",
+    apply: legacy::migrate,
+  },
+];
+
+/// Version of a raw document, 0 when it has none
+pub fn version_of(document: &Value) -> Result<u32> {
+  match document.get("version") {
+    None | Some(Value::Null) => Ok(0),
+    Some(version) => {
+      version
+        .as_u64()
+        .and_then(|v| u32::try_from(v).ok())
+        .ok_or_else(|| {
+          ClaudeCodeError::InvalidConfig(format!("Invalid schema version {:?}", version))
+        })
+    }
+  }
+}
+
+/// Upgrade a raw document to [`CURRENT_VERSION`]
+///
+/// Returns the migrations that changed the document; the version is updated either way.
+/// Documents of a newer version are left unchanged.
+pub fn migrate(document: &mut Value) -> Result<Vec<&'static Migration>> {
+  let version = version_of(document)?;
+  let mut applied = Vec::new();
+  if version >= CURRENT_VERSION || !document.is_mapping() {
+    return Ok(applied);
+  }
+
+  for migration in MIGRATIONS.iter().filter(|m| m.from >= version) {
+    if (migration.apply)(document)? {
+      applied.push(migration);
+    }
+  }
+  if let Some(root) = document.as_mapping_mut() {
+    root.insert(Value::from("version"), Value::from(CURRENT_VERSION));
+  }
+  Ok(applied)
+}
+
+/// Refuse to write a configuration of a newer version than this build knows
+pub fn check_version(version: u32) -> Result<()> {
+  if version > CURRENT_VERSION {
+    return Err(
+      ClaudeCodeError::InvalidConfig(
+        format!(
+          "Configuration schema version {} is newer than this build supports ({}); \
+           upgrade claude-code-toolkit to change it",
+          version,
+          CURRENT_VERSION
+        )
+      )
+    );
+  }
+  Ok(())
+}
+
+/// Upgrade of a configuration file, see [`plan`]
+pub struct MigrationPlan {
+  /// Version of the file
+  pub from: u32,
+  /// Migrations that change the file
+  pub applied: Vec<&'static Migration>,
+  /// Configuration after the upgrade
+  pub config: Config,
+  /// Contents of the file after the upgrade, `None` when it is current already
+  pub migrated: Option<String>,
+}
+
+/// Plan the upgrade of the contents of a configuration file
+///
+/// A file that only lacks its `version:` key keeps its comments and layout.
+pub fn plan(content: &str) -> Result<MigrationPlan> {
+  let mut document: Value = match serde_yaml::from_str(content) {
+    Ok(Value::Null) => Value::Mapping(Mapping::new()),
+    Ok(document) => document,
+    Err(e) => {
+      return Err(ClaudeCodeError::InvalidConfig(e.to_string()));
+    }
+  };
+  let from = version_of(&document)?;
+  check_version(from)?;
+
+  let applied = migrate(&mut document)?;
+  let config: Config = serde_yaml
+    ::from_value(document.clone())
+    .map_err(|e| ClaudeCodeError::InvalidConfig(e.to_string()))?;
+
+  let migrated = if from == CURRENT_VERSION {
+    None
+  } else {
+    let stamped = if applied.is_empty() {
+      edit::edit_text(content, &["version".to_string()], &document)
+    } else {
+      None
+    };
+    match stamped {
+      Some(text) => Some(text),
+      None => {
+        Some(
+          serde_yaml
+            ::to_string(&config)
+            .map_err(|e| ClaudeCodeError::InvalidConfig(e.to_string()))?
+        )
+      }
+    }
+  };
+
+  Ok(MigrationPlan { from, applied, config, migrated })
+}
+
+/// Fold `secret_name` keys of GitHub entries into field mappings of the access token
+///
+/// The most common name becomes the `accessToken` mapping of `credentials`; entries with
+/// another name keep it as a per-target mapping.
+fn migrate_secret_names(document: &mut Value) -> Result<bool> {
+  let Some(root) = document.as_mapping_mut() else {
+    return Ok(false);
+  };
+  let has_mappings = root
+    .get("credentials")
+    .and_then(|c| c.get("field_mappings"))
+    .is_some();
+
+  let mut names: Vec<Option<String>> = Vec::new();
+  if let Some(github) = root.get_mut("github").and_then(Value::as_mapping_mut) {
+    for section in ["organizations", "repositories"] {
+      let Some(items) = github.get_mut(section).and_then(Value::as_sequence_mut) else {
+        continue;
+      };
+      for item in items.iter_mut().filter_map(Value::as_mapping_mut) {
+        names.push(item.remove("secret_name").and_then(|v| v.as_str().map(String::from)));
+      }
+    }
+  }
+  // Without GitHub entries there was nothing the name applied to
+  if names.is_empty() || (has_mappings && names.iter().all(Option::is_none)) {
+    return Ok(false);
+  }
+
+  // Entries without a name used the default
+  let names: Vec<Option<String>> = names
+    .into_iter()
+    .map(|name| name.or_else(|| (!has_mappings).then(|| LEGACY_SECRET_NAME.to_string())))
+    .collect();
+  let mut counts: HashMap<&str, usize> = HashMap::new();
+  for name in names.iter().flatten() {
+    *counts.entry(name).or_default() += 1;
+  }
+  let global = counts
+    .into_iter()
+    .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(a.0)))
+    .map_or(LEGACY_SECRET_NAME.to_string(), |(name, _)| name.to_string());
+
+  if !has_mappings {
+    let credentials = root
+      .entry(Value::from("credentials"))
+      .or_insert_with(|| Value::Mapping(Mapping::new()));
+    if let Some(credentials) = credentials.as_mapping_mut() {
+      let mut mappings = Mapping::new();
+      mappings.insert(Value::from("accessToken"), Value::from(global.as_str()));
+      credentials.insert(Value::from("field_mappings"), Value::Mapping(mappings));
+    }
+  }
+
+  let mut names = names.into_iter();
+  if let Some(github) = root.get_mut("github").and_then(Value::as_mapping_mut) {
+    for section in ["organizations", "repositories"] {
+      let Some(items) = github.get_mut(section).and_then(Value::as_sequence_mut) else {
+        continue;
+      };
+      for item in items.iter_mut().filter_map(Value::as_mapping_mut) {
+        let Some(Some(name)) = names.next() else {
+          continue;
+        };
+        if has_mappings || name != global {
+          let mut mappings = Mapping::new();
+          mappings.insert(Value::from("accessToken"), Value::from(name));
+          item.insert(Value::from("field_mappings"), Value::Mapping(mappings));
+        }
+      }
+    }
+  }
+  Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_unversioned_file_migrates_to_current() {
+    let yaml =
+      r#"
+daemon:
+  log_level: info
+github:
+  organizations:
+    - name: my-org
+      secret_name: CLAUDE_TOKEN
+    - name: other-org
+  repositories:
+    - repo: owner/repo
+      secret_name: CLAUDE_TOKEN
+"#;
+    let mut document: Value = serde_yaml::from_str(yaml).unwrap();
+    assert_eq!(version_of(&document).unwrap(), 0);
+
+    let applied: Vec<u32> = migrate(&mut document)
+      .unwrap()
+      .iter()
+      .map(|m| m.from)
+      .collect();
+    assert_eq!(applied, vec![0, 1]);
+    assert_eq!(version_of(&document).unwrap(), CURRENT_VERSION);
+    assert!(migrate(&mut document).unwrap().is_empty());
+
+    // Missing sections and fields take their defaults
+    let config: Config = serde_yaml::from_value(document).unwrap();
+    assert_eq!(config.version, CURRENT_VERSION);
+    assert_eq!(config.daemon.sync_delay_after_expiry, 60);
+    assert_eq!(config.credentials.json_path, "claudeAiOauth");
+    assert_eq!(config.credentials.field_mappings["accessToken"], "CLAUDE_TOKEN");
+    assert_eq!(config.targets.len(), 3);
+    assert!(config.targets[0].mapping.is_empty());
+    assert_eq!(config.targets[1].mapping.field_mappings["accessToken"], LEGACY_SECRET_NAME);
+
+    let plan = plan("# Sync settings\ntargets: []\n").unwrap();
+    assert!(plan.applied.is_empty());
+    assert_eq!(plan.migrated.unwrap(), "# Sync settings\ntargets: []\nversion: 2\n");
+
+    let mut newer: Value = serde_yaml::from_str("version: 99\nfuture: true\n").unwrap();
+    assert!(migrate(&mut newer).unwrap().is_empty());
+    assert!(check_version(99).is_err());
+  }
+}
@@ -9,6 +9,11 @@
 //! - [`CredentialsManager`] - Secure credential storage and retrieval
 //! - [`ConfigurationManager`] - Main configuration orchestrator
 //! - [`YamlConfigProvider`] - YAML file-based configuration provider
+//! - [`backup::BackupStore`] - Snapshots taken before every configuration change
+//! - [`bundle`] - Portable bundles of a configuration, for sharing it with teammates
+//! - [`edit`] - Path-based edits of `config.yml` that keep its comments
+//! - [`layers`] - Defaults, system, user and project files and `CCT_*` variables, merged
+//! - [`migrations`] - Upgrades of older `config.yml` schema versions
 //!
 //! ## Usage Examples
 //!
@@ -25,7 +30,7 @@
 //!     
 //!     // Load configuration
 //!     let config = config_manager.load().await?;
-//!     println!("Loaded configuration with {} repositories", config.github.repositories.len());
+//!     println!("Loaded configuration with {} targets", config.targets.len());
 //!     
 //!     Ok(())
 //! }
@@ -58,14 +63,21 @@
 //! ## Configuration Structure
 //!
 //! The configuration system supports:
-//! - GitHub organization and repository settings
+//! - Sync targets for any registered provider (GitHub, Bitbucket, AWS, ...)
 //! - Sync intervals and retry policies
 //! - Notification preferences
 //! - Daemon service configuration
 //! - Custom provider settings
 
+pub mod backup;
+pub mod bundle;
 pub mod credentials;
+pub mod edit;
+pub mod layers;
+pub mod legacy;
 pub mod manager;
+pub mod migrations;
+pub mod seed;
 
 pub use credentials::CredentialsManager;
 pub use manager::{ ConfigurationManager, YamlConfigProvider };
@@ -0,0 +1,291 @@
+//! Seed settings for non-interactive `configure`
+//!
+//! A seed describes the desired organizations, repositories, field mappings and
+//! daemon options. It is read from a YAML file or an env file (`KEY=VALUE` lines whose
+//! keys are the YAML keys in upper case) and merged with command-line flags. Applying
+//! a seed only adds or updates what differs, so rerunning `configure` converges.
+//!
+//! ```yaml
+//! organizations: [my-org]
+//! repositories: [owner/repo]
+//! field_mappings:
+//!   accessToken: CLAUDE_ACCESS_TOKEN
+//! credentials_file: ~/.claude/.credentials.json
+//! log_level: info
+//! sync_delay_after_expiry: 60
+//! install_daemon: true
+//! ```
+//!
+//! The same seed as an env file:
+//!
+//! ```text
+//! ORGANIZATIONS=my-org
+//! REPOSITORIES=owner/repo
+//! FIELD_MAPPINGS=accessToken=CLAUDE_ACCESS_TOKEN
+//! INSTALL_DAEMON=true
+//! ```
+
+use crate::error::{ ClaudeCodeError, Result };
+use crate::types::{ Config, TargetConfig };
+use crate::validation::LOG_LEVELS;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigSeed {
+  #[serde(default)]
+  pub organizations: Vec<String>,
+
+  /// Repositories in "owner/repository" format
+  #[serde(default)]
+  pub repositories: Vec<String>,
+
+  /// credential_field -> secret_name entries added to `credentials.field_mappings`
+  #[serde(default)]
+  pub field_mappings: HashMap<String, String>,
+
+  #[serde(default)]
+  pub credentials_file: Option<String>,
+
+  #[serde(default)]
+  pub log_level: Option<String>,
+
+  #[serde(default)]
+  pub sync_delay_after_expiry: Option<u64>,
+
+  /// Install and start the sync daemon after configuring
+  #[serde(default)]
+  pub install_daemon: Option<bool>,
+}
+
+impl ConfigSeed {
+  /// Read a seed from a YAML file, or an env file when the name ends in `.env`
+  pub fn from_file(path: &Path) -> Result<Self> {
+    let content = std::fs
+      ::read_to_string(path)
+      .map_err(|e| ClaudeCodeError::InvalidConfig(format!("{}: {}", path.display(), e)))?;
+
+    let is_env = path.extension().is_some_and(|ext| ext == "env") ||
+      path.file_name().is_some_and(|name| name == ".env");
+    let seed = if is_env {
+      Self::from_env(&content)
+    } else {
+      serde_yaml::from_str(&content).map_err(|e| ClaudeCodeError::InvalidConfig(e.to_string()))
+    };
+
+    seed.map_err(|e| ClaudeCodeError::InvalidConfig(format!("{}: {}", path.display(), e)))
+  }
+
+  /// Parse `KEY=VALUE` lines; lists are comma-separated
+  pub fn from_env(content: &str) -> Result<Self> {
+    let mut seed = Self::default();
+
+    for line in content.lines() {
+      let line = line.trim();
+      if line.is_empty() || line.starts_with('#') {
+        continue;
+      }
+
+      let line = line.strip_prefix("export ").unwrap_or(line);
+      let (key, value) = line
+        .split_once('=')
+        .ok_or_else(|| ClaudeCodeError::InvalidConfig(format!("Expected KEY=VALUE: {}", line)))?;
+      let value = value.trim().trim_matches('"').trim_matches('\'');
+      let list = || {
+        value
+          .split(',')
+          .map(str::trim)
+          .filter(|s| !s.is_empty())
+          .map(String::from)
+          .collect::<Vec<_>>()
+      };
+
+      match key.trim() {
+        "ORGANIZATIONS" => seed.organizations.extend(list()),
+        "REPOSITORIES" => seed.repositories.extend(list()),
+        "FIELD_MAPPINGS" => {
+          for mapping in list() {
+            let (field, secret) = parse_mapping(&mapping)?;
+            seed.field_mappings.insert(field, secret);
+          }
+        }
+        "CREDENTIALS_FILE" => {
+          seed.credentials_file = Some(value.to_string());
+        }
+        "LOG_LEVEL" => {
+          seed.log_level = Some(value.to_string());
+        }
+        "SYNC_DELAY_AFTER_EXPIRY" => {
+          let delay = value
+            .parse()
+            .map_err(|_| {
+              ClaudeCodeError::InvalidConfig(
+                format!("SYNC_DELAY_AFTER_EXPIRY: invalid number {}", value)
+              )
+            })?;
+          seed.sync_delay_after_expiry = Some(delay);
+        }
+        "INSTALL_DAEMON" => {
+          seed.install_daemon = Some(matches!(value.to_lowercase().as_str(), "1" | "true" | "yes"));
+        }
+        other => {
+          return Err(ClaudeCodeError::InvalidConfig(format!("Unknown seed key: {}", other)));
+        }
+      }
+    }
+
+    Ok(seed)
+  }
+
+  /// Layer `other` over this seed: lists are extended, single values replaced
+  pub fn merge(&mut self, other: ConfigSeed) {
+    self.organizations.extend(other.organizations);
+    self.repositories.extend(other.repositories);
+    self.field_mappings.extend(other.field_mappings);
+    if other.credentials_file.is_some() {
+      self.credentials_file = other.credentials_file;
+    }
+    if other.log_level.is_some() {
+      self.log_level = other.log_level;
+    }
+    if other.sync_delay_after_expiry.is_some() {
+      self.sync_delay_after_expiry = other.sync_delay_after_expiry;
+    }
+    if other.install_daemon.is_some() {
+      self.install_daemon = other.install_daemon;
+    }
+  }
+
+  /// Check the seed without touching the configuration
+  pub fn validate(&self) -> Result<()> {
+    if let Some(org) = self.organizations.iter().find(|o| o.is_empty() || o.contains('/')) {
+      return Err(ClaudeCodeError::Validation(format!("Invalid organization name: '{}'", org)));
+    }
+
+    if let Some(repo) = self.repositories.iter().find(|r| !is_repository(r)) {
+      return Err(ClaudeCodeError::InvalidRepoFormat { repo: repo.clone() });
+    }
+
+    if
+      let Some((field, _)) = self.field_mappings
+        .iter()
+        .find(|(field, secret)| field.is_empty() || secret.is_empty())
+    {
+      return Err(
+        ClaudeCodeError::Validation(format!("Field mapping '{}' needs a secret name", field))
+      );
+    }
+
+    if let Some(level) = &self.log_level {
+      if !LOG_LEVELS.contains(&level.as_str()) {
+        return Err(
+          ClaudeCodeError::Validation(
+            format!("Invalid log level '{}', expected one of {}", level, LOG_LEVELS.join(", "))
+          )
+        );
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Bring the configuration in line with the seed, returning what changed
+  pub fn apply(&self, config: &mut Config) -> Vec<String> {
+    let mut changes = Vec::new();
+
+    let targets = self.organizations
+      .iter()
+      .map(|org| TargetConfig::new("github", "organization", org))
+      .chain(
+        self.repositories.iter().map(|repo| TargetConfig::new("github", "repository", repo))
+      );
+    for target in targets {
+      if !config.targets.iter().any(|t| t.matches(&target.provider, &target.kind, &target.name)) {
+        changes.push(format!("Added {}", target));
+        config.targets.push(target);
+      }
+    }
+
+    let mut mappings: Vec<_> = self.field_mappings.iter().collect();
+    mappings.sort();
+    for (field, secret) in mappings {
+      if config.credentials.field_mappings.get(field) != Some(secret) {
+        changes.push(format!("Mapped {} → {}", field, secret));
+        config.credentials.field_mappings.insert(field.clone(), secret.clone());
+      }
+    }
+
+    if let Some(file_path) = self.credentials_file.as_ref() {
+      if &config.credentials.file_path != file_path {
+        changes.push(format!("Set credentials.file_path to {}", file_path));
+        config.credentials.file_path = file_path.clone();
+      }
+    }
+
+    if let Some(log_level) = self.log_level.as_ref() {
+      if &config.daemon.log_level != log_level {
+        changes.push(format!("Set daemon.log_level to {}", log_level));
+        config.daemon.log_level = log_level.clone();
+      }
+    }
+
+    if let Some(delay) = self.sync_delay_after_expiry {
+      if config.daemon.sync_delay_after_expiry != delay {
+        changes.push(format!("Set daemon.sync_delay_after_expiry to {}", delay));
+        config.daemon.sync_delay_after_expiry = delay;
+      }
+    }
+
+    changes
+  }
+}
+
+/// Parse a `field=SECRET_NAME` mapping
+pub fn parse_mapping(mapping: &str) -> Result<(String, String)> {
+  mapping
+    .split_once('=')
+    .map(|(field, secret)| (field.trim().to_string(), secret.trim().to_string()))
+    .ok_or_else(|| {
+      ClaudeCodeError::Validation(format!("Invalid mapping '{}', expected FIELD=SECRET", mapping))
+    })
+}
+
+fn is_repository(repo: &str) -> bool {
+  match repo.split_once('/') {
+    Some((owner, name)) => !owner.is_empty() && !name.is_empty() && !name.contains('/'),
+    None => false,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_env_seed_applies_idempotently() {
+    let seed = ConfigSeed::from_env(
+      "# provisioning\nexport ORGANIZATIONS=org-a, org-b\nREPOSITORIES=\"owner/repo\"\n\
+       FIELD_MAPPINGS=accessToken=CLAUDE_ACCESS_TOKEN\nLOG_LEVEL=debug\n"
+    ).unwrap();
+    seed.validate().unwrap();
+
+    let mut config = Config::default();
+    config.targets.push(TargetConfig::new("github", "organization", "org-a"));
+
+    let changes = seed.apply(&mut config);
+    assert_eq!(changes, vec![
+      "Added github:organization:org-b",
+      "Added github:repository:owner/repo",
+      "Mapped accessToken → CLAUDE_ACCESS_TOKEN",
+      "Set daemon.log_level to debug"
+    ]);
+    assert_eq!(config.targets.len(), 3);
+    assert!(seed.apply(&mut config).is_empty());
+
+    assert!(ConfigSeed::from_env("ORGS=x").is_err());
+    let bad_repo = ConfigSeed { repositories: vec!["no-owner".to_string()], ..Default::default() };
+    assert!(bad_repo.validate().is_err());
+  }
+}
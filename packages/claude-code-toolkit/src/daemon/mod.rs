@@ -12,15 +12,17 @@
 //! - **Session Warnings**: Desktop notifications before session expiry
 //! - **Error Recovery**: Robust error handling with failure notifications
 //! - **Signal Handling**: Graceful shutdown on SIGINT/SIGTERM
+//! - **Hot Reload**: Applies configuration changes without a restart
 //! - **Startup Recovery**: Reconciliation check on daemon startup
 //!
 //! ## Daemon Lifecycle
 //!
-//! 1. **Startup**: Perform initial sync check and reconciliation
+//! 1. **Startup**: Validate the configuration, perform initial sync check and reconciliation
 //! 2. **Monitoring Loop**: Check credentials every 5 minutes, session warnings every minute
 //! 3. **Token Expiry**: Wait for refresh, then sync to all targets
 //! 4. **Notifications**: Send warnings before expiry, errors on sync failures
-//! 5. **Shutdown**: Graceful cleanup on shutdown signals
+//! 5. **Reload**: Validate and apply the configuration when `config.yml` changes or on SIGHUP
+//! 6. **Shutdown**: Graceful cleanup on shutdown signals
 //!
 //! ## Usage Examples
 //!
@@ -59,7 +61,7 @@
 //!
 //! ## Configuration
 //!
-//! The daemon reads configuration from `~/.goodiebag/claude-code/config.yml`:
+//! The daemon reads configuration from `~/.config/claude-code-toolkit/config.yml`:
 //!
 //! ```yaml
 //! daemon:
@@ -71,6 +73,13 @@
 //!   sync_failures: true
 //! ```
 //!
+//! The daemon works from a validated snapshot of the configuration. When `config.yml`
+//! changes, or on SIGHUP (which also picks up changes to the other configuration layers),
+//! it loads and validates the configuration again and swaps in the new targets, mappings,
+//! notification schedule and log level between two checks. A rejected configuration is
+//! logged and reported with a desktop notification, and the daemon keeps running with the
+//! previous one.
+//!
 //! ## Systemd Integration
 //!
 //! The daemon is designed to run as a systemd user service:
@@ -108,13 +117,34 @@ use crate::{
   config::{ credentials::CredentialsManager, manager::ConfigurationManager },
   error::*,
   sync::SyncService,
-  traits::config::ConfigManager,
-  utils::notifications,
+  types::{ Config, SessionInfo },
+  utils::{ logging, notifications, paths },
 };
+use notify::{ EventKind, RecommendedWatcher, RecursiveMode, Watcher };
 use std::time::Duration;
 use tokio::signal;
+use tokio::sync::mpsc::{ UnboundedReceiver, UnboundedSender, unbounded_channel };
 use tokio::time::{ interval, sleep };
-use tracing::{ error, info, warn };
+use tracing::{ debug, error, info, warn };
+
+/// Time to let an editor finish writing before the configuration is reloaded
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Why the configuration is reloaded
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ReloadTrigger {
+  FileChanged,
+  Hangup,
+}
+
+impl std::fmt::Display for ReloadTrigger {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ReloadTrigger::FileChanged => write!(f, "config.yml changed"),
+      ReloadTrigger::Hangup => write!(f, "SIGHUP"),
+    }
+  }
+}
 
 /// Main daemon service for background credential synchronization.
 ///
@@ -128,7 +158,7 @@ use tracing::{ error, info, warn };
 /// The daemon maintains:
 /// - [`SyncService`] - Handles the actual credential synchronization logic
 /// - [`ConfigurationManager`] - Manages YAML configuration and targets
-/// - [`CredentialsManager`] - Monitors Claude Code credential files
+/// - [`CredentialsManager`] - Monitors the credential file of every profile
 /// - Shutdown coordination - Graceful termination handling
 ///
 /// ## Monitoring Schedule
@@ -137,10 +167,10 @@ use tracing::{ error, info, warn };
 /// - **Session warnings**: Every 1 minute (60 seconds)  
 /// - **Post-expiry sync**: 30 seconds after detection
 /// - **Startup reconciliation**: Immediate on daemon start
+/// - **Configuration reload**: Half a second after `config.yml` changes, and on SIGHUP
 pub struct Daemon {
   sync_service: SyncService,
   config_manager: ConfigurationManager,
-  credentials_manager: CredentialsManager,
   shutdown_tx: Option<tokio::sync::oneshot::Sender<()>>,
 }
 
@@ -149,32 +179,95 @@ impl Daemon {
     Ok(Self {
       sync_service: SyncService::new()?,
       config_manager: ConfigurationManager::new()?,
-      credentials_manager: CredentialsManager::new()?,
       shutdown_tx: None,
     })
   }
 
   pub async fn new_with_config() -> Result<Self> {
-    let config_manager = ConfigurationManager::new()?;
-    let config = config_manager.load().await?;
-
-    // Expand tilde in path
-    let expanded_path = shellexpand::tilde(&config.credentials.file_path);
-    let credentials_path = std::path::PathBuf::from(expanded_path.as_ref());
-
     Ok(Self {
       sync_service: SyncService::new_with_config().await?,
-      config_manager,
-      credentials_manager: CredentialsManager::with_path(credentials_path),
+      config_manager: ConfigurationManager::new()?,
       shutdown_tx: None,
     })
   }
 
+  /// Session info of every credential profile, skipping profiles that cannot be read
+  async fn profile_sessions(&self) -> Result<Vec<(String, SessionInfo)>> {
+    let config = self.config_manager.load_config().await?;
+
+    let mut sessions = Vec::new();
+    for profile in config.profile_names() {
+      let Some(profile_config) = config.profile(profile) else {
+        continue;
+      };
+      match CredentialsManager::from_config(profile_config).get_session_info().await {
+        Ok(session_info) => sessions.push((profile.to_string(), session_info)),
+        Err(e) => warn!("Could not read session for profile {}: {}", profile, e),
+      }
+    }
+
+    Ok(sessions)
+  }
+
+  /// Use a validated configuration from now on
+  fn apply_config(&mut self, config: Config) {
+    if let Err(e) = logging::set_level(&config.daemon.log_level) {
+      warn!("{}", e);
+    }
+    self.sync_service.pin_config(config.clone());
+    self.config_manager.pin(config);
+  }
+
+  /// Reload the configuration, keeping the running one if the new one is rejected
+  async fn reload_config(&mut self, trigger: ReloadTrigger) {
+    let previous = self.config_manager.load_config().await.ok();
+    // Loading would write a default configuration in place of a deleted one
+    let result = if self.config_manager.config_path().exists() {
+      self.config_manager.reload().await
+    } else {
+      Err(
+        ClaudeCodeError::InvalidConfig(
+          format!("{:?} does not exist", self.config_manager.config_path())
+        )
+      )
+    };
+    match result {
+      Ok(config) => {
+        let unchanged = previous.is_some_and(|previous| {
+          serde_json::to_value(&previous).ok() == serde_json::to_value(&config).ok()
+        });
+        if unchanged {
+          debug!("Configuration unchanged ({})", trigger);
+          return;
+        }
+        info!("Reloaded configuration ({}): {} targets", trigger, config.targets.len());
+        self.apply_config(config);
+      }
+      Err(e) => {
+        error!("Rejected configuration ({}), keeping the running one: {}", trigger, e);
+        if let Err(notify_err) = notifications::send_config_rejected(&e.to_string()) {
+          warn!("Failed to send configuration notification: {}", notify_err);
+        }
+      }
+    }
+  }
+
   pub async fn start(&mut self) -> Result<()> {
     info!("Claude Code daemon starting");
 
-    // Load config
-    let _config = self.config_manager.load_config().await?;
+    // Work from a snapshot of the configuration, validated as reloads are and replaced by them
+    let config = self.config_manager.reload().await?;
+    self.apply_config(config);
+
+    let (reload_tx, mut reload_rx) = unbounded_channel();
+    let _watcher = match watch_config(reload_tx.clone()) {
+      Ok(watcher) => Some(watcher),
+      Err(e) => {
+        warn!("Not watching the configuration file, reload with SIGHUP instead: {}", e);
+        None
+      }
+    };
+    listen_for_hangup(reload_tx);
 
     // Check and sync immediately on startup
     if let Err(e) = self.sync_service.check_and_sync_if_needed().await {
@@ -213,6 +306,12 @@ impl Daemon {
                     }
                 }
 
+                // Configuration changed or SIGHUP
+                Some(trigger) = reload_rx.recv() => {
+                    let trigger = debounce(trigger, &mut reload_rx).await;
+                    self.reload_config(trigger).await;
+                }
+
                 // Handle SIGINT and SIGTERM
                 _ = signal::ctrl_c() => {
                     info!("Received Ctrl+C, shutting down");
@@ -233,10 +332,16 @@ impl Daemon {
   }
 
   async fn check_token_expiry(&mut self) -> Result<()> {
-    let session_info = self.credentials_manager.get_session_info().await?;
+    let sessions = self.profile_sessions().await?;
+
+    let expired: Vec<&str> = sessions
+      .iter()
+      .filter(|(_, session_info)| session_info.is_expired)
+      .map(|(profile, _)| profile.as_str())
+      .collect();
 
-    if session_info.is_expired {
-      info!("Token has expired, checking for refresh");
+    if !expired.is_empty() {
+      info!("Token has expired for profile(s) {}, checking for refresh", expired.join(", "));
 
       // Wait a bit for Claude Code to potentially refresh the token
       sleep(Duration::from_secs(30)).await;
@@ -252,16 +357,18 @@ impl Daemon {
       } else {
         info!("Successfully synced after token expiry");
       }
-    } else {
-      // Schedule next check around expiry time
-      let time_until_expiry = Duration::from_millis(session_info.time_remaining as u64);
-      let config = self.config_manager.load_config().await?;
-      let _sync_delay = Duration::from_secs(config.daemon.sync_delay_after_expiry);
+    }
 
+    // Schedule next check around expiry time
+    let config = self.config_manager.load_config().await?;
+    let _sync_delay = Duration::from_secs(config.daemon.sync_delay_after_expiry);
+    for (profile, session_info) in sessions.iter().filter(|(_, s)| !s.is_expired) {
+      let time_until_expiry = Duration::from_millis(session_info.time_remaining as u64);
       if time_until_expiry < Duration::from_secs(600) {
         // Less than 10 minutes
         info!(
-          "Token expires soon ({}), will check again after expiry",
+          "Token of profile {} expires soon ({}), will check again after expiry",
+          profile,
           CredentialsManager::format_time_remaining(session_info.time_remaining)
         );
       }
@@ -271,31 +378,36 @@ impl Daemon {
   }
 
   async fn check_session_warnings(&self) -> Result<()> {
-    let session_info = self.credentials_manager.get_session_info().await?;
+    let config = self.config_manager.load_config().await?;
 
-    if session_info.is_expired {
-      return Ok(());
-    }
+    for (profile, session_info) in self.profile_sessions().await? {
+      if session_info.is_expired {
+        continue;
+      }
 
-    let config = self.config_manager.load_config().await?;
-    let time_remaining_minutes = session_info.time_remaining / 1000 / 60;
+      let time_remaining_minutes = session_info.time_remaining / 1000 / 60;
 
-    // Check if we should send a warning
-    for &warning_minutes in &config.notifications.session_warnings {
-      let warning_minutes = warning_minutes as i64;
+      // Check if we should send a warning
+      for &warning_minutes in &config.notifications.session_warnings {
+        let warning_minutes = warning_minutes as i64;
 
-      // Send warning if we're within the warning window (with 1-minute tolerance)
-      if
-        time_remaining_minutes <= warning_minutes &&
-        time_remaining_minutes >= warning_minutes - 1
-      {
-        info!("Sending session warning: {} minutes remaining", warning_minutes);
+        // Send warning if we're within the warning window (with 1-minute tolerance)
+        if
+          time_remaining_minutes <= warning_minutes &&
+          time_remaining_minutes >= warning_minutes - 1
+        {
+          info!(
+            "Sending session warning for profile {}: {} minutes remaining",
+            profile,
+            warning_minutes
+          );
 
-        if let Err(e) = notifications::send_session_warning(warning_minutes as u64) {
-          warn!("Failed to send session warning: {}", e);
-        }
+          if let Err(e) = notifications::send_session_warning(&profile, warning_minutes as u64) {
+            warn!("Failed to send session warning: {}", e);
+          }
 
-        break; // Only send one warning per check
+          break; // Only send one warning per check
+        }
       }
     }
 
@@ -314,3 +426,69 @@ impl Daemon {
     Ok(())
   }
 }
+
+/// Send a trigger whenever the configuration file is written, created or replaced
+///
+/// The directory is watched, so that editors that save by renaming are noticed too.
+fn watch_config(tx: UnboundedSender<ReloadTrigger>) -> Result<RecommendedWatcher> {
+  let config_file = paths::get().config_file().to_path_buf();
+  let config_dir = paths::get().config_dir().to_path_buf();
+  std::fs::create_dir_all(&config_dir)?;
+
+  let mut watcher = notify
+    ::recommended_watcher(move |event: notify::Result<notify::Event>| {
+      let Ok(event) = event else {
+        return;
+      };
+      let relevant = matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+      );
+      if relevant && event.paths.contains(&config_file) {
+        let _ = tx.send(ReloadTrigger::FileChanged);
+      }
+    })
+    .map_err(|e| ClaudeCodeError::Generic(format!("Could not watch {:?}: {}", config_dir, e)))?;
+  watcher
+    .watch(&config_dir, RecursiveMode::NonRecursive)
+    .map_err(|e| ClaudeCodeError::Generic(format!("Could not watch {:?}: {}", config_dir, e)))?;
+
+  info!("Watching {:?} for changes", paths::get().config_file());
+  Ok(watcher)
+}
+
+/// Send a trigger on every SIGHUP
+#[cfg(unix)]
+fn listen_for_hangup(tx: UnboundedSender<ReloadTrigger>) {
+  use tokio::signal::unix::{ SignalKind, signal };
+
+  match signal(SignalKind::hangup()) {
+    Ok(mut hangups) => {
+      tokio::spawn(async move {
+        while hangups.recv().await.is_some() {
+          if tx.send(ReloadTrigger::Hangup).is_err() {
+            break;
+          }
+        }
+      });
+    }
+    Err(e) => warn!("Could not listen for SIGHUP: {}", e),
+  }
+}
+
+#[cfg(not(unix))]
+fn listen_for_hangup(_tx: UnboundedSender<ReloadTrigger>) {}
+
+/// Wait for a burst of triggers to end, preferring SIGHUP as the reported reason
+async fn debounce(
+  mut trigger: ReloadTrigger,
+  rx: &mut UnboundedReceiver<ReloadTrigger>
+) -> ReloadTrigger {
+  sleep(RELOAD_DEBOUNCE).await;
+  while let Ok(next) = rx.try_recv() {
+    if next == ReloadTrigger::Hangup {
+      trigger = next;
+    }
+  }
+  trigger
+}
@@ -67,6 +67,8 @@
 //! - [`daemon`] - Background service functionality
 //! - [`providers`] - Integration with external services (GitHub, etc.)
 //! - [`sync`] - Credential synchronization logic
+//! - [`setup`] - The `configure` wizard and its steps
+//! - [`validation`] - Configuration validation rules behind `validate`
 //! - [`cli`] - Command-line interface components
 //! - [`utils`] - Utility functions and helpers
 //!
@@ -91,6 +93,7 @@
 //!
 //! - `systemd` (default): Enables systemd service integration on Linux
 //! - `notifications` (default): Enables desktop notification support
+//! - `keyring` (default): Enables the Freedesktop Secret Service (login keyring) provider
 //!
 //! ## Platform Support
 //!
@@ -124,10 +127,12 @@ pub mod config;
 pub mod daemon;
 pub mod error;
 pub mod providers;
+pub mod setup;
 pub mod sync;
 pub mod traits;
 pub mod types;
 pub mod utils;
+pub mod validation;
 
 pub use error::{ ClaudeCodeError, Result };
 pub use traits::*;
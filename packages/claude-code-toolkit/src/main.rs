@@ -1,27 +1,50 @@
 use clap::Parser;
 use claude_code_toolkit::{
-  cli::{ Cli, Commands, OrgCommands, RepoCommands, ServiceCommands, SyncCommands, commands },
+  cli::{
+    Cli,
+    Commands,
+    ConfigCommands,
+    OrgCommands,
+    RepoCommands,
+    ServiceCommands,
+    SyncCommands,
+    TargetCommands,
+    commands,
+    output::ExitStatus,
+  },
   daemon::Daemon,
   error::Result,
+  utils::{ logging, paths::{ self, Paths }, systemd::SystemdManager },
 };
 use console::style;
-use tracing_subscriber::EnvFilter;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-  // Initialize logging
-  let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_|
-    EnvFilter::new("claude_code_toolkit=info")
-  );
-
-  tracing_subscriber::fmt().with_env_filter(filter).with_target(false).init();
+  logging::init();
 
   let cli = Cli::parse();
+  let output = cli.output;
+
+  paths::init(Paths::resolve(cli.config, cli.state_dir));
+  if let Err(e) = paths::get().migrate_legacy() {
+    eprintln!("{}", style(format!("Could not move files from ~/.goodiebag: {}", e)).yellow());
+  }
+  if let Ok(systemd_manager) = SystemdManager::new() {
+    if systemd_manager.uses_legacy_paths().await {
+      eprintln!(
+        "{}",
+        style(
+          "The installed daemon service still uses ~/.goodiebag and cannot write the new \
+           file locations; run `claude-code-toolkit service install` to update it"
+        ).yellow()
+      );
+    }
+  }
 
   // Handle commands
   let result = match cli.command {
-    Commands::Status => commands::status::handle_status().await,
-    Commands::Timer => commands::timer::handle_timer().await,
+    Commands::Status => commands::status::handle_status(output).await,
+    Commands::Timer { profile } => commands::timer::handle_timer(profile).await,
     Commands::Daemon => {
       let mut daemon = Daemon::new_with_config().await?;
       daemon.start().await
@@ -29,22 +52,80 @@ async fn main() -> Result<()> {
     Commands::Org(org_cmd) =>
       match org_cmd {
         OrgCommands::Add { name } => commands::org::handle_add_org(name).await,
-        OrgCommands::Remove { name } => commands::org::handle_remove_org(name).await,
-        OrgCommands::List => commands::org::handle_list_orgs().await,
+        OrgCommands::Remove { name, purge } => commands::org::handle_remove_org(name, purge).await,
+        OrgCommands::List => commands::org::handle_list_orgs(output).await,
       }
     Commands::Repo(repo_cmd) =>
       match repo_cmd {
         RepoCommands::Add { repo } => commands::repo::handle_add_repo(repo).await,
-        RepoCommands::Remove { repo } => commands::repo::handle_remove_repo(repo).await,
-        RepoCommands::List => commands::repo::handle_list_repos().await,
+        RepoCommands::Remove { repo, purge } => {
+          commands::repo::handle_remove_repo(repo, purge).await
+        }
+        RepoCommands::List => commands::repo::handle_list_repos(output).await,
+      }
+    Commands::Target(target_cmd) =>
+      match *target_cmd {
+        TargetCommands::Add {
+          provider,
+          kind,
+          name,
+          options,
+          profile,
+          field_mappings,
+          templates,
+          include,
+          exclude,
+          prefix,
+          suffix,
+        } => {
+          let mapping = commands::target::MappingArgs {
+            field_mappings,
+            templates,
+            include,
+            exclude,
+            prefix,
+            suffix,
+          };
+          let target = commands::target::TargetArgs { provider, kind, name, options, profile };
+          commands::target::handle_add_target(target, mapping).await
+        }
+        TargetCommands::Remove { provider, kind, name, purge } => {
+          commands::target::handle_remove_target(provider, kind, name, purge).await
+        }
+        TargetCommands::List { provider } => {
+          commands::target::handle_list_targets(provider, output).await
+        }
       }
     Commands::Sync { command } =>
       match command {
-        Some(SyncCommands::Force) => commands::sync::handle_sync_force().await,
-        Some(SyncCommands::Status) => commands::sync::handle_sync_status().await,
+        Some(SyncCommands::Force) => commands::sync::handle_sync_force(output).await,
+        Some(SyncCommands::Status) => commands::sync::handle_sync_status(output).await,
         Some(SyncCommands::Logs { lines }) => commands::sync::handle_sync_logs(lines).await,
-        None => commands::sync::handle_sync_now().await, // Default smart sync
+        Some(SyncCommands::Prune { dry_run }) => {
+          commands::sync::handle_sync_prune(dry_run, output).await
+        }
+        None => commands::sync::handle_sync_now(output).await, // Default smart sync
+      }
+    Commands::Revoke { tombstone, yes, report } => {
+      commands::revoke::handle_revoke(tombstone, yes, report, output).await
+    }
+    Commands::Rearm => commands::revoke::handle_rearm().await,
+    Commands::Config(config_cmd) =>
+      match config_cmd {
+        ConfigCommands::Show { origin } => commands::config::handle_show(origin, output).await,
+        ConfigCommands::Get { path } => commands::config::handle_get(path, output).await,
+        ConfigCommands::Set { path, value } => commands::config::handle_set(path, value).await,
+        ConfigCommands::Unset { path } => commands::config::handle_unset(path).await,
+        ConfigCommands::Backups => commands::config::handle_backups(output).await,
+        ConfigCommands::Restore { id, state } => commands::config::handle_restore(id, state).await,
+        ConfigCommands::Diff { id } => commands::config::handle_diff(id).await,
+        ConfigCommands::Migrate { dry_run } => commands::config::handle_migrate(dry_run).await,
+        ConfigCommands::Export { file } => commands::config::handle_export(file).await,
+        ConfigCommands::Import { source, overwrite, dry_run } => {
+          commands::config::handle_import(source, overwrite, dry_run, output).await
+        }
       }
+    Commands::Validate { fix } => commands::validate::handle_validate(fix, output).await,
     Commands::Service(service_cmd) =>
       match service_cmd {
         ServiceCommands::Install => commands::service::handle_install().await,
@@ -57,13 +138,41 @@ async fn main() -> Result<()> {
         ServiceCommands::Enable => commands::service::handle_enable().await,
         ServiceCommands::Disable => commands::service::handle_disable().await,
       }
-    Commands::Configure => commands::configure::handle_configure().await,
+    Commands::Configure {
+      non_interactive,
+      steps,
+      restart,
+      seed,
+      orgs,
+      repos,
+      field_mappings,
+      credentials_file,
+      log_level,
+      sync_delay,
+      install_daemon,
+    } => {
+      if non_interactive {
+        let args = commands::configure::ConfigureArgs {
+          seed,
+          orgs,
+          repos,
+          field_mappings,
+          credentials_file,
+          log_level,
+          sync_delay,
+          install_daemon,
+        };
+        commands::configure::handle_configure_non_interactive(args).await
+      } else {
+        commands::configure::handle_configure(steps, restart).await
+      }
+    }
   };
 
   // Handle errors gracefully
   if let Err(e) = result {
     eprintln!("{}: {}", style("Error").red().bold(), e);
-    std::process::exit(1);
+    std::process::exit(ExitStatus::from_error(&e).code());
   }
 
   Ok(())
@@ -0,0 +1,943 @@
+//! AWS Secrets Manager / SSM Parameter Store provider implementation
+//!
+//! Requests are signed with AWS Signature Version 4 and sent with `reqwest`, so no
+//! AWS SDK or CLI is required. Credentials are resolved from the standard chain:
+//! `AWS_ACCESS_KEY_ID`/`AWS_SECRET_ACCESS_KEY`/`AWS_SESSION_TOKEN`, then the shared
+//! credentials file for the selected profile.
+
+use super::{ BaseProvider, ProviderCreator, SecretNameRules };
+use crate::error::{ ClaudeCodeError, Result };
+use crate::traits::{ Secret, SecretProvider, SyncResult, Target };
+use async_trait::async_trait;
+use hmac::{ Hmac, Mac };
+use sha2::{ Digest, Sha256 };
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tracing::{ debug, error, info, warn };
+
+const SECRETS_MANAGER: &str = "secretsmanager";
+const SSM: &str = "ssm";
+
+/// Static AWS credentials used for request signing
+#[derive(Debug, Clone)]
+pub struct AwsCredentials {
+  pub access_key_id: String,
+  pub secret_access_key: String,
+  pub session_token: Option<String>,
+}
+
+impl AwsCredentials {
+  /// Resolve credentials from the environment, then the shared credentials file
+  pub fn resolve(profile: Option<&str>) -> Result<Self> {
+    if
+      let (Ok(access_key_id), Ok(secret_access_key)) = (
+        std::env::var("AWS_ACCESS_KEY_ID"),
+        std::env::var("AWS_SECRET_ACCESS_KEY"),
+      )
+    {
+      debug!("Using AWS credentials from environment");
+      return Ok(Self {
+        access_key_id,
+        secret_access_key,
+        session_token: std::env::var("AWS_SESSION_TOKEN").ok(),
+      });
+    }
+
+    let profile = resolve_profile(profile);
+    let path = shared_file("AWS_SHARED_CREDENTIALS_FILE", "credentials")?;
+    let content = std::fs
+      ::read_to_string(&path)
+      .map_err(|e| {
+        ClaudeCodeError::Provider(
+          format!("No AWS credentials in environment and could not read {}: {}", path.display(), e)
+        )
+      })?;
+
+    let section = parse_ini(&content).remove(&profile).ok_or_else(|| {
+      ClaudeCodeError::Provider(format!("AWS profile '{}' not found in {}", profile, path.display()))
+    })?;
+
+    let access_key_id = section.get("aws_access_key_id").cloned();
+    let secret_access_key = section.get("aws_secret_access_key").cloned();
+    match (access_key_id, secret_access_key) {
+      (Some(access_key_id), Some(secret_access_key)) => {
+        debug!("Using AWS credentials from profile {}", profile);
+        Ok(Self {
+          access_key_id,
+          secret_access_key,
+          session_token: section.get("aws_session_token").cloned(),
+        })
+      }
+      _ =>
+        Err(
+          ClaudeCodeError::Provider(format!("AWS profile '{}' is missing access keys", profile))
+        ),
+    }
+  }
+}
+
+fn resolve_profile(profile: Option<&str>) -> String {
+  profile
+    .map(|p| p.to_string())
+    .or_else(|| std::env::var("AWS_PROFILE").ok())
+    .unwrap_or_else(|| "default".to_string())
+}
+
+fn shared_file(env_var: &str, name: &str) -> Result<PathBuf> {
+  if let Ok(path) = std::env::var(env_var) {
+    return Ok(PathBuf::from(path));
+  }
+
+  Ok(dirs::home_dir().ok_or("Could not determine home directory")?.join(".aws").join(name))
+}
+
+/// Minimal INI parser for the AWS shared config and credentials files
+fn parse_ini(content: &str) -> HashMap<String, HashMap<String, String>> {
+  let mut sections: HashMap<String, HashMap<String, String>> = HashMap::new();
+  let mut current: Option<String> = None;
+
+  for line in content.lines() {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+      continue;
+    }
+
+    if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+      // ~/.aws/config prefixes non-default profiles with "profile "
+      let name = name.trim();
+      let name = name.strip_prefix("profile ").unwrap_or(name).trim();
+      current = Some(name.to_string());
+      sections.entry(name.to_string()).or_default();
+      continue;
+    }
+
+    if let (Some(section), Some((key, value))) = (&current, line.split_once('=')) {
+      sections
+        .entry(section.clone())
+        .or_default()
+        .insert(key.trim().to_string(), value.trim().to_string());
+    }
+  }
+
+  sections
+}
+
+/// Resolve the region from config, the environment, then the shared config file
+fn resolve_region(region: Option<&str>, profile: Option<&str>) -> Option<String> {
+  if let Some(region) = region {
+    return Some(region.to_string());
+  }
+
+  if let Ok(region) = std::env::var("AWS_REGION").or_else(|_| std::env::var("AWS_DEFAULT_REGION")) {
+    return Some(region);
+  }
+
+  let path = shared_file("AWS_CONFIG_FILE", "config").ok()?;
+  let content = std::fs::read_to_string(path).ok()?;
+  parse_ini(&content).remove(&resolve_profile(profile))?.remove("region")
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+  hex::encode(Sha256::digest(data))
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+  let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+  mac.update(data);
+  mac.finalize().into_bytes().to_vec()
+}
+
+/// Derive the SigV4 signing key for a given date, region and service
+fn signing_key(secret_access_key: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+  let k_date = hmac_sha256(format!("AWS4{}", secret_access_key).as_bytes(), date.as_bytes());
+  let k_region = hmac_sha256(&k_date, region.as_bytes());
+  let k_service = hmac_sha256(&k_region, service.as_bytes());
+  hmac_sha256(&k_service, b"aws4_request")
+}
+
+/// Build the SigV4 canonical request. `headers` must be lowercase and sorted by name.
+fn canonical_request(
+  method: &str,
+  path: &str,
+  query: &str,
+  headers: &[(String, String)],
+  payload: &[u8]
+) -> String {
+  let canonical_headers: String = headers
+    .iter()
+    .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+    .collect();
+  let signed_headers = signed_header_names(headers);
+
+  format!(
+    "{}\n{}\n{}\n{}\n{}\n{}",
+    method,
+    path,
+    query,
+    canonical_headers,
+    signed_headers,
+    sha256_hex(payload)
+  )
+}
+
+fn signed_header_names(headers: &[(String, String)]) -> String {
+  headers
+    .iter()
+    .map(|(name, _)| name.as_str())
+    .collect::<Vec<_>>()
+    .join(";")
+}
+
+/// Compute the `Authorization` header value for a request
+#[allow(clippy::too_many_arguments)]
+fn authorization_header(
+  credentials: &AwsCredentials,
+  region: &str,
+  service: &str,
+  amz_date: &str,
+  method: &str,
+  path: &str,
+  query: &str,
+  headers: &[(String, String)],
+  payload: &[u8]
+) -> String {
+  let date = &amz_date[..8];
+  let scope = format!("{}/{}/{}/aws4_request", date, region, service);
+  let canonical = canonical_request(method, path, query, headers, payload);
+  let string_to_sign = format!(
+    "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+    amz_date,
+    scope,
+    sha256_hex(canonical.as_bytes())
+  );
+
+  let key = signing_key(&credentials.secret_access_key, date, region, service);
+  let signature = hex::encode(hmac_sha256(&key, string_to_sign.as_bytes()));
+
+  format!(
+    "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+    credentials.access_key_id,
+    scope,
+    signed_header_names(headers),
+    signature
+  )
+}
+
+/// AWS Secrets Manager / SSM Parameter Store secret provider
+///
+/// Target types:
+/// - `secret`: a Secrets Manager secret (name or ARN) holding a JSON object of all secrets
+/// - `parameter`: an SSM path prefix; each secret is written to `<path>/<secret name>`
+pub struct AwsProvider {
+  base: BaseProvider,
+  client: reqwest::Client,
+  /// Credentials to sign with instead of the standard chain
+  credentials: Option<AwsCredentials>,
+}
+
+impl AwsProvider {
+  pub fn new(config: HashMap<String, String>) -> Result<Self> {
+    Ok(Self {
+      base: BaseProvider::new("aws", config),
+      client: reqwest::Client::new(),
+      credentials: None,
+    })
+  }
+
+  /// Sign requests with `credentials` rather than resolving them
+  pub fn with_credentials(mut self, credentials: AwsCredentials) -> Self {
+    self.credentials = Some(credentials);
+    self
+  }
+
+  fn region(&self) -> Result<String> {
+    resolve_region(
+      self.base.get_config("region").map(|s| s.as_str()),
+      self.base.get_config("profile").map(|s| s.as_str())
+    ).ok_or_else(|| {
+      ClaudeCodeError::Provider(
+        "AWS region not configured (set providers.aws.region or AWS_REGION)".to_string()
+      )
+    })
+  }
+
+  fn credentials(&self) -> Result<AwsCredentials> {
+    match &self.credentials {
+      Some(credentials) => Ok(credentials.clone()),
+      None => AwsCredentials::resolve(self.base.get_config("profile").map(|s| s.as_str())),
+    }
+  }
+
+  fn endpoint(&self, service: &str, region: &str) -> String {
+    self.base
+      .get_config("endpoint_url")
+      .cloned()
+      .or_else(|| std::env::var("AWS_ENDPOINT_URL").ok())
+      .unwrap_or_else(|| format!("https://{}.{}.amazonaws.com", service, region))
+  }
+
+  /// Call an AWS JSON 1.1 API action and return the parsed response body
+  async fn call(
+    &self,
+    service: &str,
+    target: &str,
+    body: &serde_json::Value
+  ) -> Result<serde_json::Value> {
+    let region = self.region()?;
+    let credentials = self.credentials()?;
+    let endpoint = self.endpoint(service, &region);
+
+    let url = reqwest::Url
+      ::parse(&endpoint)
+      .map_err(|e| ClaudeCodeError::Provider(format!("Invalid AWS endpoint '{}': {}", endpoint, e)))?;
+    let host = match (url.host_str(), url.port()) {
+      (Some(host), Some(port)) => format!("{}:{}", host, port),
+      (Some(host), None) => host.to_string(),
+      _ => {
+        return Err(ClaudeCodeError::Provider(format!("AWS endpoint has no host: {}", endpoint)));
+      }
+    };
+
+    let payload = serde_json::to_vec(body)?;
+    let amz_date = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+    let mut headers = vec![
+      ("content-type".to_string(), "application/x-amz-json-1.1".to_string()),
+      ("host".to_string(), host),
+      ("x-amz-date".to_string(), amz_date.clone()),
+      ("x-amz-target".to_string(), target.to_string())
+    ];
+    if let Some(token) = &credentials.session_token {
+      headers.push(("x-amz-security-token".to_string(), token.clone()));
+    }
+    headers.sort();
+
+    let authorization = authorization_header(
+      &credentials,
+      &region,
+      service,
+      &amz_date,
+      "POST",
+      url.path(),
+      "",
+      &headers,
+      &payload
+    );
+
+    let mut request = self.client.post(url.clone()).header("authorization", authorization);
+    for (name, value) in &headers {
+      if name != "host" {
+        request = request.header(name.as_str(), value.as_str());
+      }
+    }
+
+    debug!("Calling AWS {} at {}", target, endpoint);
+    let response = request.body(payload).send().await?;
+    let status = response.status();
+    let text = response.text().await?;
+
+    if status.is_success() {
+      if text.is_empty() {
+        return Ok(serde_json::Value::Null);
+      }
+      return Ok(serde_json::from_str(&text)?);
+    }
+
+    Err(ClaudeCodeError::Provider(format!("{} failed ({}): {}", target, status, aws_error(&text))))
+  }
+
+  /// Write all secrets as one JSON object, merging with any keys already stored
+  async fn upsert_secret(&self, secret_id: &str, secrets: &[Secret]) -> Result<()> {
+    let existing = self.call(
+      SECRETS_MANAGER,
+      "secretsmanager.GetSecretValue",
+      &serde_json::json!({ "SecretId": secret_id })
+    ).await;
+
+    let mut object = serde_json::Map::new();
+    let exists = match existing {
+      Ok(value) => {
+        if
+          let Some(current) = value
+            .get("SecretString")
+            .and_then(|s| s.as_str())
+            .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
+            .and_then(|v| v.as_object().cloned())
+        {
+          object = current;
+        }
+        true
+      }
+      Err(e) if is_not_found(&e) => false,
+      Err(e) => {
+        return Err(e);
+      }
+    };
+
+    for secret in secrets {
+      object.insert(secret.name.clone(), serde_json::Value::String(secret.value.clone()));
+    }
+    let secret_string = serde_json::Value::Object(object).to_string();
+
+    if exists {
+      self.call(
+        SECRETS_MANAGER,
+        "secretsmanager.PutSecretValue",
+        &serde_json::json!({ "SecretId": secret_id, "SecretString": secret_string })
+      ).await?;
+    } else {
+      info!("Secret {} not found, creating it", secret_id);
+      self.call(
+        SECRETS_MANAGER,
+        "secretsmanager.CreateSecret",
+        &serde_json::json!({
+          "Name": secret_id,
+          "SecretString": secret_string,
+          "Description": "Claude Code credentials managed by claude-code-toolkit",
+        })
+      ).await?;
+    }
+
+    Ok(())
+  }
+
+  async fn put_parameter(&self, path: &str, secret: &Secret) -> Result<()> {
+    let name = format!("{}/{}", path.trim_end_matches('/'), secret.name);
+    self.call(
+      SSM,
+      "AmazonSSM.PutParameter",
+      &serde_json::json!({
+        "Name": name,
+        "Value": secret.value,
+        "Type": "SecureString",
+        "Overwrite": true,
+      })
+    ).await?;
+    Ok(())
+  }
+
+  /// Remove keys from a JSON secret, leaving any other keys in place
+  async fn remove_secret_keys(&self, secret_id: &str, names: &[String]) -> Result<()> {
+    let existing = match
+      self.call(
+        SECRETS_MANAGER,
+        "secretsmanager.GetSecretValue",
+        &serde_json::json!({ "SecretId": secret_id })
+      ).await
+    {
+      Ok(value) => value,
+      Err(e) if is_not_found(&e) => {
+        return Ok(());
+      }
+      Err(e) => {
+        return Err(e);
+      }
+    };
+
+    let mut object = existing
+      .get("SecretString")
+      .and_then(|s| s.as_str())
+      .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
+      .and_then(|v| v.as_object().cloned())
+      .unwrap_or_default();
+    if !names.iter().any(|name| object.contains_key(name)) {
+      return Ok(());
+    }
+    for name in names {
+      object.remove(name);
+    }
+
+    self.call(
+      SECRETS_MANAGER,
+      "secretsmanager.PutSecretValue",
+      &serde_json::json!({
+        "SecretId": secret_id,
+        "SecretString": serde_json::Value::Object(object).to_string(),
+      })
+    ).await?;
+    Ok(())
+  }
+
+  async fn delete_parameter(&self, path: &str, secret_name: &str) -> Result<()> {
+    let name = format!("{}/{}", path.trim_end_matches('/'), secret_name);
+    match self.call(SSM, "AmazonSSM.DeleteParameter", &serde_json::json!({ "Name": name })).await {
+      Ok(_) => Ok(()),
+      Err(ClaudeCodeError::Provider(msg)) if msg.contains("ParameterNotFound") => Ok(()),
+      Err(e) => Err(e),
+    }
+  }
+
+  async fn check_target_access(&self, target: &Target) -> Result<bool> {
+    let result = match target.target_type.as_str() {
+      "secret" =>
+        self.call(
+          SECRETS_MANAGER,
+          "secretsmanager.DescribeSecret",
+          &serde_json::json!({ "SecretId": target.name })
+        ).await,
+      "parameter" =>
+        self.call(
+          SSM,
+          "AmazonSSM.GetParametersByPath",
+          &serde_json::json!({ "Path": target.name, "MaxResults": 1 })
+        ).await,
+      _ => {
+        return Ok(false);
+      }
+    };
+
+    match result {
+      Ok(_) => Ok(true),
+      // A missing secret is created on first sync
+      Err(e) if is_not_found(&e) => Ok(true),
+      Err(e) => {
+        debug!("Access check failed for {} {}: {}", target.target_type, target.name, e);
+        Ok(false)
+      }
+    }
+  }
+}
+
+/// Extract a readable message from an AWS JSON error body
+fn aws_error(body: &str) -> String {
+  match serde_json::from_str::<serde_json::Value>(body) {
+    Ok(value) => {
+      let error_type = value
+        .get("__type")
+        .and_then(|t| t.as_str())
+        .unwrap_or("UnknownError");
+      let message = value
+        .get("message")
+        .or_else(|| value.get("Message"))
+        .and_then(|m| m.as_str())
+        .unwrap_or("");
+      format!("{} {}", error_type, message).trim().to_string()
+    }
+    Err(_) => body.to_string(),
+  }
+}
+
+fn is_not_found(error: &ClaudeCodeError) -> bool {
+  matches!(error, ClaudeCodeError::Provider(msg) if msg.contains("ResourceNotFoundException"))
+}
+
+#[async_trait]
+impl SecretProvider for AwsProvider {
+  fn provider_name(&self) -> &str {
+    "aws"
+  }
+
+  async fn sync_secrets(&self, secrets: &[Secret], targets: &[Target]) -> Result<SyncResult> {
+    let mut succeeded = 0;
+    let mut failed = 0;
+    let mut errors = Vec::new();
+
+    for target in targets {
+      if target.provider != self.provider_name() {
+        continue;
+      }
+
+      match target.target_type.as_str() {
+        "secret" => {
+          match self.upsert_secret(&target.name, secrets).await {
+            Ok(()) => {
+              info!("Updated {} secrets in {}", secrets.len(), target.name);
+              succeeded += secrets.len();
+            }
+            Err(e) => {
+              error!("Failed to update secret {}: {}", target.name, e);
+              failed += secrets.len();
+              errors.push(format!("{}:{} - {}", target.target_type, target.name, e));
+            }
+          }
+        }
+        "parameter" => {
+          for secret in secrets {
+            match self.put_parameter(&target.name, secret).await {
+              Ok(()) => {
+                debug!("Updated parameter {}/{}", target.name, secret.name);
+                succeeded += 1;
+              }
+              Err(e) => {
+                error!("Failed to update parameter {}/{}: {}", target.name, secret.name, e);
+                failed += 1;
+                errors.push(format!("{}:{} - {}", target.target_type, target.name, e));
+              }
+            }
+          }
+        }
+        other => {
+          warn!("Unsupported AWS target type: {}", other);
+          failed += 1;
+          errors.push(format!("{}:{} - unsupported target type", other, target.name));
+        }
+      }
+    }
+
+    Ok(SyncResult {
+      succeeded,
+      failed,
+      errors,
+    })
+  }
+
+  async fn delete_secrets(
+    &self,
+    secret_names: &[String],
+    targets: &[Target]
+  ) -> Result<SyncResult> {
+    let mut succeeded = 0;
+    let mut failed = 0;
+    let mut errors = Vec::new();
+
+    for target in targets.iter().filter(|t| t.provider == self.provider_name()) {
+      match target.target_type.as_str() {
+        "secret" => {
+          match self.remove_secret_keys(&target.name, secret_names).await {
+            Ok(()) => {
+              info!("Removed {} keys from {}", secret_names.len(), target.name);
+              succeeded += secret_names.len();
+            }
+            Err(e) => {
+              error!("Failed to remove keys from secret {}: {}", target.name, e);
+              failed += secret_names.len();
+              errors.push(format!("{}:{} - {}", target.target_type, target.name, e));
+            }
+          }
+        }
+        "parameter" => {
+          for secret_name in secret_names {
+            match self.delete_parameter(&target.name, secret_name).await {
+              Ok(()) => {
+                succeeded += 1;
+              }
+              Err(e) => {
+                error!("Failed to delete parameter {}/{}: {}", target.name, secret_name, e);
+                failed += 1;
+                errors.push(format!("{}:{} - {}", target.target_type, target.name, e));
+              }
+            }
+          }
+        }
+        other => {
+          failed += 1;
+          errors.push(format!("{}:{} - unsupported target type", other, target.name));
+        }
+      }
+    }
+
+    Ok(SyncResult {
+      succeeded,
+      failed,
+      errors,
+    })
+  }
+
+  async fn validate_access(&self, targets: &[Target]) -> Result<HashMap<String, bool>> {
+    let mut results = HashMap::new();
+
+    for target in targets {
+      if target.provider != self.provider_name() {
+        continue;
+      }
+
+      let key = format!("{}:{}", target.target_type, target.name);
+      let has_access = self.check_target_access(target).await.unwrap_or(false);
+      results.insert(key, has_access);
+    }
+
+    Ok(results)
+  }
+
+  async fn list_targets(&self, target_type: &str) -> Result<Vec<String>> {
+    let (service, action, list_key) = match target_type {
+      "secret" => (SECRETS_MANAGER, "secretsmanager.ListSecrets", "SecretList"),
+      "parameter" => (SSM, "AmazonSSM.DescribeParameters", "Parameters"),
+      _ => {
+        return Err(ClaudeCodeError::Generic(format!("Unsupported target type: {}", target_type)));
+      }
+    };
+
+    let response = self.call(service, action, &serde_json::json!({})).await?;
+    let names = response
+      .get(list_key)
+      .and_then(|l| l.as_array())
+      .map(|items| {
+        items
+          .iter()
+          .filter_map(|item| item.get("Name").and_then(|n| n.as_str()))
+          .map(|s| s.to_string())
+          .collect()
+      })
+      .unwrap_or_default();
+
+    Ok(names)
+  }
+
+  async fn is_configured(&self) -> Result<bool> {
+    if let Err(e) = self.region() {
+      warn!("{}", e);
+      return Ok(false);
+    }
+
+    match self.credentials() {
+      Ok(_) => Ok(true),
+      Err(e) => {
+        warn!("{}", e);
+        Ok(false)
+      }
+    }
+  }
+}
+
+/// AWS provider creator for Factory Pattern
+pub struct AwsProviderCreator;
+
+impl ProviderCreator for AwsProviderCreator {
+  fn create(&self, config: &HashMap<String, String>) -> Result<Box<dyn SecretProvider>> {
+    let provider = AwsProvider::new(config.clone())?;
+    Ok(Box::new(provider))
+  }
+
+  fn provider_type(&self) -> &str {
+    "aws"
+  }
+
+  fn required_config(&self) -> Vec<&str> {
+    vec![] // Credentials and region come from the standard AWS chain
+  }
+
+  fn optional_config(&self) -> Vec<&str> {
+    vec!["region", "profile", "endpoint_url"]
+  }
+
+  /// Secrets are JSON keys, which may be anything; parameters are path segments
+  fn secret_name_rules(&self, kind: &str) -> SecretNameRules {
+    if kind == "parameter" {
+      SecretNameRules { extra_chars: Some("_.-"), ..SecretNameRules::default() }
+    } else {
+      SecretNameRules::default()
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use tokio::io::{ AsyncReadExt, AsyncWriteExt };
+  use tokio::net::TcpListener;
+
+  fn example_credentials() -> AwsCredentials {
+    AwsCredentials {
+      access_key_id: "AKIDEXAMPLE".to_string(),
+      secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string(),
+      session_token: None,
+    }
+  }
+
+  #[test]
+  fn test_signing_key() {
+    // Example from the AWS Signature Version 4 documentation
+    let key = signing_key(&example_credentials().secret_access_key, "20150830", "us-east-1", "iam");
+    assert_eq!(hex::encode(key), "c4afb1cc5771d871763a393e44b703571b55cc28424d1a5e86da6ed3c154a4b9");
+  }
+
+  #[test]
+  fn test_authorization_header() {
+    let headers = vec![
+      (
+        "content-type".to_string(),
+        "application/x-www-form-urlencoded; charset=utf-8".to_string(),
+      ),
+      ("host".to_string(), "iam.amazonaws.com".to_string()),
+      ("x-amz-date".to_string(), "20150830T123600Z".to_string())
+    ];
+
+    let canonical = canonical_request(
+      "GET",
+      "/",
+      "Action=ListUsers&Version=2010-05-08",
+      &headers,
+      b""
+    );
+    assert_eq!(
+      sha256_hex(canonical.as_bytes()),
+      "f536975d06c0309214f805bb90ccff089219ecd68b2577efef23edd43b7e1a59"
+    );
+
+    let authorization = authorization_header(
+      &example_credentials(),
+      "us-east-1",
+      "iam",
+      "20150830T123600Z",
+      "GET",
+      "/",
+      "Action=ListUsers&Version=2010-05-08",
+      &headers,
+      b""
+    );
+    assert_eq!(
+      authorization,
+      "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/iam/aws4_request, \
+       SignedHeaders=content-type;host;x-amz-date, \
+       Signature=5d672d79c15b13162d9279b0855cfba6789a8edb4c82c400e06b5924a6f2b5d7"
+    );
+  }
+
+  #[test]
+  fn test_parse_ini_profiles() {
+    let content =
+      "[default]\nregion = eu-west-1\n\n# comment\n[profile bots]\nregion=us-east-2\naws_access_key_id = AKID\n";
+    let sections = parse_ini(content);
+
+    assert_eq!(sections["default"]["region"], "eu-west-1");
+    assert_eq!(sections["bots"]["region"], "us-east-2");
+    assert_eq!(sections["bots"]["aws_access_key_id"], "AKID");
+  }
+
+  #[test]
+  fn test_aws_error_message() {
+    let body =
+      r#"{"__type":"ResourceNotFoundException","message":"Secrets Manager can't find the specified secret."}"#;
+    let message = aws_error(body);
+    assert!(message.starts_with("ResourceNotFoundException"));
+    assert!(is_not_found(&ClaudeCodeError::Provider(message)));
+  }
+
+  /// Answer one request per connection with the given JSON bodies and return the raw requests
+  async fn serve(
+    listener: TcpListener,
+    responses: Vec<(&'static str, &'static str)>
+  ) -> Vec<String> {
+    let mut requests = Vec::new();
+
+    for (status, body) in responses {
+      let (mut socket, _) = listener.accept().await.unwrap();
+      let mut request = Vec::new();
+      let mut buf = [0u8; 4096];
+      loop {
+        let n = socket.read(&mut buf).await.unwrap();
+        request.extend_from_slice(&buf[..n]);
+        let text = String::from_utf8_lossy(&request).to_lowercase();
+        if let Some(header_end) = text.find("\r\n\r\n") {
+          let length = text
+            .lines()
+            .find_map(|l| l.strip_prefix("content-length:"))
+            .and_then(|v| v.trim().parse::<usize>().ok())
+            .unwrap_or(0);
+          if request.len() >= header_end + 4 + length {
+            break;
+          }
+        }
+        if n == 0 {
+          break;
+        }
+      }
+
+      let response = format!(
+        "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+      );
+      socket.write_all(response.as_bytes()).await.unwrap();
+      requests.push(String::from_utf8_lossy(&request).to_string());
+    }
+
+    requests
+  }
+
+  /// Headers (lowercase) and body of a raw request
+  fn parse_request(request: &str) -> (HashMap<String, String>, String) {
+    let (head, body) = request.split_once("\r\n\r\n").unwrap();
+    let headers = head
+      .lines()
+      .skip(1)
+      .filter_map(|line| line.split_once(':'))
+      .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+      .collect();
+    (headers, body.to_string())
+  }
+
+  /// The signature a request carries matches one computed over what the server received
+  fn assert_signed(request: &str, service: &str) {
+    let (headers, body) = parse_request(request);
+    let mut signed: Vec<(String, String)> = [
+      "content-type",
+      "host",
+      "x-amz-date",
+      "x-amz-target",
+    ]
+      .iter()
+      .map(|name| (name.to_string(), headers[*name].clone()))
+      .collect();
+    signed.sort();
+
+    let expected = authorization_header(
+      &example_credentials(),
+      "eu-west-1",
+      service,
+      &headers["x-amz-date"],
+      "POST",
+      "/",
+      "",
+      &signed,
+      body.as_bytes()
+    );
+    assert_eq!(headers["authorization"], expected);
+  }
+
+  #[tokio::test]
+  async fn test_sync_merges_secret_and_puts_parameters() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    let existing = r#"{"SecretString": "{\"OTHER\":\"keep\",\"CLAUDE_ACCESS_TOKEN\":\"old\"}"}"#;
+    let server = tokio::spawn(
+      serve(listener, vec![("200 OK", existing), ("200 OK", "{}"), ("200 OK", "{}")])
+    );
+
+    let mut config = HashMap::new();
+    config.insert("region".to_string(), "eu-west-1".to_string());
+    config.insert("endpoint_url".to_string(), endpoint);
+    let provider = AwsProvider::new(config).unwrap().with_credentials(example_credentials());
+
+    let secrets = vec![Secret {
+      name: "CLAUDE_ACCESS_TOKEN".to_string(),
+      value: "sk-test".to_string(),
+      description: None,
+    }];
+    let target = |kind: &str, name: &str| Target {
+      provider: "aws".to_string(),
+      target_type: kind.to_string(),
+      name: name.to_string(),
+      config: HashMap::new(),
+    };
+    let targets = [target("secret", "claude/credentials"), target("parameter", "/claude/")];
+
+    let result = provider.sync_secrets(&secrets, &targets).await.unwrap();
+    assert_eq!((result.succeeded, result.failed), (2, 0), "{:?}", result.errors);
+
+    let requests = server.await.unwrap();
+    let (headers, _) = parse_request(&requests[0]);
+    assert_eq!(headers["x-amz-target"], "secretsmanager.GetSecretValue");
+    assert_signed(&requests[0], SECRETS_MANAGER);
+
+    // Keys already in the secret are kept, synced ones replaced
+    let (headers, body) = parse_request(&requests[1]);
+    assert_eq!(headers["x-amz-target"], "secretsmanager.PutSecretValue");
+    assert_signed(&requests[1], SECRETS_MANAGER);
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+    let stored: serde_json::Value = serde_json
+      ::from_str(body["SecretString"].as_str().unwrap())
+      .unwrap();
+    assert_eq!(stored, serde_json::json!({ "OTHER": "keep", "CLAUDE_ACCESS_TOKEN": "sk-test" }));
+
+    let (headers, body) = parse_request(&requests[2]);
+    assert_eq!(headers["x-amz-target"], "AmazonSSM.PutParameter");
+    assert!(headers["authorization"].contains("/eu-west-1/ssm/aws4_request"));
+    assert_signed(&requests[2], SSM);
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(body["Name"], "/claude/CLAUDE_ACCESS_TOKEN");
+    assert_eq!(body["Type"], "SecureString");
+  }
+}
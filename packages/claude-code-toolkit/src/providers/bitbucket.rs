@@ -0,0 +1,678 @@
+//! Bitbucket Pipelines variables provider implementation
+//!
+//! Maintains secured pipeline variables through the Bitbucket 2.0 REST API. Target types:
+//!
+//! - `repository`: `workspace/repo_slug` repository variables
+//! - `workspace`: `workspace` workspace variables
+//! - `deployment`: `workspace/repo_slug/environment` deployment-environment variables
+//!
+//! Authentication uses an access token (`Bearer`) when one is available, otherwise a
+//! username and app password (`Basic`). Secrets are read from environment variables so
+//! they never end up in the configuration file. `base_url` points the provider at a
+//! Data Center instance or proxy exposing the same API.
+
+use super::{ BaseProvider, ProviderCreator, SecretNameRules };
+use crate::error::{ ClaudeCodeError, Result };
+use crate::traits::{ Secret, SecretProvider, SyncResult, Target };
+use async_trait::async_trait;
+use std::collections::HashMap;
+use tracing::{ debug, error, info, warn };
+
+const DEFAULT_BASE_URL: &str = "https://api.bitbucket.org/2.0";
+const DEFAULT_ACCESS_TOKEN_ENV: &str = "BITBUCKET_ACCESS_TOKEN";
+const DEFAULT_APP_PASSWORD_ENV: &str = "BITBUCKET_APP_PASSWORD";
+const USERNAME_ENV: &str = "BITBUCKET_USERNAME";
+const PAGE_LENGTH: &str = "100";
+
+/// Resolved Bitbucket credentials
+#[derive(Debug, Clone, PartialEq)]
+enum BitbucketAuth {
+  Bearer(String),
+  Basic {
+    username: String,
+    password: String,
+  },
+}
+
+impl BitbucketAuth {
+  /// Resolve credentials from provider config and the environment
+  ///
+  /// An access token takes precedence over an app password.
+  fn resolve(config: &HashMap<String, String>) -> Result<Self> {
+    let env_value = |key: &str, default: &str| {
+      let name = config
+        .get(key)
+        .map(|s| s.as_str())
+        .unwrap_or(default);
+      std::env
+        ::var(name)
+        .ok()
+        .filter(|v| !v.is_empty())
+    };
+
+    if let Some(token) = env_value("access_token_env", DEFAULT_ACCESS_TOKEN_ENV) {
+      return Ok(Self::Bearer(token));
+    }
+
+    let username = config
+      .get("username")
+      .cloned()
+      .or_else(|| std::env::var(USERNAME_ENV).ok())
+      .filter(|v| !v.is_empty());
+    let password = env_value("app_password_env", DEFAULT_APP_PASSWORD_ENV);
+
+    match (username, password) {
+      (Some(username), Some(password)) => Ok(Self::Basic { username, password }),
+      (None, Some(_)) =>
+        Err(
+          ClaudeCodeError::Provider(
+            format!(
+              "Bitbucket app password requires a username (set providers.bitbucket.username or {})",
+              USERNAME_ENV
+            )
+          )
+        ),
+      _ =>
+        Err(
+          ClaudeCodeError::Provider(
+            format!(
+              "Bitbucket credentials not found (set {} or {})",
+              DEFAULT_ACCESS_TOKEN_ENV,
+              DEFAULT_APP_PASSWORD_ENV
+            )
+          )
+        ),
+    }
+  }
+
+  fn apply(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    match self {
+      Self::Bearer(token) => request.bearer_auth(token),
+      Self::Basic { username, password } => request.basic_auth(username, Some(password)),
+    }
+  }
+}
+
+/// Split a `workspace/repo_slug` target name
+fn parse_repository(name: &str) -> Result<(&str, &str)> {
+  match name.split_once('/') {
+    Some((workspace, repo)) if !workspace.is_empty() && !repo.is_empty() && !repo.contains('/') => {
+      Ok((workspace, repo))
+    }
+    _ => Err(ClaudeCodeError::InvalidRepoFormat { repo: name.to_string() }),
+  }
+}
+
+/// Split a `workspace/repo_slug/environment` target name
+fn parse_deployment(name: &str) -> Result<(&str, &str, &str)> {
+  let mut parts = name.splitn(3, '/');
+  match (parts.next(), parts.next(), parts.next()) {
+    (Some(workspace), Some(repo), Some(environment)) if
+      !workspace.is_empty() &&
+      !repo.is_empty() &&
+      !environment.is_empty()
+    => {
+      Ok((workspace, repo, environment))
+    }
+    _ =>
+      Err(
+        ClaudeCodeError::InvalidConfig(
+          format!("Invalid deployment target '{}', expected workspace/repo/environment", name)
+        )
+      ),
+  }
+}
+
+/// Extract a readable message from a Bitbucket error body
+fn bitbucket_error(body: &str) -> String {
+  serde_json
+    ::from_str::<serde_json::Value>(body)
+    .ok()
+    .and_then(|value| {
+      value
+        .pointer("/error/message")
+        .and_then(|m| m.as_str())
+        .map(|m| m.to_string())
+    })
+    .unwrap_or_else(|| body.trim().to_string())
+}
+
+/// Bitbucket secret provider implementation
+pub struct BitbucketProvider {
+  base: BaseProvider,
+  client: reqwest::Client,
+}
+
+impl BitbucketProvider {
+  pub fn new(config: HashMap<String, String>) -> Result<Self> {
+    Ok(Self {
+      base: BaseProvider::new("bitbucket", config),
+      client: reqwest::Client::new(),
+    })
+  }
+
+  fn url(&self, path: &str) -> String {
+    let base = self.base
+      .get_config("base_url")
+      .map(|s| s.as_str())
+      .unwrap_or(DEFAULT_BASE_URL);
+    format!("{}/{}", base.trim_end_matches('/'), path.trim_start_matches('/'))
+  }
+
+  /// Send an authenticated request and return the parsed response body
+  async fn request(
+    &self,
+    method: reqwest::Method,
+    url: &str,
+    body: Option<&serde_json::Value>
+  ) -> Result<serde_json::Value> {
+    let auth = BitbucketAuth::resolve(&self.base.config)?;
+    let mut request = auth.apply(self.client.request(method.clone(), url));
+    if let Some(body) = body {
+      request = request.json(body);
+    }
+
+    debug!("Calling Bitbucket {} {}", method, url);
+    let response = request.send().await?;
+    let status = response.status();
+    let text = response.text().await?;
+
+    if status.is_success() {
+      if text.trim().is_empty() {
+        return Ok(serde_json::Value::Null);
+      }
+      return Ok(serde_json::from_str(&text)?);
+    }
+
+    Err(
+      ClaudeCodeError::Provider(
+        format!("{} {} failed ({}): {}", method, url, status, bitbucket_error(&text))
+      )
+    )
+  }
+
+  /// Fetch every item of a paginated collection
+  async fn get_all(&self, url: &str) -> Result<Vec<serde_json::Value>> {
+    let mut first = reqwest::Url
+      ::parse(url)
+      .map_err(|e| ClaudeCodeError::Provider(format!("Invalid Bitbucket URL '{}': {}", url, e)))?;
+    first.query_pairs_mut().append_pair("pagelen", PAGE_LENGTH);
+
+    let mut values = Vec::new();
+    let mut next = Some(first.to_string());
+    while let Some(page_url) = next {
+      let page = self.request(reqwest::Method::GET, &page_url, None).await?;
+      if let Some(items) = page.get("values").and_then(|v| v.as_array()) {
+        values.extend(items.iter().cloned());
+      }
+      next = page
+        .get("next")
+        .and_then(|n| n.as_str())
+        .map(|n| n.to_string());
+    }
+
+    Ok(values)
+  }
+
+  /// Look up a deployment environment's UUID by name
+  async fn environment_uuid(
+    &self,
+    workspace: &str,
+    repo: &str,
+    environment: &str
+  ) -> Result<String> {
+    let url = self.url(&format!("repositories/{}/{}/environments", workspace, repo));
+    self
+      .get_all(&url).await?
+      .iter()
+      .find(|env| {
+        env
+          .get("name")
+          .and_then(|n| n.as_str())
+          .is_some_and(|n| n.eq_ignore_ascii_case(environment))
+      })
+      .and_then(|env| env.get("uuid").and_then(|u| u.as_str()))
+      .map(|u| u.to_string())
+      .ok_or_else(|| ClaudeCodeError::TargetNotFound {
+        target_type: "deployment environment".to_string(),
+        name: format!("{}/{}/{}", workspace, repo, environment),
+      })
+  }
+
+  /// Resolve the variables collection URL for a target
+  async fn variables_url(&self, target: &Target) -> Result<String> {
+    match target.target_type.as_str() {
+      "repository" => {
+        let (workspace, repo) = parse_repository(&target.name)?;
+        Ok(self.url(&format!("repositories/{}/{}/pipelines_config/variables", workspace, repo)))
+      }
+      "workspace" => {
+        Ok(self.url(&format!("workspaces/{}/pipelines-config/variables", target.name)))
+      }
+      "deployment" => {
+        let (workspace, repo, environment) = parse_deployment(&target.name)?;
+        let uuid = self.environment_uuid(workspace, repo, environment).await?;
+        Ok(
+          self.url(
+            &format!(
+              "repositories/{}/{}/deployments_config/environments/{}/variables",
+              workspace,
+              repo,
+              uuid
+            )
+          )
+        )
+      }
+      other => Err(ClaudeCodeError::Generic(format!("Unsupported target type: {}", other))),
+    }
+  }
+
+  /// Create or update one secured variable
+  async fn upsert_variable(
+    &self,
+    variables_url: &str,
+    existing: &HashMap<String, String>,
+    secret: &Secret
+  ) -> Result<()> {
+    let body = serde_json::json!({
+      "key": secret.name,
+      "value": secret.value,
+      "secured": true,
+    });
+
+    match existing.get(&secret.name) {
+      Some(uuid) => {
+        let url = format!("{}/{}", variables_url, uuid);
+        self.request(reqwest::Method::PUT, &url, Some(&body)).await?;
+      }
+      None => {
+        self.request(reqwest::Method::POST, variables_url, Some(&body)).await?;
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Map existing variable keys to their UUIDs
+  async fn existing_variables(&self, variables_url: &str) -> Result<HashMap<String, String>> {
+    let variables = self
+      .get_all(variables_url).await?
+      .into_iter()
+      .filter_map(|var| {
+        let key = var.get("key")?.as_str()?.to_string();
+        let uuid = var.get("uuid")?.as_str()?.to_string();
+        Some((key, uuid))
+      })
+      .collect();
+    Ok(variables)
+  }
+
+  async fn sync_target(&self, target: &Target, secrets: &[Secret], result: &mut SyncResult) {
+    let prepared = match self.variables_url(target).await {
+      Ok(url) => self.existing_variables(&url).await.map(|existing| (url, existing)),
+      Err(e) => Err(e),
+    };
+
+    let (variables_url, existing) = match prepared {
+      Ok(prepared) => prepared,
+      Err(e) => {
+        error!("Failed to read variables for {} {}: {}", target.target_type, target.name, e);
+        result.failed += secrets.len();
+        result.errors.push(format!("{}:{} - {}", target.target_type, target.name, e));
+        return;
+      }
+    };
+
+    for secret in secrets {
+      match self.upsert_variable(&variables_url, &existing, secret).await {
+        Ok(()) => {
+          debug!("Updated variable {} for {} {}", secret.name, target.target_type, target.name);
+          result.succeeded += 1;
+        }
+        Err(e) => {
+          error!("Failed to update variable {} for {}: {}", secret.name, target.name, e);
+          result.failed += 1;
+          result.errors.push(format!("{}:{} - {}", target.target_type, target.name, e));
+        }
+      }
+    }
+  }
+
+  async fn delete_from_target(
+    &self,
+    target: &Target,
+    secret_names: &[String],
+    result: &mut SyncResult
+  ) {
+    let prepared = match self.variables_url(target).await {
+      Ok(url) => self.existing_variables(&url).await.map(|existing| (url, existing)),
+      Err(e) => Err(e),
+    };
+
+    let (variables_url, existing) = match prepared {
+      Ok(prepared) => prepared,
+      Err(e) => {
+        error!("Failed to read variables for {} {}: {}", target.target_type, target.name, e);
+        result.failed += secret_names.len();
+        result.errors.push(format!("{}:{} - {}", target.target_type, target.name, e));
+        return;
+      }
+    };
+
+    for secret_name in secret_names {
+      // Variables that no longer exist count as deleted
+      let Some(uuid) = existing.get(secret_name) else {
+        result.succeeded += 1;
+        continue;
+      };
+
+      let url = format!("{}/{}", variables_url, uuid);
+      match self.request(reqwest::Method::DELETE, &url, None).await {
+        Ok(_) => {
+          debug!("Deleted variable {} from {} {}", secret_name, target.target_type, target.name);
+          result.succeeded += 1;
+        }
+        Err(e) => {
+          error!("Failed to delete variable {} from {}: {}", secret_name, target.name, e);
+          result.failed += 1;
+          result.errors.push(format!("{}:{} - {}", target.target_type, target.name, e));
+        }
+      }
+    }
+  }
+
+  async fn check_target_access(&self, target: &Target) -> Result<bool> {
+    let url = match target.target_type.as_str() {
+      "repository" => {
+        let (workspace, repo) = parse_repository(&target.name)?;
+        self.url(&format!("repositories/{}/{}", workspace, repo))
+      }
+      "workspace" => self.url(&format!("workspaces/{}", target.name)),
+      "deployment" => {
+        let (workspace, repo, environment) = parse_deployment(&target.name)?;
+        return Ok(self.environment_uuid(workspace, repo, environment).await.is_ok());
+      }
+      _ => {
+        return Ok(false);
+      }
+    };
+
+    match self.request(reqwest::Method::GET, &url, None).await {
+      Ok(_) => Ok(true),
+      Err(e) => {
+        debug!("Access check failed for {} {}: {}", target.target_type, target.name, e);
+        Ok(false)
+      }
+    }
+  }
+}
+
+#[async_trait]
+impl SecretProvider for BitbucketProvider {
+  fn provider_name(&self) -> &str {
+    "bitbucket"
+  }
+
+  async fn sync_secrets(&self, secrets: &[Secret], targets: &[Target]) -> Result<SyncResult> {
+    let mut result = SyncResult {
+      succeeded: 0,
+      failed: 0,
+      errors: Vec::new(),
+    };
+
+    for target in targets {
+      if target.provider != self.provider_name() {
+        continue;
+      }
+
+      self.sync_target(target, secrets, &mut result).await;
+      info!("Processed {} variables for {} {}", secrets.len(), target.target_type, target.name);
+    }
+
+    Ok(result)
+  }
+
+  async fn delete_secrets(
+    &self,
+    secret_names: &[String],
+    targets: &[Target]
+  ) -> Result<SyncResult> {
+    let mut result = SyncResult {
+      succeeded: 0,
+      failed: 0,
+      errors: Vec::new(),
+    };
+
+    for target in targets.iter().filter(|t| t.provider == self.provider_name()) {
+      self.delete_from_target(target, secret_names, &mut result).await;
+    }
+
+    Ok(result)
+  }
+
+  async fn validate_access(&self, targets: &[Target]) -> Result<HashMap<String, bool>> {
+    let mut results = HashMap::new();
+
+    for target in targets {
+      if target.provider != self.provider_name() {
+        continue;
+      }
+
+      let key = format!("{}:{}", target.target_type, target.name);
+      let has_access = self.check_target_access(target).await.unwrap_or(false);
+      results.insert(key, has_access);
+    }
+
+    Ok(results)
+  }
+
+  async fn list_targets(&self, target_type: &str) -> Result<Vec<String>> {
+    let (path, pointer) = match target_type {
+      "workspace" => ("user/permissions/workspaces", "/workspace/slug"),
+      "repository" => ("repositories?role=member", "/full_name"),
+      // Environments are listed per repository and cannot be enumerated globally
+      "deployment" => {
+        return Ok(Vec::new());
+      }
+      _ => {
+        return Err(ClaudeCodeError::Generic(format!("Unsupported target type: {}", target_type)));
+      }
+    };
+
+    match self.get_all(&self.url(path)).await {
+      Ok(values) => {
+        let targets: Vec<String> = values
+          .iter()
+          .filter_map(|v| v.pointer(pointer).and_then(|s| s.as_str()))
+          .map(|s| s.to_string())
+          .collect();
+        debug!("Found {} {} targets", targets.len(), target_type);
+        Ok(targets)
+      }
+      Err(e) => {
+        warn!("Failed to list {}: {}", target_type, e);
+        Ok(vec![])
+      }
+    }
+  }
+
+  async fn is_configured(&self) -> Result<bool> {
+    match BitbucketAuth::resolve(&self.base.config) {
+      Ok(_) => Ok(true),
+      Err(e) => {
+        warn!("{}", e);
+        Ok(false)
+      }
+    }
+  }
+}
+
+/// Bitbucket provider creator for Factory Pattern
+pub struct BitbucketProviderCreator;
+
+impl ProviderCreator for BitbucketProviderCreator {
+  fn create(&self, config: &HashMap<String, String>) -> Result<Box<dyn SecretProvider>> {
+    let provider = BitbucketProvider::new(config.clone())?;
+    Ok(Box::new(provider))
+  }
+
+  fn provider_type(&self) -> &str {
+    "bitbucket"
+  }
+
+  fn required_config(&self) -> Vec<&str> {
+    vec![] // Credentials come from BITBUCKET_ACCESS_TOKEN or BITBUCKET_APP_PASSWORD
+  }
+
+  fn optional_config(&self) -> Vec<&str> {
+    vec!["base_url", "username", "access_token_env", "app_password_env"]
+  }
+
+  fn secret_name_rules(&self, _kind: &str) -> SecretNameRules {
+    SecretNameRules {
+      extra_chars: Some("_"),
+      uppercase: false,
+      no_leading_digit: true,
+      reserved_prefixes: &["BITBUCKET_"],
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use tokio::io::{ AsyncReadExt, AsyncWriteExt };
+  use tokio::net::TcpListener;
+
+  #[test]
+  fn test_parse_target_names() {
+    assert_eq!(parse_repository("team/app").unwrap(), ("team", "app"));
+    assert!(parse_repository("team").is_err());
+    assert!(parse_repository("team/app/extra").is_err());
+
+    assert_eq!(parse_deployment("team/app/Production").unwrap(), ("team", "app", "Production"));
+    assert!(parse_deployment("team/app").is_err());
+  }
+
+  #[test]
+  fn test_resolve_auth() {
+    unsafe {
+      std::env::set_var("CCT_TEST_BITBUCKET_PASSWORD", "app-password");
+      std::env::set_var("CCT_TEST_BITBUCKET_TOKEN", "token");
+    }
+
+    let mut config = HashMap::new();
+    config.insert("access_token_env".to_string(), "CCT_TEST_BITBUCKET_UNSET".to_string());
+    config.insert("app_password_env".to_string(), "CCT_TEST_BITBUCKET_PASSWORD".to_string());
+    config.insert("username".to_string(), "bot".to_string());
+    assert_eq!(BitbucketAuth::resolve(&config).unwrap(), BitbucketAuth::Basic {
+      username: "bot".to_string(),
+      password: "app-password".to_string(),
+    });
+
+    config.insert("access_token_env".to_string(), "CCT_TEST_BITBUCKET_TOKEN".to_string());
+    let auth = BitbucketAuth::resolve(&config).unwrap();
+    assert_eq!(auth, BitbucketAuth::Bearer("token".to_string()));
+  }
+
+  #[test]
+  fn test_error_message() {
+    let body = r#"{"type": "error", "error": {"message": "Repository not found"}}"#;
+    assert_eq!(bitbucket_error(body), "Repository not found");
+    assert_eq!(bitbucket_error("Bad Gateway\n"), "Bad Gateway");
+  }
+
+  /// Answer one request per connection with the given JSON bodies and return the raw requests
+  async fn serve(
+    listener: TcpListener,
+    responses: Vec<(&'static str, &'static str)>
+  ) -> Vec<String> {
+    let mut requests = Vec::new();
+
+    for (status, body) in responses {
+      let (mut socket, _) = listener.accept().await.unwrap();
+      let mut request = Vec::new();
+      let mut buf = [0u8; 4096];
+      loop {
+        let n = socket.read(&mut buf).await.unwrap();
+        request.extend_from_slice(&buf[..n]);
+        let text = String::from_utf8_lossy(&request).to_lowercase();
+        if let Some(header_end) = text.find("\r\n\r\n") {
+          let length = text
+            .lines()
+            .find_map(|l| l.strip_prefix("content-length:"))
+            .and_then(|v| v.trim().parse::<usize>().ok())
+            .unwrap_or(0);
+          if request.len() >= header_end + 4 + length {
+            break;
+          }
+        }
+        if n == 0 {
+          break;
+        }
+      }
+
+      let response = format!(
+        "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+      );
+      socket.write_all(response.as_bytes()).await.unwrap();
+      requests.push(String::from_utf8_lossy(&request).to_string());
+    }
+
+    requests
+  }
+
+  #[tokio::test]
+  async fn test_sync_repository_variables() {
+    unsafe {
+      std::env::set_var("CCT_TEST_BITBUCKET_SYNC_TOKEN", "sync-token");
+    }
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}/2.0", listener.local_addr().unwrap());
+    let server = tokio::spawn(
+      serve(listener, vec![
+        ("200 OK", r#"{"values": [{"key": "CLAUDE_ACCESS_TOKEN", "uuid": "{abc}"}]}"#),
+        ("200 OK", "{}"),
+        ("201 Created", "{}")
+      ])
+    );
+
+    let mut config = HashMap::new();
+    config.insert("base_url".to_string(), base_url);
+    config.insert("access_token_env".to_string(), "CCT_TEST_BITBUCKET_SYNC_TOKEN".to_string());
+    let provider = BitbucketProvider::new(config).unwrap();
+
+    let secrets = vec![
+      Secret {
+        name: "CLAUDE_ACCESS_TOKEN".to_string(),
+        value: "sk-test".to_string(),
+        description: None,
+      },
+      Secret {
+        name: "CLAUDE_REFRESH_TOKEN".to_string(),
+        value: "rt-test".to_string(),
+        description: None,
+      }
+    ];
+    let target = Target {
+      provider: "bitbucket".to_string(),
+      target_type: "repository".to_string(),
+      name: "team/app".to_string(),
+      config: HashMap::new(),
+    };
+
+    let result = provider.sync_secrets(&secrets, &[target]).await.unwrap();
+    assert_eq!(result.succeeded, 2, "{:?}", result.errors);
+
+    let requests = server.await.unwrap();
+    let variables = "/2.0/repositories/team/app/pipelines_config/variables";
+    assert!(requests[0].starts_with(&format!("GET {}?pagelen=100", variables)));
+    assert!(requests[0].to_lowercase().contains("authorization: bearer sync-token"));
+    assert!(requests[1].starts_with(&format!("PUT {}/%7Babc%7D", variables)));
+    assert!(requests[2].starts_with(&format!("POST {}", variables)));
+    assert!(requests[2].contains(r#""secured":true"#));
+  }
+}
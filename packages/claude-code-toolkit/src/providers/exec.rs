@@ -0,0 +1,456 @@
+//! Exec provider for out-of-tree providers implemented as external executables
+//!
+//! Each [`SecretProvider`] call spawns the executable and exchanges one JSON line in
+//! each direction over stdin/stdout:
+//!
+//! ```text
+//! -> {"method":"sync_secrets","config":{...},"params":{"secrets":[...],"targets":[...]}}
+//! <- {"result":{"succeeded":2,"failed":0,"errors":[]}}
+//! ```
+//!
+//! | Method            | Params                     | Result                           |
+//! |-------------------|----------------------------|----------------------------------|
+//! | `provider_name`   | `{}`                       | `"name"`                         |
+//! | `is_configured`   | `{}`                       | `true`                           |
+//! | `list_targets`    | `{"target_type":"..."}`    | `["name", ...]`                  |
+//! | `validate_access` | `{"targets":[...]}`        | `{"type:name": true, ...}`       |
+//! | `sync_secrets`    | `{"secrets":[..],"targets":[..]}` | `{"succeeded":..,"failed":..,"errors":[..]}` |
+//! | `delete_secrets`  | `{"secret_names":[..],"targets":[..]}` | same as `sync_secrets` |
+//!
+//! Failures are reported as `{"error":"message"}`. Secret values are only ever sent
+//! over stdin, never as command-line arguments.
+//!
+//! Executables placed in the plugins directory (`plugins/` next to the configuration file)
+//! are registered with [`ProviderFactory`](super::ProviderFactory) under their file
+//! name, with an optional `claude-code-provider-` prefix stripped.
+
+use super::{ BaseProvider, ProviderCreator };
+use crate::error::{ ClaudeCodeError, Result };
+use crate::traits::{ Secret, SecretProvider, SyncResult, Target };
+use async_trait::async_trait;
+use serde::{ Deserialize, Serialize };
+use std::collections::HashMap;
+use std::path::{ Path, PathBuf };
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::{ AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader };
+use tokio::process::Command;
+use tracing::{ debug, warn };
+
+/// File name prefix stripped from discovered plugin executables
+pub const PLUGIN_PREFIX: &str = "claude-code-provider-";
+
+const DEFAULT_TIMEOUT_SECS: u64 = 60;
+
+/// Default plugins directory
+pub fn plugins_dir() -> Option<PathBuf> {
+  Some(crate::utils::paths::get().plugins_dir())
+}
+
+/// Find plugin executables in a directory, returning `(provider name, path)` pairs
+pub fn discover_plugins(dir: &Path) -> Vec<(String, PathBuf)> {
+  let Ok(entries) = std::fs::read_dir(dir) else {
+    return Vec::new();
+  };
+
+  let mut plugins: Vec<(String, PathBuf)> = entries
+    .filter_map(|entry| entry.ok())
+    .map(|entry| entry.path())
+    .filter(|path| is_executable(path))
+    .filter_map(|path| {
+      let stem = path.file_stem()?.to_str()?;
+      let name = stem.strip_prefix(PLUGIN_PREFIX).unwrap_or(stem);
+      if name.is_empty() || name.starts_with('.') {
+        return None;
+      }
+      Some((name.to_string(), path.clone()))
+    })
+    .collect();
+
+  plugins.sort();
+  plugins
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+  use std::os::unix::fs::PermissionsExt;
+  path
+    .metadata()
+    .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+    .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+  path.is_file()
+}
+
+#[derive(Serialize)]
+struct Request<'a> {
+  method: &'a str,
+  config: &'a HashMap<String, String>,
+  params: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct Response {
+  #[serde(default)]
+  result: Option<serde_json::Value>,
+  #[serde(default)]
+  error: Option<String>,
+}
+
+/// Provider backed by an external executable speaking the JSON-lines protocol
+pub struct ExecProvider {
+  name: String,
+  program: PathBuf,
+  base: BaseProvider,
+}
+
+impl ExecProvider {
+  /// Create a provider for `program`, exposed under `name`
+  pub fn new(name: &str, program: PathBuf, config: HashMap<String, String>) -> Self {
+    Self {
+      name: name.to_string(),
+      program,
+      base: BaseProvider::new(name, config),
+    }
+  }
+
+  fn timeout(&self) -> Duration {
+    let secs = self.base
+      .get_config("timeout")
+      .and_then(|t| t.parse().ok())
+      .unwrap_or(DEFAULT_TIMEOUT_SECS);
+    Duration::from_secs(secs)
+  }
+
+  /// Send a single request to the executable and return its `result`
+  async fn call<T: serde::de::DeserializeOwned>(
+    &self,
+    method: &str,
+    params: serde_json::Value
+  ) -> Result<T> {
+    let request = Request {
+      method,
+      config: &self.base.config,
+      params,
+    };
+    let mut line = serde_json::to_vec(&request)?;
+    line.push(b'\n');
+
+    debug!("Calling {} on plugin {}", method, self.program.display());
+
+    // The limit covers the plugin's whole run, not just the reply
+    let deadline = tokio::time::Instant::now() + self.timeout();
+    let mut child = Command::new(&self.program)
+      .stdin(Stdio::piped())
+      .stdout(Stdio::piped())
+      .stderr(Stdio::piped())
+      .kill_on_drop(true)
+      .spawn()
+      .map_err(|e| {
+        ClaudeCodeError::Process(
+          format!("Failed to start plugin {}: {}", self.program.display(), e)
+        )
+      })?;
+
+    let mut stdin = child.stdin.take().ok_or("Plugin stdin unavailable")?;
+    let stdout = child.stdout.take().ok_or("Plugin stdout unavailable")?;
+    let mut stderr = child.stderr.take().ok_or("Plugin stderr unavailable")?;
+
+    // Drained while the plugin runs, so that it cannot block on a full pipe
+    let stderr_task = tokio::spawn(async move {
+      let mut output = Vec::new();
+      let _ = stderr.read_to_end(&mut output).await;
+      output
+    });
+
+    let run = async {
+      stdin.write_all(&line).await?;
+      drop(stdin);
+
+      let mut response = String::new();
+      BufReader::new(stdout).read_line(&mut response).await?;
+      let status = child.wait().await?;
+      Ok::<_, std::io::Error>((response, status))
+    };
+
+    let (response, status) = match tokio::time::timeout_at(deadline, run).await {
+      Ok(result) => result?,
+      Err(_) => {
+        // Kill and reap, so that neither the plugin nor a zombie outlives the call
+        if let Err(e) = child.kill().await {
+          warn!("Could not kill plugin {}: {}", self.name, e);
+        }
+        stderr_task.abort();
+        return Err(
+          ClaudeCodeError::Provider(format!("Plugin {} timed out on {}", self.name, method))
+        );
+      }
+    };
+
+    // Children the plugin left behind may hold stderr open
+    match tokio::time::timeout_at(deadline, stderr_task).await {
+      Ok(Ok(output)) if !output.is_empty() => {
+        debug!("Plugin {} stderr: {}", self.name, String::from_utf8_lossy(&output).trim());
+      }
+      Ok(_) => {}
+      Err(_) => debug!("Plugin {} left its stderr open", self.name),
+    }
+
+    if response.trim().is_empty() {
+      return Err(
+        ClaudeCodeError::Provider(
+          format!("Plugin {} returned no response to {} ({})", self.name, method, status)
+        )
+      );
+    }
+
+    let response: Response = serde_json
+      ::from_str(response.trim())
+      .map_err(|e| {
+        ClaudeCodeError::Provider(format!("Plugin {} sent an invalid response: {}", self.name, e))
+      })?;
+
+    if let Some(error) = response.error {
+      return Err(ClaudeCodeError::Provider(format!("{}: {}", self.name, error)));
+    }
+
+    let result = response.result.unwrap_or(serde_json::Value::Null);
+    serde_json
+      ::from_value(result)
+      .map_err(|e| {
+        ClaudeCodeError::Provider(
+          format!("Plugin {} returned an unexpected result: {}", self.name, e)
+        )
+      })
+  }
+
+  fn own_targets<'a>(&self, targets: &'a [Target]) -> Vec<&'a Target> {
+    targets
+      .iter()
+      .filter(|t| t.provider == self.name)
+      .collect()
+  }
+}
+
+#[async_trait]
+impl SecretProvider for ExecProvider {
+  fn provider_name(&self) -> &str {
+    &self.name
+  }
+
+  async fn sync_secrets(&self, secrets: &[Secret], targets: &[Target]) -> Result<SyncResult> {
+    let targets = self.own_targets(targets);
+    if targets.is_empty() {
+      return Ok(SyncResult {
+        succeeded: 0,
+        failed: 0,
+        errors: Vec::new(),
+      });
+    }
+
+    self.call("sync_secrets", serde_json::json!({ "secrets": secrets, "targets": targets })).await
+  }
+
+  async fn delete_secrets(
+    &self,
+    secret_names: &[String],
+    targets: &[Target]
+  ) -> Result<SyncResult> {
+    let targets = self.own_targets(targets);
+    if targets.is_empty() {
+      return Ok(SyncResult {
+        succeeded: 0,
+        failed: 0,
+        errors: Vec::new(),
+      });
+    }
+
+    let params = serde_json::json!({ "secret_names": secret_names, "targets": targets });
+    self.call("delete_secrets", params).await
+  }
+
+  async fn validate_access(&self, targets: &[Target]) -> Result<HashMap<String, bool>> {
+    let targets = self.own_targets(targets);
+    if targets.is_empty() {
+      return Ok(HashMap::new());
+    }
+
+    self.call("validate_access", serde_json::json!({ "targets": targets })).await
+  }
+
+  async fn list_targets(&self, target_type: &str) -> Result<Vec<String>> {
+    self.call("list_targets", serde_json::json!({ "target_type": target_type })).await
+  }
+
+  async fn is_configured(&self) -> Result<bool> {
+    // Handshake first so a misplaced executable is not mistaken for a provider
+    let reported: String = match self.call("provider_name", serde_json::json!({})).await {
+      Ok(name) => name,
+      Err(e) => {
+        warn!("Plugin {} handshake failed: {}", self.name, e);
+        return Ok(false);
+      }
+    };
+
+    if reported != self.name && self.name != "exec" {
+      warn!("Plugin {} reports provider name '{}'", self.program.display(), reported);
+    }
+
+    self.call("is_configured", serde_json::json!({})).await
+  }
+}
+
+/// Creator for the generic `exec` provider, which runs the configured `command`
+pub struct ExecProviderCreator;
+
+impl ProviderCreator for ExecProviderCreator {
+  fn create(&self, config: &HashMap<String, String>) -> Result<Box<dyn SecretProvider>> {
+    let command = config
+      .get("command")
+      .ok_or_else(|| ClaudeCodeError::Generic("Missing required config: command".to_string()))?;
+    let program = PathBuf::from(shellexpand::tilde(command).as_ref());
+    Ok(Box::new(ExecProvider::new("exec", program, config.clone())))
+  }
+
+  fn provider_type(&self) -> &str {
+    "exec"
+  }
+
+  fn required_config(&self) -> Vec<&str> {
+    vec!["command"]
+  }
+
+  fn optional_config(&self) -> Vec<&str> {
+    vec!["timeout"]
+  }
+}
+
+/// Creator for a plugin executable discovered in the plugins directory
+pub struct PluginProviderCreator {
+  name: String,
+  program: PathBuf,
+}
+
+impl PluginProviderCreator {
+  pub fn new(name: &str, program: PathBuf) -> Self {
+    Self {
+      name: name.to_string(),
+      program,
+    }
+  }
+}
+
+impl ProviderCreator for PluginProviderCreator {
+  fn create(&self, config: &HashMap<String, String>) -> Result<Box<dyn SecretProvider>> {
+    Ok(Box::new(ExecProvider::new(&self.name, self.program.clone(), config.clone())))
+  }
+
+  fn provider_type(&self) -> &str {
+    &self.name
+  }
+
+  fn required_config(&self) -> Vec<&str> {
+    vec![]
+  }
+
+  fn optional_config(&self) -> Vec<&str> {
+    vec!["timeout"]
+  }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+  use super::*;
+  use std::os::unix::fs::PermissionsExt;
+  use tempfile::TempDir;
+
+  const PLUGIN: &str =
+    r#"#!/bin/sh
+read -r line
+case "$line" in
+  *'"method":"provider_name"'*) echo '{"result":"broker"}' ;;
+  *'"method":"is_configured"'*) echo '{"result":true}' ;;
+  *'"method":"list_targets"'*) echo '{"result":["team/claude"]}' ;;
+  *'"method":"sync_secrets"'*'"value":"sk-test"'*) echo '{"result":{"succeeded":1,"failed":0,"errors":[]}}' ;;
+  *) echo '{"error":"unsupported method"}' ;;
+esac
+"#;
+
+  fn write_plugin(dir: &Path, file_name: &str) -> PathBuf {
+    let path = dir.join(file_name);
+    std::fs::write(&path, PLUGIN).unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    path
+  }
+
+  #[test]
+  fn test_discover_plugins() {
+    let dir = TempDir::new().unwrap();
+    write_plugin(dir.path(), "claude-code-provider-broker");
+    std::fs::write(dir.path().join("README.md"), "not a plugin").unwrap();
+
+    let plugins = discover_plugins(dir.path());
+    assert_eq!(plugins.len(), 1);
+    assert_eq!(plugins[0].0, "broker");
+  }
+
+  #[tokio::test]
+  async fn test_plugin_round_trip() {
+    let dir = TempDir::new().unwrap();
+    let program = write_plugin(dir.path(), "broker");
+    let provider = ExecProvider::new("broker", program, HashMap::new());
+
+    assert!(provider.is_configured().await.unwrap());
+    assert_eq!(provider.list_targets("vault").await.unwrap(), vec!["team/claude".to_string()]);
+
+    let secrets = vec![Secret {
+      name: "CLAUDE_ACCESS_TOKEN".to_string(),
+      value: "sk-test".to_string(),
+      description: None,
+    }];
+    let targets = vec![Target {
+      provider: "broker".to_string(),
+      target_type: "vault".to_string(),
+      name: "team/claude".to_string(),
+      config: HashMap::new(),
+    }];
+    let result = provider.sync_secrets(&secrets, &targets).await.unwrap();
+    assert_eq!(result.succeeded, 1);
+
+    let error = provider.validate_access(&targets).await.unwrap_err();
+    assert!(error.to_string().contains("unsupported method"));
+  }
+
+  #[tokio::test]
+  async fn test_plugin_that_lingers_after_replying_times_out() {
+    let dir = TempDir::new().unwrap();
+    let program = dir.path().join("lingering");
+    // Replies, floods stderr past the pipe buffer, then never exits
+    let script =
+      "#!/bin/sh\nread -r line\necho '{\"result\":true}'\n\
+       head -c 200000 /dev/zero >&2\nexec sleep 30\n";
+    std::fs::write(&program, script).unwrap();
+    std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let mut config = HashMap::new();
+    config.insert("timeout".to_string(), "1".to_string());
+    let provider = ExecProvider::new("lingering", program, config);
+
+    let started = std::time::Instant::now();
+    let error = provider.call::<bool>("is_configured", serde_json::json!({})).await.unwrap_err();
+    assert!(error.to_string().contains("timed out"), "{}", error);
+    assert!(started.elapsed() < Duration::from_secs(10));
+
+    // A plugin that floods stderr before replying still gets its answer through
+    let chatty = dir.path().join("chatty");
+    let script =
+      "#!/bin/sh\nhead -c 200000 /dev/zero >&2\nread -r line\necho '{\"result\":true}'\n";
+    std::fs::write(&chatty, script).unwrap();
+    std::fs::set_permissions(&chatty, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let provider = ExecProvider::new("chatty", chatty, HashMap::new());
+    assert!(provider.call::<bool>("is_configured", serde_json::json!({})).await.unwrap());
+  }
+}
@@ -1,6 +1,6 @@
 //! GitHub provider implementation following Repository Pattern
 
-use super::{ BaseProvider, ProviderCreator };
+use super::{ BaseProvider, ProviderCreator, SecretNameRules };
 use crate::error::{ ClaudeCodeError, Result };
 use crate::traits::{ Secret, SecretProvider, SyncResult, Target };
 use async_trait::async_trait;
@@ -122,6 +122,31 @@ impl GitHubProvider {
     }
   }
 
+  async fn delete_secret(&self, target: &Target, secret_name: &str) -> Result<()> {
+    let args = match target.target_type.as_str() {
+      "organization" => vec!["secret", "delete", secret_name, "--org", &target.name],
+      "repository" => vec!["secret", "delete", secret_name, "--repo", &target.name],
+      _ => {
+        return Err(
+          ClaudeCodeError::Generic(format!("Unsupported target type: {}", target.target_type))
+        );
+      }
+    };
+
+    info!("Deleting secret {} from {} {}", secret_name, target.target_type, target.name);
+
+    let output = self.execute_gh_command(&args).await?;
+    let error_msg = String::from_utf8_lossy(&output.stderr);
+
+    // A secret that is already gone counts as deleted
+    if output.status.success() || is_not_found(&error_msg) {
+      Ok(())
+    } else {
+      error!("Failed to delete secret for {} {}: {}", target.target_type, target.name, error_msg);
+      Err(ClaudeCodeError::Process(format!("Failed to delete secret: {}", error_msg)))
+    }
+  }
+
   async fn check_target_access(&self, target: &Target) -> Result<bool> {
     let (api_path, args) = match target.target_type.as_str() {
       "organization" => {
@@ -193,6 +218,36 @@ impl SecretProvider for GitHubProvider {
     })
   }
 
+  async fn delete_secrets(
+    &self,
+    secret_names: &[String],
+    targets: &[Target]
+  ) -> Result<SyncResult> {
+    let mut succeeded = 0;
+    let mut failed = 0;
+    let mut errors = Vec::new();
+
+    for target in targets.iter().filter(|t| t.provider == self.provider_name()) {
+      for secret_name in secret_names {
+        match self.delete_secret(target, secret_name).await {
+          Ok(()) => {
+            succeeded += 1;
+          }
+          Err(e) => {
+            failed += 1;
+            errors.push(format!("{}:{} - {}", target.target_type, target.name, e));
+          }
+        }
+      }
+    }
+
+    Ok(SyncResult {
+      succeeded,
+      failed,
+      errors,
+    })
+  }
+
   async fn validate_access(&self, targets: &[Target]) -> Result<HashMap<String, bool>> {
     let mut results = HashMap::new();
 
@@ -286,4 +341,36 @@ impl ProviderCreator for GitHubProviderCreator {
   fn optional_config(&self) -> Vec<&str> {
     vec!["api_endpoint", "timeout", "retry_count"]
   }
+
+  fn secret_name_rules(&self, _kind: &str) -> SecretNameRules {
+    SecretNameRules {
+      extra_chars: Some("_"),
+      uppercase: true,
+      no_leading_digit: true,
+      reserved_prefixes: &["GITHUB_"],
+    }
+  }
+}
+
+/// Whether gh failed because the API answered 404, e.g.
+/// `failed to delete secret X: HTTP 404: Not Found (https://api.github.com/...)`
+fn is_not_found(stderr: &str) -> bool {
+  stderr.contains("HTTP 404: Not Found")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_only_api_not_found_counts_as_deleted() {
+    assert!(
+      is_not_found(
+        "failed to delete secret CLAUDE_TOKEN: HTTP 404: Not Found \
+         (https://api.github.com/repos/owner/repo/actions/secrets/CLAUDE_TOKEN)"
+      )
+    );
+    assert!(!is_not_found("HTTP 403: Resource not accessible by integration (request 404A:1B)"));
+    assert!(!is_not_found("could not resolve host: proxy-404.internal"));
+  }
 }
@@ -0,0 +1,819 @@
+//! Freedesktop Secret Service (GNOME Keyring / KWallet) provider implementation
+//!
+//! Talks to `org.freedesktop.secrets` on the session bus using the `plain` session
+//! algorithm, so every secret becomes one item in the selected collection. Items are
+//! tagged with `application=claude-code-toolkit` and `secret=<name>` so other tools
+//! can look them up without reading `~/.claude/.credentials.json`.
+
+use super::ProviderCreator;
+use crate::error::{ ClaudeCodeError, Result };
+use crate::traits::{ Secret, SecretProvider, SyncResult, Target };
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{ debug, error, info, warn };
+use zbus::Connection;
+use zbus::zvariant::{ OwnedObjectPath, OwnedValue, Value };
+
+const BUS_NAME: &str = "org.freedesktop.secrets";
+const SERVICE_PATH: &str = "/org/freedesktop/secrets";
+const SERVICE_IFACE: &str = "org.freedesktop.Secret.Service";
+const COLLECTION_IFACE: &str = "org.freedesktop.Secret.Collection";
+const ITEM_IFACE: &str = "org.freedesktop.Secret.Item";
+const PROPERTIES_IFACE: &str = "org.freedesktop.DBus.Properties";
+
+/// Attribute identifying items created by this toolkit
+pub const APPLICATION_ATTRIBUTE: &str = "application";
+pub const APPLICATION_NAME: &str = "claude-code-toolkit";
+
+/// Prefix for per-target attribute options in [`Target::config`]
+pub const ATTRIBUTE_PREFIX: &str = "attribute.";
+
+fn zbus_error(e: zbus::Error) -> ClaudeCodeError {
+  ClaudeCodeError::Provider(format!("Secret Service error: {}", e))
+}
+
+/// Build the lookup attributes for a secret item
+fn item_attributes(target: &Target, secret_name: &str) -> HashMap<String, String> {
+  let mut attributes: HashMap<String, String> = target.config
+    .iter()
+    .filter_map(|(key, value)| {
+      key.strip_prefix(ATTRIBUTE_PREFIX).map(|k| (k.to_string(), value.clone()))
+    })
+    .collect();
+
+  attributes.insert(APPLICATION_ATTRIBUTE.to_string(), APPLICATION_NAME.to_string());
+  attributes.insert("secret".to_string(), secret_name.to_string());
+  attributes
+}
+
+/// Build the human readable label for a secret item
+fn item_label(target: &Target, secret_name: &str) -> String {
+  let prefix = target.config.get("label").map(|s| s.as_str()).unwrap_or("Claude Code");
+  format!("{}: {}", prefix, secret_name)
+}
+
+/// Secret Service calls the provider makes, so that they can be faked in tests
+///
+/// Object paths are passed as strings; `"/"` stands for "none", as on the bus.
+#[async_trait]
+pub trait SecretService: Send + Sync {
+  /// Open a `plain` transfer session
+  async fn open_session(&self) -> Result<String>;
+  async fn close_session(&self, session: &str) -> Result<()>;
+  /// Collection behind an alias such as `default`
+  async fn read_alias(&self, name: &str) -> Result<String>;
+  async fn collections(&self) -> Result<Vec<String>>;
+  async fn collection_label(&self, collection: &str) -> Result<String>;
+  async fn collection_locked(&self, collection: &str) -> Result<bool>;
+  /// Store an item, replacing one with the same attributes; returns the prompt path
+  async fn create_item(
+    &self,
+    collection: &str,
+    session: &str,
+    label: &str,
+    attributes: HashMap<String, String>,
+    value: &[u8]
+  ) -> Result<String>;
+  async fn search_items(
+    &self,
+    collection: &str,
+    attributes: HashMap<String, String>
+  ) -> Result<Vec<String>>;
+  /// Delete an item, returning the prompt path
+  async fn delete_item(&self, item: &str) -> Result<String>;
+}
+
+fn object_path(path: &str) -> Result<OwnedObjectPath> {
+  OwnedObjectPath::try_from(path).map_err(|e| zbus_error(e.into()))
+}
+
+/// [`SecretService`] on the D-Bus session bus
+pub struct DbusSecretService {
+  conn: Connection,
+}
+
+impl DbusSecretService {
+  pub async fn connect() -> Result<Self> {
+    Ok(Self { conn: Connection::session().await.map_err(zbus_error)? })
+  }
+
+  async fn call<B, R>(&self, path: &str, interface: &str, method: &str, body: &B) -> Result<R>
+    where
+      B: serde::Serialize + zbus::zvariant::DynamicType,
+      R: for<'d> serde::Deserialize<'d> + zbus::zvariant::Type
+  {
+    let reply = self.conn
+      .call_method(Some(BUS_NAME), path, Some(interface), method, body).await
+      .map_err(zbus_error)?;
+    reply.body().deserialize::<R>().map_err(zbus_error)
+  }
+
+  async fn get_property<T>(&self, path: &str, interface: &str, name: &str) -> Result<T>
+    where T: TryFrom<OwnedValue, Error = zbus::zvariant::Error>
+  {
+    let value: OwnedValue = self.call(path, PROPERTIES_IFACE, "Get", &(interface, name)).await?;
+    T::try_from(value).map_err(|e| zbus_error(e.into()))
+  }
+}
+
+#[async_trait]
+impl SecretService for DbusSecretService {
+  async fn open_session(&self) -> Result<String> {
+    let (_output, session): (OwnedValue, OwnedObjectPath) = self.call(
+      SERVICE_PATH,
+      SERVICE_IFACE,
+      "OpenSession",
+      &("plain", Value::from(""))
+    ).await?;
+    Ok(session.to_string())
+  }
+
+  async fn close_session(&self, session: &str) -> Result<()> {
+    self.call::<_, ()>(session, "org.freedesktop.Secret.Session", "Close", &()).await
+  }
+
+  async fn read_alias(&self, name: &str) -> Result<String> {
+    let path: OwnedObjectPath = self.call(SERVICE_PATH, SERVICE_IFACE, "ReadAlias", &name).await?;
+    Ok(path.to_string())
+  }
+
+  async fn collections(&self) -> Result<Vec<String>> {
+    let paths: Vec<OwnedObjectPath> = self.get_property(
+      SERVICE_PATH,
+      SERVICE_IFACE,
+      "Collections"
+    ).await?;
+    Ok(
+      paths
+        .iter()
+        .map(|p| p.to_string())
+        .collect()
+    )
+  }
+
+  async fn collection_label(&self, collection: &str) -> Result<String> {
+    self.get_property(collection, COLLECTION_IFACE, "Label").await
+  }
+
+  async fn collection_locked(&self, collection: &str) -> Result<bool> {
+    self.get_property(collection, COLLECTION_IFACE, "Locked").await
+  }
+
+  async fn create_item(
+    &self,
+    collection: &str,
+    session: &str,
+    label: &str,
+    attributes: HashMap<String, String>,
+    value: &[u8]
+  ) -> Result<String> {
+    let mut properties: HashMap<&str, Value> = HashMap::new();
+    properties.insert("org.freedesktop.Secret.Item.Label", Value::from(label));
+    properties.insert("org.freedesktop.Secret.Item.Attributes", Value::from(attributes));
+    let secret = (object_path(session)?, Vec::<u8>::new(), value.to_vec(), "text/plain");
+
+    let (_item, prompt): (OwnedObjectPath, OwnedObjectPath) = self.call(
+      collection,
+      COLLECTION_IFACE,
+      "CreateItem",
+      &(properties, secret, true)
+    ).await?;
+    Ok(prompt.to_string())
+  }
+
+  async fn search_items(
+    &self,
+    collection: &str,
+    attributes: HashMap<String, String>
+  ) -> Result<Vec<String>> {
+    let items: Vec<OwnedObjectPath> = self.call(
+      collection,
+      COLLECTION_IFACE,
+      "SearchItems",
+      &attributes
+    ).await?;
+    Ok(
+      items
+        .iter()
+        .map(|p| p.to_string())
+        .collect()
+    )
+  }
+
+  async fn delete_item(&self, item: &str) -> Result<String> {
+    let prompt: OwnedObjectPath = self.call(item, ITEM_IFACE, "Delete", &()).await?;
+    Ok(prompt.to_string())
+  }
+}
+
+/// Secret Service keyring provider
+///
+/// Target type `collection`: the target name is a collection alias (`default`,
+/// `login`, `session`) or a collection label.
+pub struct KeyringProvider {
+  /// Service to use instead of connecting to the session bus
+  service: Option<Arc<dyn SecretService>>,
+}
+
+impl KeyringProvider {
+  pub fn new(_config: HashMap<String, String>) -> Result<Self> {
+    Ok(Self { service: None })
+  }
+
+  /// Provider talking to `service` rather than the session bus
+  pub fn with_service(service: Arc<dyn SecretService>) -> Self {
+    Self { service: Some(service) }
+  }
+
+  async fn connect(&self) -> Result<Arc<dyn SecretService>> {
+    match &self.service {
+      Some(service) => Ok(service.clone()),
+      None => Ok(Arc::new(DbusSecretService::connect().await?)),
+    }
+  }
+
+  /// Resolve a collection alias or label to its object path
+  async fn find_collection(&self, service: &dyn SecretService, name: &str) -> Result<String> {
+    let alias = service.read_alias(name).await?;
+    if alias != "/" {
+      return Ok(alias);
+    }
+
+    for path in service.collections().await? {
+      if service.collection_label(&path).await? == name {
+        return Ok(path);
+      }
+    }
+
+    Err(ClaudeCodeError::TargetNotFound {
+      target_type: "collection".to_string(),
+      name: name.to_string(),
+    })
+  }
+
+  /// Fail early with a clear message if the collection is locked
+  async fn ensure_unlocked(&self, service: &dyn SecretService, collection: &str) -> Result<()> {
+    if service.collection_locked(collection).await? {
+      return Err(
+        ClaudeCodeError::Provider(
+          format!("Keyring collection {} is locked; unlock it and retry", collection)
+        )
+      );
+    }
+
+    Ok(())
+  }
+
+  async fn store_secret(
+    &self,
+    service: &dyn SecretService,
+    session: &str,
+    collection: &str,
+    target: &Target,
+    secret: &Secret
+  ) -> Result<()> {
+    let prompt = service.create_item(
+      collection,
+      session,
+      &item_label(target, &secret.name),
+      item_attributes(target, &secret.name),
+      secret.value.as_bytes()
+    ).await?;
+
+    if prompt != "/" {
+      return Err(
+        ClaudeCodeError::Provider(
+          format!("Keyring requested an interactive prompt to store {}", secret.name)
+        )
+      );
+    }
+
+    Ok(())
+  }
+
+  /// Delete the items this toolkit stored for `secret_name` in a collection
+  async fn delete_items(
+    &self,
+    service: &dyn SecretService,
+    collection: &str,
+    target: &Target,
+    secret_name: &str
+  ) -> Result<()> {
+    let items = service.search_items(collection, item_attributes(target, secret_name)).await?;
+
+    for item in items {
+      if service.delete_item(&item).await? != "/" {
+        return Err(
+          ClaudeCodeError::Provider(
+            format!("Keyring requested an interactive prompt to delete {}", secret_name)
+          )
+        );
+      }
+    }
+
+    Ok(())
+  }
+
+  async fn sync_collection(
+    &self,
+    service: &dyn SecretService,
+    target: &Target,
+    secrets: &[Secret],
+    result: &mut SyncResult
+  ) {
+    let prepared = async {
+      let collection = self.find_collection(service, &target.name).await?;
+      self.ensure_unlocked(service, &collection).await?;
+      let session = service.open_session().await?;
+      Ok::<_, ClaudeCodeError>((collection, session))
+    }.await;
+
+    let (collection, session) = match prepared {
+      Ok(prepared) => prepared,
+      Err(e) => {
+        error!("Failed to open keyring collection {}: {}", target.name, e);
+        result.failed += secrets.len();
+        result.errors.push(format!("{}:{} - {}", target.target_type, target.name, e));
+        return;
+      }
+    };
+
+    for secret in secrets {
+      match self.store_secret(service, &session, &collection, target, secret).await {
+        Ok(()) => {
+          debug!("Stored {} in keyring collection {}", secret.name, target.name);
+          result.succeeded += 1;
+        }
+        Err(e) => {
+          error!("Failed to store {} in keyring: {}", secret.name, e);
+          result.failed += 1;
+          result.errors.push(format!("{}:{} - {}", target.target_type, target.name, e));
+        }
+      }
+    }
+
+    // Sessions are cheap, but close them so the daemon doesn't leak one per sync
+    let _ = service.close_session(&session).await;
+  }
+}
+
+#[async_trait]
+impl SecretProvider for KeyringProvider {
+  fn provider_name(&self) -> &str {
+    "keyring"
+  }
+
+  async fn sync_secrets(&self, secrets: &[Secret], targets: &[Target]) -> Result<SyncResult> {
+    let mut result = SyncResult {
+      succeeded: 0,
+      failed: 0,
+      errors: Vec::new(),
+    };
+
+    let targets: Vec<&Target> = targets
+      .iter()
+      .filter(|t| t.provider == self.provider_name())
+      .collect();
+    if targets.is_empty() {
+      return Ok(result);
+    }
+
+    let service = self.connect().await?;
+    for target in targets {
+      if target.target_type != "collection" {
+        warn!("Unsupported keyring target type: {}", target.target_type);
+        result.failed += 1;
+        result.errors.push(
+          format!("{}:{} - unsupported target type", target.target_type, target.name)
+        );
+        continue;
+      }
+
+      self.sync_collection(service.as_ref(), target, secrets, &mut result).await;
+    }
+
+    info!("Keyring sync: {} succeeded, {} failed", result.succeeded, result.failed);
+    Ok(result)
+  }
+
+  async fn delete_secrets(
+    &self,
+    secret_names: &[String],
+    targets: &[Target]
+  ) -> Result<SyncResult> {
+    let mut result = SyncResult {
+      succeeded: 0,
+      failed: 0,
+      errors: Vec::new(),
+    };
+
+    let targets: Vec<&Target> = targets
+      .iter()
+      .filter(|t| t.provider == self.provider_name())
+      .collect();
+    if targets.is_empty() {
+      return Ok(result);
+    }
+
+    let service = self.connect().await?;
+    for target in targets {
+      let prepared = async {
+        let collection = self.find_collection(service.as_ref(), &target.name).await?;
+        self.ensure_unlocked(service.as_ref(), &collection).await?;
+        Ok::<_, ClaudeCodeError>(collection)
+      }.await;
+
+      let collection = match prepared {
+        Ok(collection) => collection,
+        Err(e) => {
+          result.failed += secret_names.len();
+          result.errors.push(format!("{}:{} - {}", target.target_type, target.name, e));
+          continue;
+        }
+      };
+
+      for secret_name in secret_names {
+        match self.delete_items(service.as_ref(), &collection, target, secret_name).await {
+          Ok(()) => {
+            debug!("Deleted {} from keyring collection {}", secret_name, target.name);
+            result.succeeded += 1;
+          }
+          Err(e) => {
+            error!("Failed to delete {} from keyring: {}", secret_name, e);
+            result.failed += 1;
+            result.errors.push(format!("{}:{} - {}", target.target_type, target.name, e));
+          }
+        }
+      }
+    }
+
+    Ok(result)
+  }
+
+  async fn validate_access(&self, targets: &[Target]) -> Result<HashMap<String, bool>> {
+    let mut results = HashMap::new();
+    let service = match self.connect().await {
+      Ok(service) => Some(service),
+      Err(e) => {
+        warn!("{}", e);
+        None
+      }
+    };
+
+    for target in targets {
+      if target.provider != self.provider_name() {
+        continue;
+      }
+
+      let key = format!("{}:{}", target.target_type, target.name);
+      let has_access = match &service {
+        Some(service) => self.find_collection(service.as_ref(), &target.name).await.is_ok(),
+        None => false,
+      };
+      results.insert(key, has_access);
+    }
+
+    Ok(results)
+  }
+
+  async fn list_targets(&self, target_type: &str) -> Result<Vec<String>> {
+    if target_type != "collection" {
+      return Err(ClaudeCodeError::Generic(format!("Unsupported target type: {}", target_type)));
+    }
+
+    let service = self.connect().await?;
+    let mut labels = Vec::new();
+    for path in service.collections().await? {
+      if let Ok(label) = service.collection_label(&path).await {
+        labels.push(label);
+      }
+    }
+
+    Ok(labels)
+  }
+
+  async fn is_configured(&self) -> Result<bool> {
+    match self.connect().await {
+      Ok(service) => Ok(service.open_session().await.is_ok()),
+      Err(e) => {
+        warn!("{}", e);
+        Ok(false)
+      }
+    }
+  }
+}
+
+/// Keyring provider creator for Factory Pattern
+pub struct KeyringProviderCreator;
+
+impl ProviderCreator for KeyringProviderCreator {
+  fn create(&self, config: &HashMap<String, String>) -> Result<Box<dyn SecretProvider>> {
+    let provider = KeyringProvider::new(config.clone())?;
+    Ok(Box::new(provider))
+  }
+
+  fn provider_type(&self) -> &str {
+    "keyring"
+  }
+
+  fn required_config(&self) -> Vec<&str> {
+    vec![] // Uses the Secret Service on the session bus
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn collection_target() -> Target {
+    let mut config = HashMap::new();
+    config.insert("label".to_string(), "Team Claude".to_string());
+    config.insert("attribute.service".to_string(), "claude".to_string());
+    Target {
+      provider: "keyring".to_string(),
+      target_type: "collection".to_string(),
+      name: "default".to_string(),
+      config,
+    }
+  }
+
+  #[test]
+  fn test_item_attributes() {
+    let attributes = item_attributes(&collection_target(), "CLAUDE_ACCESS_TOKEN");
+
+    assert_eq!(attributes["application"], "claude-code-toolkit");
+    assert_eq!(attributes["secret"], "CLAUDE_ACCESS_TOKEN");
+    assert_eq!(attributes["service"], "claude");
+    assert!(!attributes.contains_key("label"));
+  }
+
+  #[test]
+  fn test_item_label() {
+    assert_eq!(
+      item_label(&collection_target(), "CLAUDE_ACCESS_TOKEN"),
+      "Team Claude: CLAUDE_ACCESS_TOKEN"
+    );
+
+    let mut target = collection_target();
+    target.config.clear();
+    assert_eq!(item_label(&target, "CLAUDE_ACCESS_TOKEN"), "Claude Code: CLAUDE_ACCESS_TOKEN");
+  }
+
+  const LOGIN: &str = "/org/freedesktop/secrets/collection/login";
+
+  /// Path, label, attributes and value of a stored item
+  type Item = (String, String, HashMap<String, String>, Vec<u8>);
+
+  /// In-memory [`SecretService`] with a `Login` collection behind the `default` alias
+  #[derive(Default)]
+  struct FakeService {
+    locked: bool,
+    items: std::sync::Mutex<Vec<Item>>,
+  }
+
+  impl FakeService {
+    fn with_items(items: Vec<Item>) -> Self {
+      Self { locked: false, items: std::sync::Mutex::new(items) }
+    }
+
+    fn values(&self) -> Vec<(String, Vec<u8>)> {
+      let mut values: Vec<(String, Vec<u8>)> = self.items
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(_, label, _, value)| (label.clone(), value.clone()))
+        .collect();
+      values.sort();
+      values
+    }
+  }
+
+  #[async_trait]
+  impl SecretService for FakeService {
+    async fn open_session(&self) -> Result<String> {
+      Ok("/org/freedesktop/secrets/session/1".to_string())
+    }
+
+    async fn close_session(&self, _session: &str) -> Result<()> {
+      Ok(())
+    }
+
+    async fn read_alias(&self, name: &str) -> Result<String> {
+      Ok((if name == "default" { LOGIN } else { "/" }).to_string())
+    }
+
+    async fn collections(&self) -> Result<Vec<String>> {
+      Ok(vec![LOGIN.to_string()])
+    }
+
+    async fn collection_label(&self, _collection: &str) -> Result<String> {
+      Ok("Login".to_string())
+    }
+
+    async fn collection_locked(&self, _collection: &str) -> Result<bool> {
+      Ok(self.locked)
+    }
+
+    async fn create_item(
+      &self,
+      collection: &str,
+      _session: &str,
+      label: &str,
+      attributes: HashMap<String, String>,
+      value: &[u8]
+    ) -> Result<String> {
+      let mut items = self.items.lock().unwrap();
+      match items.iter_mut().find(|item| item.2 == attributes) {
+        Some(item) => {
+          item.1 = label.to_string();
+          item.3 = value.to_vec();
+        }
+        None => {
+          let path = format!("{}/{}", collection, items.len() + 1);
+          items.push((path, label.to_string(), attributes, value.to_vec()));
+        }
+      }
+      Ok("/".to_string())
+    }
+
+    async fn search_items(
+      &self,
+      _collection: &str,
+      attributes: HashMap<String, String>
+    ) -> Result<Vec<String>> {
+      Ok(
+        self.items
+          .lock()
+          .unwrap()
+          .iter()
+          .filter(|item| attributes.iter().all(|(k, v)| item.2.get(k) == Some(v)))
+          .map(|item| item.0.clone())
+          .collect()
+      )
+    }
+
+    async fn delete_item(&self, item: &str) -> Result<String> {
+      self.items.lock().unwrap().retain(|i| i.0 != item);
+      Ok("/".to_string())
+    }
+  }
+
+  #[tokio::test]
+  async fn test_sync_replaces_and_deletes_own_items() {
+    // An item of another application with the same secret name
+    let mut foreign = HashMap::new();
+    foreign.insert("secret".to_string(), "CLAUDE_ACCESS_TOKEN".to_string());
+    let item = (format!("{}/0", LOGIN), "Other".to_string(), foreign, b"x".to_vec());
+    let service = Arc::new(FakeService::with_items(vec![item]));
+    let provider = KeyringProvider::with_service(service.clone());
+
+    let secret = |name: &str, value: &str| Secret {
+      name: name.to_string(),
+      value: value.to_string(),
+      description: None,
+    };
+    let mut target = collection_target();
+    target.name = "Login".to_string();
+    let targets = [target];
+
+    let secrets = [secret("CLAUDE_ACCESS_TOKEN", "old"), secret("CLAUDE_REFRESH_TOKEN", "rt")];
+    let result = provider.sync_secrets(&secrets, &targets).await.unwrap();
+    assert_eq!(result.succeeded, 2, "{:?}", result.errors);
+
+    // A second sync replaces the items rather than adding more
+    let result = provider
+      .sync_secrets(&[secret("CLAUDE_ACCESS_TOKEN", "new")], &targets).await
+      .unwrap();
+    assert_eq!(result.succeeded, 1);
+    assert_eq!(service.values(), vec![
+      ("Other".to_string(), b"x".to_vec()),
+      ("Team Claude: CLAUDE_ACCESS_TOKEN".to_string(), b"new".to_vec()),
+      ("Team Claude: CLAUDE_REFRESH_TOKEN".to_string(), b"rt".to_vec())
+    ]);
+
+    let names = vec!["CLAUDE_ACCESS_TOKEN".to_string()];
+    let result = provider.delete_secrets(&names, &targets).await.unwrap();
+    assert_eq!(result.succeeded, 1);
+    assert_eq!(service.values(), vec![
+      ("Other".to_string(), b"x".to_vec()),
+      ("Team Claude: CLAUDE_REFRESH_TOKEN".to_string(), b"rt".to_vec())
+    ]);
+
+    let locked = KeyringProvider::with_service(
+      Arc::new(FakeService { locked: true, ..FakeService::default() })
+    );
+    let result = locked.sync_secrets(&secrets, &targets).await.unwrap();
+    assert_eq!(result.failed, 2);
+    assert!(result.errors[0].contains("is locked"), "{:?}", result.errors);
+  }
+
+  /// Minimal in-process Secret Service used to exercise the D-Bus calls
+  mod mock {
+    use std::collections::HashMap;
+    use std::sync::{ Arc, Mutex };
+    use zbus::interface;
+    use zbus::zvariant::{ OwnedObjectPath, OwnedValue, Value };
+
+    pub type Items = Arc<Mutex<Vec<(String, HashMap<String, String>, Vec<u8>)>>>;
+
+    pub struct Service;
+
+    #[interface(name = "org.freedesktop.Secret.Service")]
+    impl Service {
+      fn open_session(
+        &self,
+        _algorithm: &str,
+        _input: Value<'_>
+      ) -> (OwnedValue, OwnedObjectPath) {
+        let session = OwnedObjectPath::try_from("/org/freedesktop/secrets/session/1").unwrap();
+        (OwnedValue::from(0u32), session)
+      }
+
+      fn read_alias(&self, name: &str) -> OwnedObjectPath {
+        let path = match name {
+          "default" => "/org/freedesktop/secrets/collection/login",
+          _ => "/",
+        };
+        OwnedObjectPath::try_from(path).unwrap()
+      }
+
+      #[zbus(property)]
+      fn collections(&self) -> Vec<OwnedObjectPath> {
+        vec![OwnedObjectPath::try_from("/org/freedesktop/secrets/collection/login").unwrap()]
+      }
+    }
+
+    pub struct Collection {
+      pub items: Items,
+    }
+
+    #[interface(name = "org.freedesktop.Secret.Collection")]
+    impl Collection {
+      fn create_item(
+        &self,
+        properties: HashMap<String, OwnedValue>,
+        secret: (OwnedObjectPath, Vec<u8>, Vec<u8>, String),
+        _replace: bool
+      ) -> (OwnedObjectPath, OwnedObjectPath) {
+        let label = String::try_from(
+          properties["org.freedesktop.Secret.Item.Label"].try_clone().unwrap()
+        ).unwrap();
+        let attributes = HashMap::<String, String>::try_from(
+          properties["org.freedesktop.Secret.Item.Attributes"].try_clone().unwrap()
+        ).unwrap();
+        self.items.lock().unwrap().push((label, attributes, secret.2));
+        (
+          OwnedObjectPath::try_from("/org/freedesktop/secrets/collection/login/1").unwrap(),
+          OwnedObjectPath::try_from("/").unwrap(),
+        )
+      }
+
+      #[zbus(property)]
+      fn label(&self) -> String {
+        "Login".to_string()
+      }
+
+      #[zbus(property)]
+      fn locked(&self) -> bool {
+        false
+      }
+    }
+  }
+
+  #[tokio::test]
+  #[ignore = "requires a D-Bus session bus, e.g. `dbus-run-session -- cargo test -- --ignored`"]
+  async fn test_sync_against_session_bus() {
+    let items = mock::Items::default();
+    let _server = zbus::connection::Builder
+      ::session()
+      .unwrap()
+      .name(BUS_NAME)
+      .unwrap()
+      .serve_at(SERVICE_PATH, mock::Service)
+      .unwrap()
+      .serve_at("/org/freedesktop/secrets/collection/login", mock::Collection {
+        items: items.clone(),
+      })
+      .unwrap()
+      .build().await
+      .unwrap();
+
+    let provider = KeyringProvider::new(HashMap::new()).unwrap();
+    assert!(provider.is_configured().await.unwrap());
+    assert_eq!(provider.list_targets("collection").await.unwrap(), vec!["Login".to_string()]);
+
+    let secrets = vec![Secret {
+      name: "CLAUDE_ACCESS_TOKEN".to_string(),
+      value: "sk-test".to_string(),
+      description: None,
+    }];
+    let result = provider.sync_secrets(&secrets, &[collection_target()]).await.unwrap();
+    assert_eq!(result.succeeded, 1, "{:?}", result.errors);
+
+    let items = items.lock().unwrap();
+    assert_eq!(items[0].0, "Team Claude: CLAUDE_ACCESS_TOKEN");
+    assert_eq!(items[0].1["service"], "claude");
+    assert_eq!(items[0].2, b"sk-test");
+  }
+}
@@ -8,6 +8,11 @@
 //! ## Supported Providers
 //!
 //! - [`github`] - GitHub API integration for repository and organization sync
+//! - [`bitbucket`] - Bitbucket Pipelines repository, workspace and deployment variables
+//! - [`aws`] - AWS Secrets Manager and SSM Parameter Store sync
+//! - `keyring` - Freedesktop Secret Service (GNOME Keyring/KWallet), behind the `keyring` feature
+//! - [`exec`] - Out-of-tree providers implemented as executables (JSON-lines over stdin/stdout)
+//! - [`webhook`] - HTTPS push to internal services with HMAC signing and mTLS
 //! - [`registry`] - Provider registry management and factory patterns
 //!
 //! ## Provider Architecture
@@ -73,6 +78,29 @@
 //! - **Required**: `token` (GitHub personal access token)
 //! - **Optional**: `org` (default organization), `base_url` (GitHub Enterprise URL)
 //!
+//! ### Bitbucket Provider
+//! - **Required**: `BITBUCKET_ACCESS_TOKEN`, or `BITBUCKET_APP_PASSWORD` with a `username`
+//! - **Optional**: `base_url` (Data Center), `access_token_env` / `app_password_env`
+//!   (alternative variable names)
+//!
+//! ### AWS Provider
+//! - **Required**: none (credentials and region come from the standard AWS env/profile chain)
+//! - **Optional**: `region`, `profile`, `endpoint_url` (e.g. LocalStack)
+//!
+//! ### Keyring Provider
+//! - **Required**: none (uses the Secret Service on the D-Bus session bus)
+//! - **Target options**: `label` (item label prefix), `attribute.<name>` (extra item attributes)
+//!
+//! ### Exec Provider and Plugins
+//! - **Required**: `command` (path to the provider executable, `exec` provider only)
+//! - **Optional**: `timeout` (seconds per call); all settings are forwarded to the executable
+//! - Executables in `plugins/` next to the configuration file are registered under their
+//!   file name
+//!
+//! ### Webhook Provider
+//! - **Required**: none; every option is per target (method, headers, body templates
+//!   for syncs and deletions, HMAC key, client certificates, expected status codes)
+//!
 //! ## Security Considerations
 //!
 //! - All API tokens are stored securely and never logged
@@ -80,8 +108,14 @@
 //! - Sensitive configuration is validated before use
 //! - Rate limiting and retry logic prevent API abuse
 
+pub mod aws;
+pub mod bitbucket;
+pub mod exec;
 pub mod github;
+#[cfg(feature = "keyring")]
+pub mod keyring;
 pub mod registry;
+pub mod webhook;
 
 use crate::error::Result;
 use crate::traits::SecretProvider;
@@ -100,6 +134,17 @@ impl ProviderFactory {
 
     // Register built-in providers
     factory.register("github", Box::new(github::GitHubProviderCreator));
+    factory.register("bitbucket", Box::new(bitbucket::BitbucketProviderCreator));
+    factory.register("aws", Box::new(aws::AwsProviderCreator));
+    #[cfg(feature = "keyring")]
+    factory.register("keyring", Box::new(keyring::KeyringProviderCreator));
+    factory.register("exec", Box::new(exec::ExecProviderCreator));
+    factory.register("webhook", Box::new(webhook::WebhookProviderCreator));
+
+    // Register out-of-tree providers from the plugins directory
+    if let Some(dir) = exec::plugins_dir() {
+      factory.register_plugins(&dir);
+    }
 
     factory
   }
@@ -108,6 +153,19 @@ impl ProviderFactory {
     self.creators.insert(name.to_string(), creator);
   }
 
+  /// Register every plugin executable found in `dir` (built-in providers take precedence)
+  pub fn register_plugins(&mut self, dir: &std::path::Path) {
+    for (name, program) in exec::discover_plugins(dir) {
+      if self.creators.contains_key(&name) {
+        tracing::warn!("Plugin {} shadows a registered provider, skipping", program.display());
+        continue;
+      }
+
+      tracing::debug!("Registering plugin provider {} from {}", name, program.display());
+      self.register(&name, Box::new(exec::PluginProviderCreator::new(&name, program)));
+    }
+  }
+
   pub fn create(
     &self,
     name: &str,
@@ -122,6 +180,11 @@ impl ProviderFactory {
     creator.create(config)
   }
 
+  /// Naming rules of a provider's targets of `kind`, `None` for unknown providers
+  pub fn secret_name_rules(&self, name: &str, kind: &str) -> Option<SecretNameRules> {
+    self.creators.get(name).map(|creator| creator.secret_name_rules(kind))
+  }
+
   pub fn available_providers(&self) -> Vec<&str> {
     self.creators
       .keys()
@@ -144,6 +207,106 @@ pub trait ProviderCreator: Send + Sync {
   fn optional_config(&self) -> Vec<&str> {
     Vec::new()
   }
+  /// Names the provider accepts for secrets in targets of `kind` (any name by default)
+  fn secret_name_rules(&self, _kind: &str) -> SecretNameRules {
+    SecretNameRules::default()
+  }
+}
+
+/// Naming rules a provider places on secret names
+///
+/// The default accepts any name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SecretNameRules {
+  /// Characters allowed besides ASCII letters and digits, `None` for any character
+  pub extra_chars: Option<&'static str>,
+  /// Lowercase letters are not allowed
+  pub uppercase: bool,
+  /// Names must not start with a digit
+  pub no_leading_digit: bool,
+  /// Prefixes reserved by the provider, compared case-insensitively
+  pub reserved_prefixes: &'static [&'static str],
+}
+
+impl SecretNameRules {
+  /// Whether every name is accepted
+  pub fn is_any(&self) -> bool {
+    *self == Self::default()
+  }
+
+  fn allows(&self, c: char) -> bool {
+    if self.uppercase && c.is_ascii_lowercase() {
+      return false;
+    }
+    match self.extra_chars {
+      Some(extra) => c.is_ascii_alphanumeric() || extra.contains(c),
+      None => true,
+    }
+  }
+
+  /// Whether `name`, or a part of one, has characters the provider rejects
+  pub fn has_invalid_chars(&self, name: &str) -> bool {
+    !name.chars().all(|c| self.allows(c))
+  }
+
+  /// Allowed characters, for messages
+  pub fn describe_chars(&self) -> String {
+    let letters = if self.uppercase { "uppercase letters" } else { "letters" };
+    match self.extra_chars {
+      Some(extra) => {
+        let extra: Vec<String> = extra.chars().map(String::from).collect();
+        format!("{}, digits and {}", letters, extra.join(" "))
+      }
+      None => format!("any characters but lowercase {}", letters),
+    }
+  }
+
+  /// Why the provider rejects `name`, `None` when it accepts it
+  pub fn check(&self, name: &str) -> Option<String> {
+    if name.is_empty() {
+      Some("is empty".to_string())
+    } else if self.has_invalid_chars(name) {
+      Some(format!("may only contain {}", self.describe_chars()))
+    } else if self.no_leading_digit && name.starts_with(|c: char| c.is_ascii_digit()) {
+      Some("starts with a digit".to_string())
+    } else {
+      self
+        .reserved_prefix(name)
+        .map(|prefix| format!("uses the reserved {} prefix", prefix))
+    }
+  }
+
+  fn reserved_prefix(&self, name: &str) -> Option<&'static str> {
+    let upper = name.to_ascii_uppercase();
+    self.reserved_prefixes
+      .iter()
+      .copied()
+      .find(|prefix| upper.starts_with(&prefix.to_ascii_uppercase()))
+  }
+
+  /// `name` with rejected characters replaced by `_`, uppercased if need be
+  pub fn normalize_chars(&self, name: &str) -> String {
+    name
+      .chars()
+      .map(|c| if self.uppercase { c.to_ascii_uppercase() } else { c })
+      .map(|c| if self.allows(c) { c } else { '_' })
+      .collect()
+  }
+
+  /// The closest name the provider accepts
+  ///
+  /// Characters are normalised, reserved prefixes dropped and a leading digit gets a `_`
+  /// in front, so `my-secret.name` becomes `MY_SECRET_NAME` under GitHub's rules.
+  pub fn normalize(&self, name: &str) -> String {
+    let mut name = self.normalize_chars(name);
+    while let Some(prefix) = self.reserved_prefix(&name) {
+      name = name[prefix.len()..].to_string();
+    }
+    if self.no_leading_digit && name.starts_with(|c: char| c.is_ascii_digit()) {
+      name.insert(0, '_');
+    }
+    name
+  }
 }
 
 /// Base provider implementation with common functionality
@@ -121,12 +121,20 @@ impl SecretManager for ProviderRegistry {
     mapping: &SecretMapping,
     targets: &[Target]
   ) -> Result<SyncResult> {
-    let secrets = mapping.to_secrets(credentials);
     let mut total_result = SyncResult {
       succeeded: 0,
       failed: 0,
       errors: Vec::new(),
     };
+    let secrets = match mapping.to_secrets(credentials) {
+      Ok(secrets) => secrets,
+      Err(e) => {
+        error!("Could not build secrets: {}", e);
+        total_result.failed = targets.len().max(1);
+        total_result.errors.push(e.to_string());
+        return Ok(total_result);
+      }
+    };
 
     for (provider_name, provider) in &self.providers {
       match provider.sync_secrets(&secrets, targets).await {
@@ -148,6 +156,55 @@ impl SecretManager for ProviderRegistry {
     Ok(total_result)
   }
 
+  async fn delete_secrets_from_targets(
+    &self,
+    secret_names: &[String],
+    targets: &[Target]
+  ) -> Result<SyncResult> {
+    let mut total_result = SyncResult {
+      succeeded: 0,
+      failed: 0,
+      errors: Vec::new(),
+    };
+
+    for target in targets {
+      let Some(provider) = self.providers.get(&target.provider) else {
+        total_result.failed += secret_names.len();
+        total_result.errors.push(
+          format!(
+            "{}:{}:{} - provider {} is not initialized",
+            target.provider,
+            target.target_type,
+            target.name,
+            target.provider
+          )
+        );
+        continue;
+      };
+
+      match provider.delete_secrets(secret_names, std::slice::from_ref(target)).await {
+        Ok(result) => {
+          total_result.succeeded += result.succeeded;
+          total_result.failed += result.failed;
+          total_result.errors.extend(result.errors);
+        }
+        Err(e) => {
+          error!("Provider {} delete failed: {}", target.provider, e);
+          total_result.failed += secret_names.len();
+          total_result.errors.push(format!("Provider {}: {}", target.provider, e));
+        }
+      }
+    }
+
+    info!(
+      "Delete completed: {} succeeded, {} failed",
+      total_result.succeeded,
+      total_result.failed
+    );
+
+    Ok(total_result)
+  }
+
   async fn validate_targets(&self) -> Result<HashMap<String, bool>> {
     let mut all_results = HashMap::new();
 
@@ -0,0 +1,545 @@
+//! Generic webhook provider for pushing credentials to internal services
+//!
+//! Each target is a URL that receives the secret set in a single request. Per-target
+//! options (see [`Target::config`]):
+//!
+//! - `method`: HTTP method (default `POST`)
+//! - `header.<Name>`: extra request headers; `${VAR}` references are expanded from the environment
+//! - `body_template`: request body, a [template](crate::utils::template) over the secrets by
+//!   name plus `secrets_json` (all of them as an object) and `timestamp` (default:
+//!   `{"secrets": {{ secrets_json }}}`); use `{{ NAME | json }}` for a quoted JSON string
+//! - `delete_body_template`: body sent when secrets are deleted, over `deleted` (the secret
+//!   names) and `timestamp` (default: `{"deleted": {{ deleted }}}`)
+//! - `hmac_secret_env`: environment variable holding the HMAC-SHA256 signing key
+//! - `hmac_header`: signature header name (default `X-Signature-256`)
+//! - `client_cert` / `client_key` / `ca_cert`: PEM files for mutual TLS
+//! - `expected_status`: comma separated status codes (default: any 2xx)
+//! - `probe_method`: `HEAD` (default) or `OPTIONS`, used by `validate_access`
+//! - `timeout`: request timeout in seconds (default 30)
+
+use super::ProviderCreator;
+use crate::error::{ ClaudeCodeError, Result };
+use crate::traits::{ Secret, SecretProvider, SyncResult, Target };
+use async_trait::async_trait;
+use hmac::{ Hmac, Mac };
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::{ debug, error, info, warn };
+
+const DEFAULT_BODY_TEMPLATE: &str = r#"{"secrets": {{ secrets_json }}}"#;
+const DEFAULT_DELETE_BODY_TEMPLATE: &str = r#"{"deleted": {{ deleted }}}"#;
+const DEFAULT_SIGNATURE_HEADER: &str = "X-Signature-256";
+const TIMESTAMP_HEADER: &str = "X-Signature-Timestamp";
+const HEADER_PREFIX: &str = "header.";
+
+/// Render a request body template, see the module docs for the fields it can use
+fn render_body(option: &str, template: &str, context: &serde_json::Value) -> Result<String> {
+  crate::utils::template
+    ::render(template, context)
+    .map_err(|e| ClaudeCodeError::InvalidConfig(format!("{}: {}", option, e)))
+}
+
+/// Fields of `body_template`
+fn sync_context(secrets: &[Secret], timestamp: i64) -> serde_json::Value {
+  let mut context: serde_json::Map<String, serde_json::Value> = secrets
+    .iter()
+    .map(|s| (s.name.clone(), serde_json::Value::String(s.value.clone())))
+    .collect();
+  let secrets_json = serde_json::Value::Object(context.clone());
+  context.insert("secrets_json".to_string(), secrets_json);
+  context.insert("timestamp".to_string(), timestamp.into());
+  serde_json::Value::Object(context)
+}
+
+/// Compute the `sha256=<hex>` signature over `<timestamp>.<body>`
+fn sign(key: &[u8], timestamp: i64, body: &[u8]) -> String {
+  let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+  mac.update(timestamp.to_string().as_bytes());
+  mac.update(b".");
+  mac.update(body);
+  format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+fn parse_expected_status(value: Option<&String>) -> Result<Vec<u16>> {
+  value
+    .map(|v| {
+      v.split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+          s.parse::<u16>().map_err(|_| {
+            ClaudeCodeError::InvalidConfig(format!("Invalid expected_status code: {}", s))
+          })
+        })
+        .collect()
+    })
+    .unwrap_or_else(|| Ok(Vec::new()))
+}
+
+fn parse_method(value: &str) -> Result<reqwest::Method> {
+  reqwest::Method
+    ::from_bytes(value.to_uppercase().as_bytes())
+    .map_err(|_| ClaudeCodeError::InvalidConfig(format!("Invalid HTTP method: {}", value)))
+}
+
+/// Lookup of environment variables by name
+type EnvLookup = Box<dyn Fn(&str) -> Option<String> + Send + Sync>;
+
+/// Webhook secret provider
+///
+/// Target type `endpoint`: the target name is the URL to call.
+pub struct WebhookProvider {
+  /// Looks up the variables of `hmac_secret_env` and `${VAR}` in headers
+  env: EnvLookup,
+}
+
+impl WebhookProvider {
+  pub fn new(_config: HashMap<String, String>) -> Result<Self> {
+    Ok(Self::with_env(|name| std::env::var(name).ok()))
+  }
+
+  /// Provider reading variables from `env` rather than the process environment
+  pub fn with_env(env: impl Fn(&str) -> Option<String> + Send + Sync + 'static) -> Self {
+    Self { env: Box::new(env) }
+  }
+
+  /// Build an HTTP client honouring the target's TLS and timeout options
+  fn client_for(&self, target: &Target) -> Result<reqwest::Client> {
+    let timeout = target.config
+      .get("timeout")
+      .and_then(|t| t.parse().ok())
+      .unwrap_or(30);
+    let mut builder = reqwest::Client::builder().timeout(Duration::from_secs(timeout));
+
+    if let Some(cert_path) = target.config.get("client_cert") {
+      let mut pem = std::fs::read(shellexpand::tilde(cert_path).as_ref())?;
+      if let Some(key_path) = target.config.get("client_key") {
+        pem.push(b'\n');
+        pem.extend(std::fs::read(shellexpand::tilde(key_path).as_ref())?);
+      }
+      builder = builder.identity(reqwest::Identity::from_pem(&pem)?);
+    }
+
+    if let Some(ca_path) = target.config.get("ca_cert") {
+      let pem = std::fs::read(shellexpand::tilde(ca_path).as_ref())?;
+      builder = builder.add_root_certificate(reqwest::Certificate::from_pem(&pem)?);
+    }
+
+    Ok(builder.build()?)
+  }
+
+  fn headers_for(&self, target: &Target) -> Result<Vec<(String, String)>> {
+    target.config
+      .iter()
+      .filter_map(|(key, value)| key.strip_prefix(HEADER_PREFIX).map(|name| (name, value)))
+      .map(|(name, value)| {
+        let lookup = |var: &str| Ok::<_, std::env::VarError>((self.env)(var));
+        let expanded = shellexpand::env_with_context(value, lookup).map_err(|e| {
+          ClaudeCodeError::InvalidConfig(format!("Header {}: {}", name, e))
+        })?;
+        Ok((name.to_string(), expanded.into_owned()))
+      })
+      .collect()
+  }
+
+  /// Send the secrets to a target
+  async fn deliver(&self, target: &Target, secrets: &[Secret]) -> Result<()> {
+    let timestamp = chrono::Utc::now().timestamp();
+    let template = target.config
+      .get("body_template")
+      .map(|s| s.as_str())
+      .unwrap_or(DEFAULT_BODY_TEMPLATE);
+    let body = render_body("body_template", template, &sync_context(secrets, timestamp))?;
+
+    debug!("Sending {} secrets to {}", secrets.len(), target.name);
+    self.send(target, body, timestamp).await
+  }
+
+  /// Tell a target that secrets are gone
+  async fn retract(&self, target: &Target, secret_names: &[String]) -> Result<()> {
+    let timestamp = chrono::Utc::now().timestamp();
+    let template = target.config
+      .get("delete_body_template")
+      .map(|s| s.as_str())
+      .unwrap_or(DEFAULT_DELETE_BODY_TEMPLATE);
+    let context = serde_json::json!({ "deleted": secret_names, "timestamp": timestamp });
+    let body = render_body("delete_body_template", template, &context)?;
+
+    debug!("Sending deletion of {} secrets to {}", secret_names.len(), target.name);
+    self.send(target, body, timestamp).await
+  }
+
+  /// Send a rendered body with the target's method, headers and signature
+  async fn send(&self, target: &Target, body: String, timestamp: i64) -> Result<()> {
+    let method = parse_method(target.config.get("method").map(|s| s.as_str()).unwrap_or("POST"))?;
+    let headers = self.headers_for(target)?;
+    let content_type = headers
+      .iter()
+      .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+      .map(|(_, value)| value.clone())
+      .unwrap_or_else(|| "application/json".to_string());
+
+    let mut request = self
+      .client_for(target)?
+      .request(method, &target.name)
+      .header("content-type", &content_type);
+    for (name, value) in &headers {
+      if !name.eq_ignore_ascii_case("content-type") {
+        request = request.header(name.as_str(), value.as_str());
+      }
+    }
+
+    if let Some(env_var) = target.config.get("hmac_secret_env") {
+      let key = (self.env)(env_var).ok_or_else(|| {
+        ClaudeCodeError::InvalidConfig(format!("HMAC key variable {} is not set", env_var))
+      })?;
+      let header = target.config
+        .get("hmac_header")
+        .map(|s| s.as_str())
+        .unwrap_or(DEFAULT_SIGNATURE_HEADER);
+      request = request
+        .header(header, sign(key.as_bytes(), timestamp, body.as_bytes()))
+        .header(TIMESTAMP_HEADER, timestamp.to_string());
+    }
+
+    let response = request.body(body).send().await?;
+    let status = response.status();
+
+    let expected = parse_expected_status(target.config.get("expected_status"))?;
+    let accepted = if expected.is_empty() {
+      status.is_success()
+    } else {
+      expected.contains(&status.as_u16())
+    };
+
+    if accepted {
+      Ok(())
+    } else {
+      let text = response.text().await.unwrap_or_default();
+      Err(
+        ClaudeCodeError::Provider(
+          format!("{} returned unexpected status {}: {}", target.name, status, text.trim())
+        )
+      )
+    }
+  }
+
+  async fn check_target_access(&self, target: &Target) -> Result<bool> {
+    let method = parse_method(
+      target.config
+        .get("probe_method")
+        .map(|s| s.as_str())
+        .unwrap_or("HEAD")
+    )?;
+
+    let mut request = self.client_for(target)?.request(method, &target.name);
+    for (name, value) in self.headers_for(target)? {
+      request = request.header(name, value);
+    }
+
+    let status = request.send().await?.status();
+    debug!("Probe of {} returned {}", target.name, status);
+
+    // Reachable and authorised, even if the probe method itself is not supported
+    Ok(
+      !matches!(status.as_u16(), 401 | 403 | 404) &&
+        !status.is_server_error()
+    )
+  }
+}
+
+#[async_trait]
+impl SecretProvider for WebhookProvider {
+  fn provider_name(&self) -> &str {
+    "webhook"
+  }
+
+  async fn sync_secrets(&self, secrets: &[Secret], targets: &[Target]) -> Result<SyncResult> {
+    let mut succeeded = 0;
+    let mut failed = 0;
+    let mut errors = Vec::new();
+
+    for target in targets {
+      if target.provider != self.provider_name() {
+        continue;
+      }
+
+      match self.deliver(target, secrets).await {
+        Ok(()) => {
+          info!("Delivered {} secrets to {}", secrets.len(), target.name);
+          succeeded += secrets.len();
+        }
+        Err(e) => {
+          error!("Failed to deliver secrets to {}: {}", target.name, e);
+          failed += secrets.len();
+          errors.push(format!("{}:{} - {}", target.target_type, target.name, e));
+        }
+      }
+    }
+
+    Ok(SyncResult {
+      succeeded,
+      failed,
+      errors,
+    })
+  }
+
+  /// Sends each target the `delete_body_template` body; the service does the deleting
+  async fn delete_secrets(
+    &self,
+    secret_names: &[String],
+    targets: &[Target]
+  ) -> Result<SyncResult> {
+    let mut result = SyncResult {
+      succeeded: 0,
+      failed: 0,
+      errors: Vec::new(),
+    };
+
+    for target in targets {
+      if target.provider != self.provider_name() {
+        continue;
+      }
+
+      match self.retract(target, secret_names).await {
+        Ok(()) => {
+          info!("Sent deletion of {} secrets to {}", secret_names.len(), target.name);
+          result.succeeded += secret_names.len();
+        }
+        Err(e) => {
+          error!("Failed to send deletion to {}: {}", target.name, e);
+          result.failed += secret_names.len();
+          result.errors.push(format!("{}:{} - {}", target.target_type, target.name, e));
+        }
+      }
+    }
+
+    Ok(result)
+  }
+
+  async fn validate_access(&self, targets: &[Target]) -> Result<HashMap<String, bool>> {
+    let mut results = HashMap::new();
+
+    for target in targets {
+      if target.provider != self.provider_name() {
+        continue;
+      }
+
+      let key = format!("{}:{}", target.target_type, target.name);
+      let has_access = match self.check_target_access(target).await {
+        Ok(has_access) => has_access,
+        Err(e) => {
+          warn!("Probe of {} failed: {}", target.name, e);
+          false
+        }
+      };
+      results.insert(key, has_access);
+    }
+
+    Ok(results)
+  }
+
+  async fn list_targets(&self, target_type: &str) -> Result<Vec<String>> {
+    // Webhook endpoints cannot be discovered; they only come from configuration
+    match target_type {
+      "endpoint" => Ok(Vec::new()),
+      _ => Err(ClaudeCodeError::Generic(format!("Unsupported target type: {}", target_type))),
+    }
+  }
+
+  async fn is_configured(&self) -> Result<bool> {
+    Ok(true)
+  }
+}
+
+/// Webhook provider creator for Factory Pattern
+pub struct WebhookProviderCreator;
+
+impl ProviderCreator for WebhookProviderCreator {
+  fn create(&self, config: &HashMap<String, String>) -> Result<Box<dyn SecretProvider>> {
+    let provider = WebhookProvider::new(config.clone())?;
+    Ok(Box::new(provider))
+  }
+
+  fn provider_type(&self) -> &str {
+    "webhook"
+  }
+
+  fn required_config(&self) -> Vec<&str> {
+    vec![] // All options are per target
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use tokio::io::{ AsyncReadExt, AsyncWriteExt };
+  use tokio::net::TcpListener;
+
+  fn secrets() -> Vec<Secret> {
+    vec![
+      Secret {
+        name: "CLAUDE_ACCESS_TOKEN".to_string(),
+        value: "sk-\"quoted\"".to_string(),
+        description: None,
+      },
+      Secret {
+        name: "CLAUDE_EXPIRES_AT".to_string(),
+        value: "1750255977327".to_string(),
+        description: None,
+      }
+    ]
+  }
+
+  #[test]
+  fn test_render_body() {
+    let template = r#"{"token": {{ CLAUDE_ACCESS_TOKEN | json }}, "at": {{timestamp}}}"#;
+    let body = render_body("body_template", template, &sync_context(&secrets(), 42)).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(parsed["token"], "sk-\"quoted\"");
+    assert_eq!(parsed["at"], 42);
+
+    let context = sync_context(&secrets(), 0);
+    let body = render_body("body_template", DEFAULT_BODY_TEMPLATE, &context).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(parsed["secrets"]["CLAUDE_EXPIRES_AT"], "1750255977327");
+
+    let error = render_body("body_template", "{{ MISSING }}", &context).unwrap_err();
+    assert!(error.to_string().contains("body_template"));
+    assert!(render_body("body_template", "{{ unclosed", &context).is_err());
+  }
+
+  #[test]
+  fn test_expected_status() {
+    let value = "200, 204".to_string();
+    assert_eq!(parse_expected_status(Some(&value)).unwrap(), vec![200, 204]);
+    assert!(parse_expected_status(None).unwrap().is_empty());
+    assert!(parse_expected_status(Some(&"ok".to_string())).is_err());
+  }
+
+  fn header_value(request: &str, name: &str) -> Option<String> {
+    request.lines().find_map(|line| {
+      let (key, value) = line.split_once(':')?;
+      key.eq_ignore_ascii_case(name).then(|| value.trim().to_string())
+    })
+  }
+
+  /// Accept one HTTP request, reply with `status`, and return the raw request
+  async fn serve_once(listener: TcpListener, status: &'static str) -> String {
+    let (mut socket, _) = listener.accept().await.unwrap();
+    let mut request = Vec::new();
+    let mut buf = [0u8; 4096];
+
+    loop {
+      let n = socket.read(&mut buf).await.unwrap();
+      request.extend_from_slice(&buf[..n]);
+      let text = String::from_utf8_lossy(&request);
+      if let Some(header_end) = text.find("\r\n\r\n") {
+        let length = header_value(&text, "content-length")
+          .and_then(|v| v.parse::<usize>().ok())
+          .unwrap_or(0);
+        if request.len() >= header_end + 4 + length {
+          break;
+        }
+      }
+      if n == 0 {
+        break;
+      }
+    }
+
+    let response = format!("HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status);
+    socket.write_all(response.as_bytes()).await.unwrap();
+    String::from_utf8_lossy(&request).to_string()
+  }
+
+  fn endpoint_target(url: String) -> Target {
+    let mut config = HashMap::new();
+    config.insert("hmac_secret_env".to_string(), "CCT_TEST_WEBHOOK_KEY".to_string());
+    config.insert("header.X-Team".to_string(), "bots".to_string());
+    config.insert("expected_status".to_string(), "202".to_string());
+    Target {
+      provider: "webhook".to_string(),
+      target_type: "endpoint".to_string(),
+      name: url,
+      config,
+    }
+  }
+
+  fn test_env(name: &str) -> Option<String> {
+    match name {
+      "CCT_TEST_WEBHOOK_KEY" => Some("test-key".to_string()),
+      "CCT_TEST_TEAM" => Some("bots".to_string()),
+      _ => None,
+    }
+  }
+
+  #[tokio::test]
+  async fn test_sync_to_local_listener() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/credentials", listener.local_addr().unwrap());
+    let server = tokio::spawn(serve_once(listener, "202 Accepted"));
+
+    let mut target = endpoint_target(url);
+    target.config.insert("header.X-Team".to_string(), "${CCT_TEST_TEAM}".to_string());
+    let provider = WebhookProvider::with_env(test_env);
+    let result = provider.sync_secrets(&secrets(), &[target]).await.unwrap();
+    assert_eq!(result.succeeded, 2, "{:?}", result.errors);
+
+    let request = server.await.unwrap();
+    assert!(request.starts_with("POST /credentials"));
+    assert!(request.to_lowercase().contains("x-team: bots"));
+
+    let (head, body) = request.split_once("\r\n\r\n").unwrap();
+    let timestamp: i64 = header_value(head, "x-signature-timestamp").unwrap().parse().unwrap();
+    let expected = sign(b"test-key", timestamp, body.as_bytes());
+    assert!(head.contains(&expected));
+  }
+
+  #[tokio::test]
+  async fn test_delete_sends_tombstone() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/credentials", listener.local_addr().unwrap());
+    let server = tokio::spawn(serve_once(listener, "202 Accepted"));
+
+    let provider = WebhookProvider::with_env(test_env);
+    let names = vec!["CLAUDE_ACCESS_TOKEN".to_string()];
+    let result = provider.delete_secrets(&names, &[endpoint_target(url)]).await.unwrap();
+    assert_eq!(result.succeeded, 1, "{:?}", result.errors);
+
+    let request = server.await.unwrap();
+    let (head, body) = request.split_once("\r\n\r\n").unwrap();
+    assert!(head.starts_with("POST /credentials"));
+    assert!(header_value(head, "x-signature-256").is_some());
+    let parsed: serde_json::Value = serde_json::from_str(body).unwrap();
+    assert_eq!(parsed, serde_json::json!({ "deleted": ["CLAUDE_ACCESS_TOKEN"] }));
+  }
+
+  #[tokio::test]
+  async fn test_unexpected_status_fails() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/credentials", listener.local_addr().unwrap());
+    let server = tokio::spawn(serve_once(listener, "200 OK"));
+
+    let mut target = endpoint_target(url);
+    target.config.remove("hmac_secret_env");
+    let provider = WebhookProvider::new(HashMap::new()).unwrap();
+    let result = provider.sync_secrets(&secrets(), &[target]).await.unwrap();
+    assert_eq!(result.failed, 2);
+    assert!(result.errors[0].contains("unexpected status 200"));
+    server.await.unwrap();
+  }
+
+  #[tokio::test]
+  async fn test_validate_access_probe() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/credentials", listener.local_addr().unwrap());
+    let server = tokio::spawn(serve_once(listener, "405 Method Not Allowed"));
+
+    let target = endpoint_target(url.clone());
+    let provider = WebhookProvider::new(HashMap::new()).unwrap();
+    let results = provider.validate_access(&[target]).await.unwrap();
+    assert_eq!(results.get(&format!("endpoint:{}", url)), Some(&true));
+    assert!(server.await.unwrap().starts_with("HEAD /credentials"));
+  }
+}
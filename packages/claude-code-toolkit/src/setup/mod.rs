@@ -0,0 +1,289 @@
+//! Interactive setup wizard behind `configure`.
+//!
+//! [`ConfigureWizard`] implements [`SetupWizard`] over a fixed list of
+//! [`SetupStep`]s:
+//!
+//! | Step            | Purpose                                |
+//! |-----------------|----------------------------------------|
+//! | `prerequisites` | GitHub CLI installed and authenticated |
+//! | `credentials`   | Find a readable credential file        |
+//! | `targets`       | Choose organizations and repositories  |
+//! | `mappings`      | Map credential fields to secret names  |
+//! | `daemon`        | Install and start the sync daemon      |
+//!
+//! The configuration is saved after every completed step and the completed steps are
+//! recorded in `setup-progress.json` next to the configuration file, so an interrupted
+//! `configure` resumes where it stopped. Single steps can be re-run on their own with
+//! `configure --step <id>`.
+//!
+//! All input goes through a [`Prompter`]; [`ScriptedPrompter`] replays canned answers:
+//!
+//! ```rust,no_run
+//! use claude_code_toolkit::setup::{ ConfigureWizard, ScriptedPrompter };
+//! use claude_code_toolkit::traits::SetupWizard;
+//! use std::sync::Arc;
+//!
+//! #[tokio::main]
+//! async fn main() -> claude_code_toolkit::Result<()> {
+//!     let answers = ["y", "accessToken", "CLAUDE_ACCESS_TOKEN", "done"];
+//!     let wizard = ConfigureWizard::new(Arc::new(ScriptedPrompter::new(answers)))?;
+//!     wizard.run_steps(&["mappings"]).await?;
+//!     Ok(())
+//! }
+//! ```
+
+pub mod prompt;
+pub mod steps;
+
+pub use prompt::{ Prompter, ScriptedPrompter, TerminalPrompter };
+
+use crate::config::manager::{ ConfigurationManager, YamlConfigProvider };
+use crate::error::{ ClaudeCodeError, Result };
+use crate::traits::setup::{ SetupContext, SetupStep, SetupWizard };
+use crate::types::Config;
+use async_trait::async_trait;
+use console::style;
+use std::path::PathBuf;
+use std::sync::Arc;
+use steps::{ CredentialsStep, DaemonStep, MappingsStep, PrerequisitesStep, TargetsStep };
+
+/// Step result of completed steps
+const DONE: &str = "done";
+
+/// Step result of skipped steps
+const SKIPPED: &str = "skipped";
+
+/// The `configure` wizard
+pub struct ConfigureWizard {
+  config_manager: ConfigurationManager,
+  prompter: Arc<dyn Prompter>,
+  progress_path: PathBuf,
+}
+
+impl ConfigureWizard {
+  pub fn new(prompter: Arc<dyn Prompter>) -> Result<Self> {
+    let config_manager = ConfigurationManager::new()?;
+    let progress_path = config_manager.config_dir().join("setup-progress.json");
+    Ok(Self { config_manager, prompter, progress_path })
+  }
+
+  /// Wizard working on the configuration file at `config_path`
+  pub fn with_config_path(config_path: PathBuf, prompter: Arc<dyn Prompter>) -> Self {
+    let progress_path = config_path.with_file_name("setup-progress.json");
+    let config_manager = ConfigurationManager::with_provider(
+      Box::new(YamlConfigProvider::with_path(config_path))
+    );
+    Self { config_manager, prompter, progress_path }
+  }
+
+  /// Identifiers of all steps, in order
+  pub fn step_ids(&self) -> Vec<String> {
+    self
+      .get_steps()
+      .iter()
+      .map(|s| s.step_id().to_string())
+      .collect()
+  }
+
+  /// Forget the progress of an interrupted run, so the next run starts over
+  pub fn reset_progress(&self) -> Result<()> {
+    match std::fs::remove_file(&self.progress_path) {
+      Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+      _ => Ok(()),
+    }
+  }
+
+  fn load_progress(&self) -> Vec<String> {
+    std::fs
+      ::read_to_string(&self.progress_path)
+      .ok()
+      .and_then(|data| serde_json::from_str(&data).ok())
+      .unwrap_or_default()
+  }
+
+  fn save_progress(&self, completed: &[String]) -> Result<()> {
+    std::fs::write(&self.progress_path, serde_json::to_string(completed)?)?;
+    Ok(())
+  }
+
+  async fn run(&self, steps: Vec<Box<dyn SetupStep>>, resume: bool) -> Result<Config> {
+    let mut context = SetupContext::new();
+    context.config = self.config_manager.load_file().await?;
+
+    let mut completed = if resume { self.load_progress() } else { Vec::new() };
+    completed.retain(|id| steps.iter().any(|s| s.step_id() == id));
+    for id in &completed {
+      context.set_result(id, DONE);
+    }
+    if !completed.is_empty() {
+      let resuming = format!(
+        "Resuming setup ({} of {} steps already done)",
+        completed.len(),
+        steps.len()
+      );
+      self.prompter.say(&style(resuming).dim().to_string());
+    }
+
+    let total = steps.len();
+    for (index, step) in steps.iter().enumerate() {
+      if context.get_result(step.step_id()).is_some() {
+        continue;
+      }
+
+      self.prompter.say("");
+      self.prompter.say(
+        &style(format!("[{}/{}] {}", index + 1, total, step.description()))
+          .bold()
+          .cyan()
+          .to_string()
+      );
+
+      if step.can_skip(&context) {
+        self.prompter.say(&style("Nothing to do, skipping").dim().to_string());
+        context.set_result(step.step_id(), SKIPPED);
+        continue;
+      }
+
+      if !step.check_prerequisites(&context).await? {
+        self.prompter.say(&style("⚠️  Prerequisites not met, skipping").yellow().to_string());
+        context.set_result(step.step_id(), SKIPPED);
+        continue;
+      }
+
+      loop {
+        let outcome = match step.execute(&mut context).await {
+          Ok(()) if step.validate(&context).await? => Ok(()),
+          Ok(()) => Err(ClaudeCodeError::Setup(format!("Step '{}' is incomplete", step.step_id()))),
+          Err(e) => Err(e),
+        };
+
+        match outcome {
+          Ok(()) => {
+            break;
+          }
+          Err(e) => {
+            if !self.handle_error(step.as_ref(), &e, &mut context).await? {
+              // Progress so far is kept, so the next run resumes at this step
+              return Err(e);
+            }
+          }
+        }
+      }
+
+      self.config_manager.save_config(&context.config).await?;
+      context.set_result(step.step_id(), DONE);
+      if resume {
+        completed.push(step.step_id().to_string());
+        self.save_progress(&completed)?;
+      }
+    }
+
+    if resume {
+      self.reset_progress()?;
+    }
+
+    let next_steps: Vec<String> = steps
+      .iter()
+      .flat_map(|s| s.next_steps(&context))
+      .collect();
+    if !next_steps.is_empty() {
+      self.prompter.say("");
+      self.prompter.say(&style("Here's what you can do next:").dim().to_string());
+      for next_step in next_steps {
+        self.prompter.say(&format!("  • {}", next_step));
+      }
+    }
+
+    Ok(context.config)
+  }
+}
+
+#[async_trait]
+impl SetupWizard for ConfigureWizard {
+  fn wizard_name(&self) -> &str {
+    "configure"
+  }
+
+  fn get_steps(&self) -> Vec<Box<dyn SetupStep>> {
+    let prompter = &self.prompter;
+    vec![
+      Box::new(PrerequisitesStep { prompter: prompter.clone() }),
+      Box::new(CredentialsStep { prompter: prompter.clone() }),
+      Box::new(TargetsStep { prompter: prompter.clone() }),
+      Box::new(MappingsStep { prompter: prompter.clone() }),
+      Box::new(DaemonStep { prompter: prompter.clone() })
+    ]
+  }
+
+  /// Run every step, resuming after the last completed step of an interrupted run
+  async fn run_setup(&self) -> Result<Config> {
+    self.run(self.get_steps(), true).await
+  }
+
+  /// Run the given steps, in the given order, whether or not they ran before
+  async fn run_steps(&self, step_ids: &[&str]) -> Result<Config> {
+    let mut available = self.get_steps();
+    let mut steps = Vec::new();
+    for id in step_ids {
+      let index = available
+        .iter()
+        .position(|s| s.step_id() == *id)
+        .ok_or_else(|| {
+          ClaudeCodeError::Validation(
+            format!("Unknown setup step '{}' (available: {})", id, self.step_ids().join(", "))
+          )
+        })?;
+      steps.push(available.remove(index));
+    }
+
+    self.run(steps, false).await
+  }
+
+  fn get_progress(&self, context: &SetupContext) -> f32 {
+    let steps = self.get_steps();
+    let finished = steps
+      .iter()
+      .filter(|s| context.get_result(s.step_id()).is_some())
+      .count();
+    (finished as f32) / (steps.len() as f32)
+  }
+
+  /// Report a failed step and ask whether to retry it
+  async fn handle_error(
+    &self,
+    step: &dyn SetupStep,
+    error: &ClaudeCodeError,
+    _context: &mut SetupContext
+  ) -> Result<bool> {
+    self.prompter.say(&style(format!("❌ {}", error)).red().to_string());
+    Ok(self.prompter.confirm(&format!("Retry '{}'?", step.step_id())).unwrap_or(false))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn test_scripted_mappings_step() {
+    let dir = std::env::temp_dir().join(format!("cct-wizard-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let config_path = dir.join("config.yml");
+
+    let prompter = Arc::new(
+      ScriptedPrompter::new(["y", "accessToken", "CLAUDE_ACCESS_TOKEN", "expiresAt", "", "done"])
+    );
+    let wizard = ConfigureWizard::with_config_path(config_path.clone(), prompter.clone());
+
+    let config = wizard.run_steps(&["mappings"]).await.unwrap();
+    assert_eq!(config.credentials.field_mappings.len(), 1);
+    assert!(std::fs::read_to_string(&config_path).unwrap().contains("CLAUDE_ACCESS_TOKEN"));
+
+    let transcript = prompter.transcript();
+    assert_eq!(transcript[1], "[1/1] Map credential fields to secret names");
+    assert!(transcript.contains(&"❌ A secret name is required".to_string()));
+
+    assert!(wizard.run_steps(&["nope"]).await.is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+  }
+}
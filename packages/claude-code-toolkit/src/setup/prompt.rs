@@ -0,0 +1,94 @@
+//! Input sources for the setup wizard
+//!
+//! Steps never read stdin directly; they ask a [`Prompter`]. [`TerminalPrompter`]
+//! talks to the user, [`ScriptedPrompter`] replays canned answers and records
+//! everything the wizard printed, which makes whole wizard runs testable.
+
+use crate::error::{ ClaudeCodeError, Result };
+use console::style;
+use std::collections::VecDeque;
+use std::io::{ self, Write };
+use std::sync::Mutex;
+
+/// Where the wizard gets answers and shows messages
+pub trait Prompter: Send + Sync {
+  /// Ask for a line of input
+  fn prompt(&self, message: &str) -> Result<String>;
+
+  /// Show a line of output
+  fn say(&self, message: &str);
+
+  /// Ask a yes/no question (default: no)
+  fn confirm(&self, message: &str) -> Result<bool> {
+    loop {
+      match self.prompt(&format!("{} [y/N]", message))?.to_lowercase().as_str() {
+        "y" | "yes" => {
+          return Ok(true);
+        }
+        "n" | "no" | "" => {
+          return Ok(false);
+        }
+        _ => self.say(&style("Please enter 'y' for yes or 'n' for no").yellow().to_string()),
+      }
+    }
+  }
+}
+
+/// Interactive prompts on stdin/stdout
+#[derive(Debug, Default)]
+pub struct TerminalPrompter;
+
+impl Prompter for TerminalPrompter {
+  fn prompt(&self, message: &str) -> Result<String> {
+    print!("{}: ", style(message).bold());
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    let read = io::stdin().read_line(&mut input)?;
+    if read == 0 {
+      return Err(ClaudeCodeError::Setup("Input closed".to_string()));
+    }
+
+    Ok(input.trim().to_string())
+  }
+
+  fn say(&self, message: &str) {
+    println!("{}", message);
+  }
+}
+
+/// Canned answers, for tests and scripted runs
+#[derive(Debug, Default)]
+pub struct ScriptedPrompter {
+  answers: Mutex<VecDeque<String>>,
+  transcript: Mutex<Vec<String>>,
+}
+
+impl ScriptedPrompter {
+  pub fn new<I, S>(answers: I) -> Self where I: IntoIterator<Item = S>, S: Into<String> {
+    Self {
+      answers: Mutex::new(answers.into_iter().map(Into::into).collect()),
+      transcript: Mutex::new(Vec::new()),
+    }
+  }
+
+  /// Prompts and messages shown so far, in order
+  pub fn transcript(&self) -> Vec<String> {
+    self.transcript.lock().unwrap().clone()
+  }
+}
+
+impl Prompter for ScriptedPrompter {
+  fn prompt(&self, message: &str) -> Result<String> {
+    self.transcript.lock().unwrap().push(format!("? {}", message));
+    self.answers
+      .lock()
+      .unwrap()
+      .pop_front()
+      .ok_or_else(|| ClaudeCodeError::Setup(format!("No scripted answer for '{}'", message)))
+  }
+
+  fn say(&self, message: &str) {
+    self.transcript.lock().unwrap().push(console::strip_ansi_codes(message).to_string());
+  }
+}
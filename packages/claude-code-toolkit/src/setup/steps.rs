@@ -0,0 +1,387 @@
+//! Steps of the `configure` wizard
+
+use super::prompt::Prompter;
+use crate::config::credentials::CredentialsManager;
+use crate::error::{ ClaudeCodeError, Result };
+use crate::providers::github::GitHubManager;
+use crate::traits::setup::{ SetupContext, SetupStep };
+use crate::types::{ DEFAULT_PROFILE, TargetConfig };
+use crate::utils::systemd::SystemdManager;
+use crate::validation::check_secret_name;
+use async_trait::async_trait;
+use console::style;
+use std::sync::Arc;
+
+/// Set once the GitHub CLI is known to be installed and authenticated
+const GITHUB_READY: &str = "github_ready";
+
+/// Set when the sync daemon is already running
+const DAEMON_RUNNING: &str = "daemon_running";
+
+/// Well-known credential locations tried when the configured file is missing
+const CREDENTIAL_CANDIDATES: &[&str] = &[
+  "~/.claude/.credentials.json",
+  "~/.config/claude/.credentials.json",
+];
+
+async fn github_ready(github_manager: &GitHubManager) -> Result<bool> {
+  Ok(github_manager.check_gh_cli().await? && github_manager.check_authentication().await?)
+}
+
+/// GitHub CLI availability and daemon state
+pub struct PrerequisitesStep {
+  pub prompter: Arc<dyn Prompter>,
+}
+
+#[async_trait]
+impl SetupStep for PrerequisitesStep {
+  fn step_id(&self) -> &str {
+    "prerequisites"
+  }
+
+  fn description(&self) -> &str {
+    "Check prerequisites"
+  }
+
+  async fn check_prerequisites(&self, _context: &SetupContext) -> Result<bool> {
+    Ok(true)
+  }
+
+  async fn execute(&self, context: &mut SetupContext) -> Result<()> {
+    let github_manager = GitHubManager::new();
+
+    if !github_manager.check_gh_cli().await? {
+      return Err(
+        ClaudeCodeError::Setup(
+          "GitHub CLI (gh) not found. Please install it first: https://cli.github.com/".to_string()
+        )
+      );
+    }
+
+    if !github_manager.check_authentication().await? {
+      return Err(
+        ClaudeCodeError::Setup(
+          "GitHub CLI not authenticated. Please run: gh auth login".to_string()
+        )
+      );
+    }
+
+    self.prompter.say(&style("✅ GitHub CLI is available and authenticated").green().to_string());
+    context.set_flag(GITHUB_READY, true);
+
+    if let Ok(systemd_manager) = SystemdManager::new() {
+      context.set_flag(DAEMON_RUNNING, systemd_manager.is_running().await.unwrap_or(false));
+    }
+
+    Ok(())
+  }
+
+  async fn validate(&self, context: &SetupContext) -> Result<bool> {
+    Ok(context.get_flag(GITHUB_READY))
+  }
+}
+
+/// Locate a readable credential file
+pub struct CredentialsStep {
+  pub prompter: Arc<dyn Prompter>,
+}
+
+#[async_trait]
+impl SetupStep for CredentialsStep {
+  fn step_id(&self) -> &str {
+    "credentials"
+  }
+
+  fn description(&self) -> &str {
+    "Discover Claude credentials"
+  }
+
+  async fn check_prerequisites(&self, _context: &SetupContext) -> Result<bool> {
+    Ok(true)
+  }
+
+  async fn execute(&self, context: &mut SetupContext) -> Result<()> {
+    let base = context.config.credentials.clone();
+    let readable = |file_path: &str| {
+      let mut credentials = base.clone();
+      credentials.file_path = file_path.to_string();
+      async move { CredentialsManager::from_config(&credentials).read_generic_credentials().await }
+    };
+
+    let configured = context.config.credentials.file_path.clone();
+    if readable(&configured).await.is_ok() {
+      self.prompter.say(
+        &style(format!("✅ Claude credentials found at {}", configured)).green().to_string()
+      );
+      return Ok(());
+    }
+
+    self.prompter.say(
+      &style(format!("⚠️  No Claude credentials at {}", configured)).yellow().to_string()
+    );
+
+    for candidate in CREDENTIAL_CANDIDATES.iter().filter(|c| **c != configured) {
+      if
+        readable(candidate).await.is_ok() &&
+        self.prompter.confirm(&format!("Use the credentials found at {}?", candidate))?
+      {
+        context.config.credentials.file_path = candidate.to_string();
+        return Ok(());
+      }
+    }
+
+    loop {
+      let file_path = self.prompter.prompt("Credential file path (empty to give up)")?;
+      if file_path.is_empty() {
+        return Err(
+          ClaudeCodeError::Setup(
+            "Claude credentials not found. Please ensure Claude Code is installed and you're \
+             logged in.".to_string()
+          )
+        );
+      }
+
+      match readable(&file_path).await {
+        Ok(_) => {
+          context.config.credentials.file_path = file_path;
+          return Ok(());
+        }
+        Err(e) => self.prompter.say(&style(format!("❌ {}", e)).red().to_string()),
+      }
+    }
+  }
+
+  async fn validate(&self, context: &SetupContext) -> Result<bool> {
+    let credentials_manager = CredentialsManager::from_config(&context.config.credentials);
+    Ok(credentials_manager.read_generic_credentials().await.is_ok())
+  }
+}
+
+/// Choose GitHub organizations and repositories
+pub struct TargetsStep {
+  pub prompter: Arc<dyn Prompter>,
+}
+
+impl TargetsStep {
+  fn add_target(&self, context: &mut SetupContext, target: TargetConfig) {
+    let targets = &mut context.config.targets;
+    if targets.iter().any(|t| t.matches(&target.provider, &target.kind, &target.name)) {
+      let message = format!("{} is already configured", target.name);
+      self.prompter.say(&style(message).dim().to_string());
+    } else {
+      let message = format!("✅ Added {} {}", target.kind, target.name);
+      self.prompter.say(&style(message).green().to_string());
+      targets.push(target);
+    }
+  }
+}
+
+#[async_trait]
+impl SetupStep for TargetsStep {
+  fn step_id(&self) -> &str {
+    "targets"
+  }
+
+  fn description(&self) -> &str {
+    "Select organizations and repositories"
+  }
+
+  async fn check_prerequisites(&self, context: &SetupContext) -> Result<bool> {
+    Ok(context.get_flag(GITHUB_READY) || github_ready(&GitHubManager::new()).await?)
+  }
+
+  async fn execute(&self, context: &mut SetupContext) -> Result<()> {
+    let available_orgs = GitHubManager::new().list_organizations().await?;
+    if !available_orgs.is_empty() {
+      self.prompter.say(&style("Available organizations:").dim().to_string());
+      for (i, org) in available_orgs.iter().enumerate() {
+        self.prompter.say(&style(format!("  {}. {}", i + 1, org)).dim().to_string());
+      }
+    }
+
+    if self.prompter.confirm("Would you like to add organizations for credential sync?")? {
+      loop {
+        let org_name = self.prompter.prompt("Organization name (or 'done' to finish)")?;
+        if org_name.is_empty() || org_name.to_lowercase() == "done" {
+          break;
+        }
+
+        if
+          !available_orgs.contains(&org_name) &&
+          !self.prompter.confirm(
+            &format!(
+              "Organization '{}' not found in your available organizations. Add it anyway?",
+              org_name
+            )
+          )?
+        {
+          continue;
+        }
+        self.add_target(context, TargetConfig::new("github", "organization", &org_name));
+      }
+    }
+
+    if self.prompter.confirm("Would you like to add repositories for credential sync?")? {
+      loop {
+        let repo = self.prompter.prompt("Repository (owner/repo format, or 'done' to finish)")?;
+        if repo.is_empty() || repo.to_lowercase() == "done" {
+          break;
+        }
+
+        if repo.contains('/') {
+          self.add_target(context, TargetConfig::new("github", "repository", &repo));
+        } else {
+          self.prompter.say(
+            &style("❌ Invalid format. Use owner/repo format (e.g., microsoft/vscode)")
+              .red()
+              .to_string()
+          );
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  async fn validate(&self, context: &SetupContext) -> Result<bool> {
+    Ok(
+      context.config.targets
+        .iter()
+        .filter(|t| t.kind == "repository")
+        .all(|t| t.name.contains('/'))
+    )
+  }
+}
+
+/// Map credential fields to secret names
+pub struct MappingsStep {
+  pub prompter: Arc<dyn Prompter>,
+}
+
+#[async_trait]
+impl SetupStep for MappingsStep {
+  fn step_id(&self) -> &str {
+    "mappings"
+  }
+
+  fn description(&self) -> &str {
+    "Map credential fields to secret names"
+  }
+
+  async fn check_prerequisites(&self, _context: &SetupContext) -> Result<bool> {
+    Ok(true)
+  }
+
+  async fn execute(&self, context: &mut SetupContext) -> Result<()> {
+    self.prompter.say(
+      &style("Secret mappings define which credential fields sync to which secrets.")
+        .dim()
+        .to_string()
+    );
+
+    if !self.prompter.confirm("Configure secret mappings now?")? {
+      if context.config.credentials.field_mappings.is_empty() {
+        self.prompter.say(&style("⚠️  No secret mappings configured.").yellow().to_string());
+        self.prompter.say(
+          &style("   Run 'claude-code configure --step mappings' to add them later.")
+            .dim()
+            .to_string()
+        );
+      }
+      return Ok(());
+    }
+
+    self.prompter.say(
+      &style("Common fields: accessToken, refreshToken, expiresAt, subscriptionType, scopes")
+        .dim()
+        .to_string()
+    );
+
+    let mut mappings = std::collections::HashMap::new();
+    loop {
+      let field = self.prompter.prompt("Credential field name (or 'done' to finish)")?;
+      if field.is_empty() || field.to_lowercase() == "done" {
+        break;
+      }
+
+      let mut secret = self.prompter.prompt(&format!("Secret name for '{}'", field))?;
+      if secret.is_empty() {
+        self.prompter.say(&style("❌ A secret name is required").red().to_string());
+        continue;
+      }
+
+      // Checked against the targets configured so far
+      if let Some(problem) = check_secret_name(&context.config, DEFAULT_PROFILE, &secret) {
+        self.prompter.say(&style(format!("❌ {}", problem.message)).red().to_string());
+        if !self.prompter.confirm(&format!("Use {} instead?", problem.suggestion))? {
+          continue;
+        }
+        secret = problem.suggestion;
+      }
+
+      self.prompter.say(
+        &style(format!("✅ Added mapping: {} → {}", field, secret)).green().to_string()
+      );
+      mappings.insert(field, secret);
+    }
+
+    if !mappings.is_empty() {
+      context.config.credentials.field_mappings = mappings;
+    }
+
+    Ok(())
+  }
+
+  async fn validate(&self, context: &SetupContext) -> Result<bool> {
+    Ok(
+      context.config.credentials.field_mappings
+        .iter()
+        .all(|(field, secret)| !field.is_empty() && !secret.is_empty())
+    )
+  }
+}
+
+/// Install and start the sync daemon
+pub struct DaemonStep {
+  pub prompter: Arc<dyn Prompter>,
+}
+
+#[async_trait]
+impl SetupStep for DaemonStep {
+  fn step_id(&self) -> &str {
+    "daemon"
+  }
+
+  fn description(&self) -> &str {
+    "Install the sync daemon"
+  }
+
+  fn can_skip(&self, context: &SetupContext) -> bool {
+    context.get_flag(DAEMON_RUNNING)
+  }
+
+  async fn check_prerequisites(&self, _context: &SetupContext) -> Result<bool> {
+    Ok(true)
+  }
+
+  async fn execute(&self, _context: &mut SetupContext) -> Result<()> {
+    if self.prompter.confirm("Would you like to install and start the sync daemon?")? {
+      SystemdManager::new()?.install().await?;
+      self.prompter.say(&style("✅ Service installed and started").green().to_string());
+    }
+
+    Ok(())
+  }
+
+  async fn validate(&self, _context: &SetupContext) -> Result<bool> {
+    Ok(true)
+  }
+
+  fn next_steps(&self, _context: &SetupContext) -> Vec<String> {
+    vec![
+      "View status: claude-code status".to_string(),
+      "Manual sync: claude-code sync".to_string(),
+      "Real-time timer: claude-code timer".to_string()
+    ]
+  }
+}
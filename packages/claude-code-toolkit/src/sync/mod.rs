@@ -8,7 +8,7 @@
 //! ## Core Functionality
 //!
 //! - **Intelligent Sync**: Only syncs when credentials change or targets are missing secrets
-//! - **Multi-Target Support**: Sync to multiple GitHub organizations and repositories
+//! - **Multi-Target Support**: Sync to any mix of targets across registered providers
 //! - **State Tracking**: Maintains sync history and prevents unnecessary operations
 //! - **Error Handling**: Robust error reporting and partial failure recovery
 //! - **Configuration-Driven**: Uses YAML configuration for flexible target management
@@ -84,9 +84,9 @@
 //!
 //! The sync service implements intelligent change detection:
 //!
-//! 1. **Token Change Detection**: Compares current access token with last known state
+//! 1. **Token Change Detection**: Compares each profile's access token with last known state
 //! 2. **Secret Validation**: Checks if required secrets exist in target repositories/organizations
-//! 3. **State Persistence**: Saves sync state to `~/.goodiebag/sync-state.json`
+//! 3. **State Persistence**: Saves sync state to `sync-state.json` in the state directory
 //! 4. **Incremental Updates**: Only syncs when changes are detected
 //!
 //! ## Configuration Integration
@@ -94,13 +94,20 @@
 //! The service reads sync targets from YAML configuration:
 //!
 //! ```yaml
-//! github:
-//!   organizations:
-//!     - name: my-org
-//!       secret_name: CLAUDE_CODE_TOKEN
-//!   repositories:
-//!     - repo: owner/repository
-//!       secret_name: CUSTOM_TOKEN_NAME
+//! providers:
+//!   aws:
+//!     region: eu-west-1
+//! targets:
+//!   - provider: github
+//!     kind: organization
+//!     name: my-org
+//!   - provider: github
+//!     kind: repository
+//!     name: owner/repository
+//!   - provider: aws
+//!     kind: secret
+//!     name: claude/credentials
+//!     profile: team # credentials from profiles.team instead of the credentials section
 //! ```
 //!
 //! ## Error Handling
@@ -112,17 +119,60 @@
 //! - State consistency maintained even during partial failures
 
 use crate::config::{ credentials::CredentialsManager, manager::ConfigurationManager };
-use crate::error::Result;
+use crate::error::{ ClaudeCodeError, Result };
 use crate::providers::registry::ProviderRegistry;
 use crate::traits::config::ConfigManager;
 use crate::traits::{ Credentials, SecretManager, SecretMapping, SyncResult };
+use crate::types::{ Config, DEFAULT_PROFILE, SyncState };
 use std::collections::HashMap;
-use std::path::PathBuf;
 use tracing::{ error, info, warn };
 
+pub mod revoke;
+pub mod state;
+
+/// Targets that receive the same credential profile through the same secret mapping
+struct SyncGroup {
+  profile: String,
+  mapping: SecretMapping,
+  targets: Vec<crate::traits::Target>,
+}
+
+/// What a sync pass did to one target, recorded in the sync state after the pass
+struct TargetOutcome<'a> {
+  target: &'a crate::traits::Target,
+  /// Whether the secrets were pushed to the target at all
+  pushed: bool,
+  /// Names of the written secrets, or why the target failed
+  result: std::result::Result<&'a [String], String>,
+}
+
+impl TargetOutcome<'_> {
+  fn record(&self, sync_state: &mut SyncState, now: i64) {
+    let target = self.target;
+    let status = sync_state.target_mut(&target.provider, &target.target_type, &target.name);
+    if self.pushed {
+      status.last_sync_time = now;
+      status.options = target.config.clone();
+    }
+    match &self.result {
+      Ok(written) => {
+        status.last_sync_status = crate::types::SyncStatus::Success;
+        status.last_error = None;
+        // Only fully written secrets are tracked, so pruning never deletes foreign ones
+        status.secrets.extend(written.iter().cloned());
+        status.secrets.sort();
+        status.secrets.dedup();
+      }
+      Err(error) => {
+        status.last_sync_status = crate::types::SyncStatus::Failure;
+        status.last_error = Some(error.clone());
+      }
+    }
+  }
+}
+
 /// High-level synchronization service
 pub struct SyncService {
-  credentials_manager: CredentialsManager,
   config_manager: ConfigurationManager,
   provider_registry: ProviderRegistry,
 }
@@ -130,165 +180,220 @@ pub struct SyncService {
 impl SyncService {
   pub fn new() -> Result<Self> {
     Ok(Self {
-      credentials_manager: CredentialsManager::new()?,
       config_manager: ConfigurationManager::with_yaml_provider()?,
       provider_registry: ProviderRegistry::new(),
     })
   }
 
+  /// Create a sync service, verifying the configuration can be loaded
+  ///
+  /// Credential files are resolved per profile from the configuration at sync time.
   pub async fn new_with_config() -> Result<Self> {
     let config_manager = ConfigurationManager::with_yaml_provider()?;
-    let config = config_manager.load().await?;
-
-    // Expand tilde in path
-    let expanded_path = shellexpand::tilde(&config.credentials.file_path);
-    let credentials_path = PathBuf::from(expanded_path.as_ref());
+    config_manager.load().await?;
 
     Ok(Self {
-      credentials_manager: CredentialsManager::with_path(credentials_path),
       config_manager,
       provider_registry: ProviderRegistry::new(),
     })
   }
 
+  /// Sync with `config` instead of loading the configuration files every time
+  ///
+  /// The daemon pins each configuration it has validated, see [`ConfigurationManager::reload`].
+  pub fn pin_config(&mut self, config: Config) {
+    self.config_manager.pin(config);
+  }
+
   /// Initialize providers from configuration
   pub async fn initialize(&mut self) -> Result<()> {
     let config = self.config_manager.load().await?;
 
-    // Initialize GitHub provider if we have GitHub targets
-    if !config.github.organizations.is_empty() || !config.github.repositories.is_empty() {
-      let github_config = HashMap::new(); // GitHub provider uses gh CLI, no config needed
-      match self.provider_registry.initialize_provider("github", github_config).await {
-        Ok(()) => info!("Initialized GitHub provider"),
-        Err(e) => warn!("Failed to initialize GitHub provider: {}", e),
+    // Initialize every provider that has at least one target
+    let mut providers: Vec<&str> = config.targets
+      .iter()
+      .map(|t| t.provider.as_str())
+      .collect();
+    providers.sort();
+    providers.dedup();
+
+    for provider in providers {
+      let provider_config = config.providers.get(provider).cloned().unwrap_or_default();
+      match self.provider_registry.initialize_provider(provider, provider_config).await {
+        Ok(()) => info!("Initialized {} provider", provider),
+        Err(e) => warn!("Failed to initialize {} provider: {}", provider, e),
       }
     }
 
     Ok(())
   }
 
-  /// Convert Claude credentials to our generic format
-  async fn get_credentials(&self) -> Result<Credentials> {
-    let claude_creds = self.credentials_manager.read_credentials().await?;
-    let config = self.config_manager.load().await?;
-
-    // Convert credential structure to generic map
-    let cred_value = serde_json::to_value(&claude_creds)?;
-    let oauth_obj = cred_value
-      .get(&config.credentials.json_path)
-      .and_then(|v| v.as_object())
+  /// Read a credential profile's object into our generic format
+  async fn get_credentials(&self, config: &Config, profile: &str) -> Result<Credentials> {
+    let profile_config = config
+      .profile(profile)
       .ok_or_else(|| {
-        crate::error::ClaudeCodeError::InvalidCredentials(
-          format!("Could not find '{}' in credentials file", config.credentials.json_path)
-        )
+        ClaudeCodeError::InvalidConfig(format!("Unknown credential profile '{}'", profile))
       })?;
+    let credential_obj = CredentialsManager::from_config(profile_config)
+      .read_credential_object(&profile_config.json_path).await?;
 
-    // Build credential data dynamically based on configured mappings
-    let mut credential_data = HashMap::new();
-    for field_name in config.credentials.field_mappings.keys() {
-      if let Some(value) = oauth_obj.get(field_name) {
-        // Convert JSON value to string
-        let string_value = match value {
-          serde_json::Value::String(s) => s.clone(),
-          serde_json::Value::Number(n) => n.to_string(),
-          serde_json::Value::Bool(b) => b.to_string(),
-          _ => value.to_string(),
-        };
-        credential_data.insert(field_name.clone(), string_value);
-      }
-    }
-
-    // For backward compatibility, try to extract common fields if they exist
-    let access_token = credential_data
-      .get("access_token")
-      .or_else(|| credential_data.get("accessToken"))
-      .cloned()
-      .unwrap_or_default();
-
-    let refresh_token = credential_data
-      .get("refresh_token")
-      .or_else(|| credential_data.get("refreshToken"))
-      .cloned();
-
-    let expires_at = credential_data
-      .get("expires_at")
-      .or_else(|| credential_data.get("expiresAt"))
-      .and_then(|s| s.parse::<i64>().ok());
-
-    // Store all discovered fields in metadata for generic access
-    let metadata = credential_data;
-
-    Ok(Credentials {
-      access_token,
-      refresh_token,
-      expires_at,
-      metadata,
-    })
+    // Collect every mapped field (nested paths included) for generic access
+    Ok(Credentials::from_source(credential_obj, profile_config.field_mappings.keys()))
   }
 
-  /// Create secret mapping from configuration
-  async fn get_secret_mapping(&self) -> Result<SecretMapping> {
-    let config = self.config_manager.load().await?;
-
-    let mut mapping = SecretMapping::new("claude");
-
-    // Use the field mappings from credentials configuration
-    for (field, secret_name) in &config.credentials.field_mappings {
-      mapping.add_mapping(field, secret_name);
+  /// Read the credentials of every profile used by a target
+  ///
+  /// Profiles whose credentials cannot be read are logged and left out.
+  async fn get_profile_credentials(
+    &self,
+    config: &Config,
+    groups: &[SyncGroup]
+  ) -> HashMap<String, Result<Credentials>> {
+    let mut credentials = HashMap::new();
+    for group in groups {
+      if !credentials.contains_key(&group.profile) {
+        let result = self.get_credentials(config, &group.profile).await;
+        if let Err(e) = &result {
+          error!("Failed to read credentials for profile {}: {}", group.profile, e);
+        }
+        credentials.insert(group.profile.clone(), result);
+      }
     }
-
-    Ok(mapping)
+    credentials
   }
 
-  /// Get targets from configuration
-  async fn get_targets_from_config(&self) -> Result<Vec<crate::traits::Target>> {
-    let config = self.config_manager.load().await?;
-    let mut targets = Vec::new();
-
-    // Add organizations
-    for org in &config.github.organizations {
-      targets.push(crate::traits::Target {
-        provider: "github".to_string(),
-        target_type: "organization".to_string(),
-        name: org.name.clone(),
-        config: HashMap::new(),
-      });
-    }
-
-    // Add repositories
-    for repo in &config.github.repositories {
-      targets.push(crate::traits::Target {
-        provider: "github".to_string(),
-        target_type: "repository".to_string(),
-        name: repo.repo.clone(),
-        config: HashMap::new(),
-      });
+  /// Get targets from configuration, grouped by profile and effective secret mapping
+  ///
+  /// Each target's mapping overrides are layered over its profile's mapping; targets that
+  /// end up with the same profile and mapping share one sync pass.
+  fn get_targets_from_config(&self, config: &Config) -> Vec<SyncGroup> {
+    let mut groups: Vec<SyncGroup> = Vec::new();
+    for target in &config.targets {
+      let profile = target.profile_name();
+      let Some(profile_config) = config.profile(profile) else {
+        warn!("Target {} uses unknown profile {}, skipping", target, profile);
+        continue;
+      };
+
+      let mapping = SecretMapping::from_config(profile_config).with_overrides(&target.mapping);
+      let sync_target = crate::traits::Target {
+        provider: target.provider.clone(),
+        target_type: target.kind.clone(),
+        name: target.name.clone(),
+        config: target.options.clone(),
+      };
+
+      match groups.iter_mut().find(|g| g.profile == profile && g.mapping == mapping) {
+        Some(group) => group.targets.push(sync_target),
+        None =>
+          groups.push(SyncGroup {
+            profile: profile.to_string(),
+            mapping,
+            targets: vec![sync_target],
+          }),
+      }
     }
 
-    info!("Found {} targets for sync", targets.len());
-    Ok(targets)
+    info!("Found {} targets for sync", config.targets.len());
+    groups
   }
 
   /// Perform complete credential synchronization
   pub async fn sync_all(&mut self) -> Result<SyncResult> {
     info!("Starting credential synchronization");
 
+    if let Some(revoked_at) = state::load().revoked_at {
+      return Err(ClaudeCodeError::Generic(disarmed_message(revoked_at)));
+    }
+
     // Initialize providers
     self.initialize().await?;
 
-    // Get credentials and mapping
-    let credentials = self.get_credentials().await?;
-    let mapping = self.get_secret_mapping().await?;
+    let config = self.config_manager.load().await?;
+    let groups = self.get_targets_from_config(&config);
+    let credentials = self.get_profile_credentials(&config, &groups).await;
+    let now = chrono::Utc::now().timestamp();
+
+    // Perform sync target by target, so each target's status and secrets are tracked
+    let mut result = SyncResult {
+      succeeded: 0,
+      failed: 0,
+      errors: Vec::new(),
+    };
+    let prepared: Vec<_> = groups
+      .iter()
+      .map(|group| {
+        // A profile without credentials or a template that does not render fails every target
+        match &credentials[&group.profile] {
+          Ok(profile_credentials) =>
+            group.mapping
+              .to_secrets(profile_credentials)
+              .map(|secrets| {
+                let names: Vec<String> = secrets
+                  .into_iter()
+                  .map(|s| s.name)
+                  .collect();
+                (profile_credentials, names)
+              })
+              .map_err(|e| format!("Profile {}: {}", group.profile, e)),
+          Err(e) => Err(format!("Profile {}: {}", group.profile, e)),
+        }
+      })
+      .collect();
+
+    let mut outcomes = Vec::new();
+    let mut disarmed = None;
+    'groups: for (group, prepared) in groups.iter().zip(&prepared) {
+      let (profile_credentials, secret_names) = match prepared {
+        Ok(prepared) => prepared,
+        Err(error) => {
+          outcomes.extend(
+            group.targets.iter().map(|target| TargetOutcome {
+              target,
+              pushed: false,
+              result: Err(error.clone()),
+            })
+          );
+          result.failed += group.targets.len();
+          result.errors.push(error.clone());
+          continue;
+        }
+      };
+
+      for target in &group.targets {
+        // A revoke may have disarmed syncing while earlier targets were written
+        if let Some(revoked_at) = state::load().revoked_at {
+          disarmed = Some(revoked_at);
+          break 'groups;
+        }
 
-    // Get targets from configuration
-    let targets = self.get_targets_from_config().await?;
+        let mut target_result = self.provider_registry.sync_credentials_to_targets(
+          profile_credentials,
+          &group.mapping,
+          std::slice::from_ref(target)
+        ).await?;
+        if target_result.succeeded == 0 && target_result.failed == 0 && !secret_names.is_empty() {
+          target_result.failed += 1;
+          target_result.errors.push(
+            format!("{} - provider {} is not initialized", target, target.provider)
+          );
+        }
 
-    // Perform sync
-    let result = self.provider_registry.sync_credentials_to_targets(
-      &credentials,
-      &mapping,
-      &targets
-    ).await?;
+        outcomes.push(TargetOutcome {
+          target,
+          pushed: true,
+          result: if target_result.failed == 0 {
+            Ok(secret_names.as_slice())
+          } else {
+            Err(target_result.errors.join("; "))
+          },
+        });
+        result.succeeded += target_result.succeeded;
+        result.failed += target_result.failed;
+        result.errors.extend(target_result.errors);
+      }
+    }
 
     info!("Sync completed: {} succeeded, {} failed", result.succeeded, result.failed);
 
@@ -298,10 +403,162 @@ impl SyncService {
       }
     }
 
-    // Save sync state if successful
-    if result.succeeded > 0 && result.failed == 0 {
-      self.save_sync_state(&credentials).await?;
+    // Merge into the state as it is now, so a concurrent revoke is never undone
+    let complete = disarmed.is_none() && result.succeeded > 0 && result.failed == 0;
+    let revoked_at = state::update(|sync_state| {
+      for outcome in &outcomes {
+        outcome.record(sync_state, now);
+      }
+
+      // Record the synced tokens only if everything succeeded, so failures are retried
+      if complete && sync_state.revoked_at.is_none() {
+        let mut tokens: HashMap<String, String> = credentials
+          .iter()
+          .filter_map(|(profile, c)| {
+            c.as_ref()
+              .ok()
+              .map(|c| (profile.clone(), c.access_token.clone()))
+          })
+          .collect();
+        sync_state.last_sync = now;
+        sync_state.last_token = tokens.remove(DEFAULT_PROFILE).unwrap_or_default();
+        sync_state.profile_tokens = tokens;
+      }
+      sync_state.revoked_at
+    })?;
+
+    if let Some(revoked_at) = disarmed.or(revoked_at) {
+      return Err(ClaudeCodeError::Generic(disarmed_message(revoked_at)));
     }
+    Ok(result)
+  }
+
+  /// Tracked secrets that no configured target should receive any more
+  ///
+  /// Returns each affected target with the secret names to delete: every tracked secret
+  /// of targets that were removed from the configuration, and secrets whose name is no
+  /// longer produced by a target's mapping (e.g. after renaming a field mapping). Only the
+  /// configuration file counts, so a project file or variable cannot make targets look
+  /// removed.
+  pub async fn stale_secrets(&self) -> Result<Vec<(crate::traits::Target, Vec<String>)>> {
+    let config = self.config_manager.load_file().await?;
+    let sync_state = state::load();
+
+    let mut stale = Vec::new();
+    for status in &sync_state.targets {
+      let configured = config.targets
+        .iter()
+        .find(|t| t.matches(&status.provider, &status.target_type, &status.name));
+      let wanted = configured
+        .and_then(|t| {
+          config
+            .profile(t.profile_name())
+            .map(|p| SecretMapping::from_config(p).with_overrides(&t.mapping).secret_names())
+        })
+        .unwrap_or_default();
+
+      let names: Vec<String> = status.secrets
+        .iter()
+        .filter(|name| !wanted.contains(name))
+        .cloned()
+        .collect();
+      if names.is_empty() {
+        continue;
+      }
+
+      let target = crate::traits::Target {
+        provider: status.provider.clone(),
+        target_type: status.target_type.clone(),
+        name: status.name.clone(),
+        config: configured.map(|t| t.options.clone()).unwrap_or_else(|| status.options.clone()),
+      };
+      stale.push((target, names));
+    }
+
+    Ok(stale)
+  }
+
+  /// Delete every stale secret (see [`Self::stale_secrets`])
+  pub async fn prune(&mut self) -> Result<SyncResult> {
+    self.prune_matching(|_| true).await
+  }
+
+  /// Delete the stale secrets of one target, typically right after removing it
+  pub async fn purge_target(
+    &mut self,
+    provider: &str,
+    kind: &str,
+    name: &str
+  ) -> Result<SyncResult> {
+    self.prune_matching(|t| t.provider == provider && t.target_type == kind && t.name == name).await
+  }
+
+  async fn prune_matching(
+    &mut self,
+    filter: impl Fn(&crate::traits::Target) -> bool
+  ) -> Result<SyncResult> {
+    let config = self.config_manager.load_file().await?;
+    let stale: Vec<_> = self
+      .stale_secrets().await?
+      .into_iter()
+      .filter(|(target, _)| filter(target))
+      .collect();
+
+    let mut result = SyncResult {
+      succeeded: 0,
+      failed: 0,
+      errors: Vec::new(),
+    };
+    if stale.is_empty() {
+      info!("No stale secrets to prune");
+      return Ok(result);
+    }
+
+    // Removed targets may belong to providers that no longer have configured targets
+    let mut providers: Vec<&str> = stale
+      .iter()
+      .map(|(t, _)| t.provider.as_str())
+      .collect();
+    providers.sort();
+    providers.dedup();
+    for provider in providers {
+      let provider_config = config.providers.get(provider).cloned().unwrap_or_default();
+      if let Err(e) = self.provider_registry.initialize_provider(provider, provider_config).await {
+        warn!("Failed to initialize {} provider: {}", provider, e);
+      }
+    }
+
+    let mut pruned = Vec::new();
+    for (target, names) in &stale {
+      let target_result = self.provider_registry.delete_secrets_from_targets(
+        names,
+        std::slice::from_ref(target)
+      ).await?;
+
+      if target_result.failed == 0 {
+        info!("Pruned {} from {}:{}", names.join(", "), target.target_type, target.name);
+        pruned.push((target, names));
+      }
+
+      result.succeeded += target_result.succeeded;
+      result.failed += target_result.failed;
+      result.errors.extend(target_result.errors);
+    }
+
+    state::update(|sync_state| {
+      for (target, names) in &pruned {
+        let status = sync_state.target_mut(&target.provider, &target.target_type, &target.name);
+        status.secrets.retain(|name| !names.contains(name));
+      }
+
+      // Forget removed targets once nothing of ours is left on them
+      sync_state.targets.retain(|status| {
+        !status.secrets.is_empty() ||
+          config.targets
+            .iter()
+            .any(|t| t.matches(&status.provider, &status.target_type, &status.name))
+      });
+    })?;
 
     Ok(result)
   }
@@ -313,7 +570,14 @@ impl SyncService {
   }
 
   /// Check if sync is needed and perform sync if required
-  pub async fn check_and_sync_if_needed(&mut self) -> Result<()> {
+  ///
+  /// Returns the sync result, or `None` if nothing was synced.
+  pub async fn check_and_sync_if_needed(&mut self) -> Result<Option<SyncResult>> {
+    if let Some(revoked_at) = state::load().revoked_at {
+      warn!("{}", disarmed_message(revoked_at));
+      return Ok(None);
+    }
+
     if self.is_sync_needed().await? {
       let result = self.sync_all().await?;
       if result.failed > 0 {
@@ -321,32 +585,40 @@ impl SyncService {
       } else {
         info!("Sync completed successfully: {} targets", result.succeeded);
       }
+      Ok(Some(result))
     } else {
       info!("Credentials are already up to date, no sync needed");
+      Ok(None)
     }
-    Ok(())
   }
 
-  /// Check if sync is needed (token has changed or secrets are missing)
+  /// Check if sync is needed (a profile's token has changed or secrets are missing)
   pub async fn is_sync_needed(&self) -> Result<bool> {
-    let credentials = self.get_credentials().await?;
-    let mapping = self.get_secret_mapping().await?;
-    let secrets = mapping.to_secrets(&credentials);
+    let config = self.config_manager.load().await?;
+    let groups = self.get_targets_from_config(&config);
+    // Unreadable profiles are logged and skipped, so they do not keep the others from syncing
+    let credentials: HashMap<String, Credentials> = self
+      .get_profile_credentials(&config, &groups).await
+      .into_iter()
+      .filter_map(|(profile, result)| result.ok().map(|c| (profile, c)))
+      .collect();
 
     // First check if credentials have changed
-    let state_path = std::path::Path
-      ::new(&std::env::var("HOME").unwrap_or_default())
-      .join(".goodiebag")
-      .join("sync-state.json");
-
-    let token_changed = if let Ok(state_data) = std::fs::read_to_string(&state_path) {
-      if let Ok(last_state) = serde_json::from_str::<crate::types::SyncState>(&state_data) {
-        last_state.last_token != credentials.access_token
-      } else {
-        true // Invalid state file
-      }
-    } else {
-      true // No state file
+    let last_state = std::fs
+      ::read_to_string(state::state_path())
+      .ok()
+      .and_then(|data| serde_json::from_str::<crate::types::SyncState>(&data).ok());
+    let token_changed = match last_state {
+      Some(last_state) =>
+        credentials.iter().any(|(profile, c)| {
+          let last_token = if profile == DEFAULT_PROFILE {
+            Some(&last_state.last_token)
+          } else {
+            last_state.profile_tokens.get(profile)
+          };
+          last_token != Some(&c.access_token)
+        }),
+      None => true, // No or invalid state file
     };
 
     if token_changed {
@@ -355,40 +627,45 @@ impl SyncService {
     }
 
     // Check if all required secrets exist in GitHub
-    let targets = self.get_targets_from_config().await?;
-    for target in &targets {
-      for secret in &secrets {
-        let args = match target.target_type.as_str() {
-          "repository" => vec!["secret", "list", "--repo", &target.name],
-          "organization" => vec!["secret", "list", "--org", &target.name],
-          _ => {
-            continue;
-          }
-        };
-
-        let check_result = std::process::Command::new("gh").args(&args).output();
-
-        match check_result {
-          Ok(output) if output.status.success() => {
-            let secret_list = String::from_utf8_lossy(&output.stdout);
-            if !secret_list.contains(&secret.name) {
-              info!(
-                "Secret {} missing from {} {}, sync needed",
-                secret.name,
+    for group in &groups {
+      let Some(profile_credentials) = credentials.get(&group.profile) else {
+        continue;
+      };
+      let secrets = group.mapping.to_secrets(profile_credentials)?;
+      for target in group.targets.iter().filter(|t| t.provider == "github") {
+        for secret in &secrets {
+          let args = match target.target_type.as_str() {
+            "repository" => vec!["secret", "list", "--repo", &target.name],
+            "organization" => vec!["secret", "list", "--org", &target.name],
+            _ => {
+              continue;
+            }
+          };
+
+          let check_result = std::process::Command::new("gh").args(&args).output();
+
+          match check_result {
+            Ok(output) if output.status.success() => {
+              let secret_list = String::from_utf8_lossy(&output.stdout);
+              if !secret_list.contains(&secret.name) {
+                info!(
+                  "Secret {} missing from {} {}, sync needed",
+                  secret.name,
+                  target.target_type,
+                  target.name
+                );
+                return Ok(true);
+              }
+            }
+            _ => {
+              warn!(
+                "Could not check secrets for {} {}, assuming sync needed",
                 target.target_type,
                 target.name
               );
               return Ok(true);
             }
           }
-          _ => {
-            warn!(
-              "Could not check secrets for {} {}, assuming sync needed",
-              target.target_type,
-              target.name
-            );
-            return Ok(true);
-          }
         }
       }
     }
@@ -397,39 +674,19 @@ impl SyncService {
     Ok(false)
   }
 
-  /// Save sync state after successful sync
-  async fn save_sync_state(&self, credentials: &Credentials) -> Result<()> {
-    let state_dir = std::path::Path
-      ::new(&std::env::var("HOME").unwrap_or_default())
-      .join(".goodiebag");
-
-    // Create directory if it doesn't exist
-    std::fs
-      ::create_dir_all(&state_dir)
-      .map_err(|e| {
-        crate::error::ClaudeCodeError::Generic(format!("Failed to create state directory: {}", e))
-      })?;
-
-    let state_path = state_dir.join("sync-state.json");
-    let sync_state = crate::types::SyncState {
-      last_sync: chrono::Utc::now().timestamp(),
-      last_token: credentials.access_token.clone(),
-      targets: Vec::new(), // TODO: Add target status tracking
-    };
-
-    let state_json = serde_json::to_string_pretty(&sync_state)?;
-    std::fs
-      ::write(&state_path, state_json)
-      .map_err(|e| {
-        crate::error::ClaudeCodeError::Generic(format!("Failed to save sync state: {}", e))
-      })?;
-
-    info!("Saved sync state to {:?}", state_path);
-    Ok(())
-  }
-
   /// Get sync status
   pub async fn get_sync_status(&self) -> Result<HashMap<String, bool>> {
     self.provider_registry.validate_targets().await
   }
 }
+
+fn disarmed_message(revoked_at: i64) -> String {
+  let revoked_at = chrono::DateTime
+    ::from_timestamp(revoked_at, 0)
+    .unwrap_or_default()
+    .format("%Y-%m-%d %H:%M:%S UTC");
+  format!(
+    "Sync is disarmed since a revoke at {}; run 'claude-code-toolkit rearm' to resume",
+    revoked_at
+  )
+}
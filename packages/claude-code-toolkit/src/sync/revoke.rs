@@ -0,0 +1,265 @@
+//! Emergency revocation of every synced secret
+//!
+//! `revoke` first disarms syncing (recorded in the sync state, so the daemon stops
+//! re-syncing immediately), then deletes or tombstones every secret recorded in the sync
+//! state, on every target it was written to. Syncing stays disarmed until
+//! [`SyncService::rearm`].
+
+use super::{ SyncService, state };
+use crate::error::{ ClaudeCodeError, Result };
+use crate::traits::{ Secret, SecretManager, SyncResult, Target };
+use crate::types::{ Config, SyncState };
+use serde::Serialize;
+use tracing::{ info, warn };
+
+/// Value written over secrets when revoking with tombstones
+pub const TOMBSTONE: &str = "REVOKED";
+
+/// What `revoke` did, target by target
+#[derive(Debug, Clone, Serialize)]
+pub struct RevokeReport {
+  pub revoked_at: i64,
+  /// Secrets were overwritten with [`TOMBSTONE`] instead of deleted
+  pub tombstone: bool,
+  pub targets: Vec<RevokedTarget>,
+  /// Configured targets without recorded secrets, which may still hold live ones
+  pub untracked: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RevokedTarget {
+  pub target: String,
+  pub removed: Vec<String>,
+  pub failed: Vec<String>,
+  pub errors: Vec<String>,
+}
+
+impl RevokeReport {
+  pub fn removed_count(&self) -> usize {
+    self.targets
+      .iter()
+      .map(|t| t.removed.len())
+      .sum()
+  }
+
+  pub fn failed_count(&self) -> usize {
+    self.targets
+      .iter()
+      .map(|t| t.failed.len())
+      .sum()
+  }
+}
+
+/// Every target holding synced secrets, with the secret names to revoke
+///
+/// Only secrets recorded in the sync state are revoked: a same-named secret the toolkit
+/// never wrote is left alone. Targets still configured use their current options.
+fn revoke_plan(config: &Config, sync_state: &SyncState) -> Vec<(Target, Vec<String>)> {
+  let mut plan: Vec<(Target, Vec<String>)> = Vec::new();
+  for status in sync_state.targets.iter().filter(|s| !s.secrets.is_empty()) {
+    let options = config.targets
+      .iter()
+      .find(|t| t.matches(&status.provider, &status.target_type, &status.name))
+      .map_or_else(|| status.options.clone(), |t| t.options.clone());
+    let target = Target {
+      provider: status.provider.clone(),
+      target_type: status.target_type.clone(),
+      name: status.name.clone(),
+      config: options,
+    };
+
+    let mut names = status.secrets.clone();
+    names.sort();
+    names.dedup();
+    plan.push((target, names));
+  }
+  plan
+}
+
+/// Configured targets the sync state records no secrets for
+///
+/// State written before secrets were tracked per target lists none, so whatever such a
+/// target holds has to be revoked by hand.
+fn untracked_targets(config: &Config, sync_state: &SyncState) -> Vec<String> {
+  config.targets
+    .iter()
+    .filter(|t| {
+      !sync_state.targets
+        .iter()
+        .any(|s| s.matches(&t.provider, &t.kind, &t.name) && !s.secrets.is_empty())
+    })
+    .map(|t| t.to_string())
+    .collect()
+}
+
+impl SyncService {
+  /// Disarm syncing, then delete (or tombstone) every synced secret everywhere
+  pub async fn revoke(&mut self, tombstone: bool) -> Result<RevokeReport> {
+    let revoked_at = chrono::Utc::now().timestamp();
+
+    // Disarm before touching any target, so a running daemon cannot re-sync in between
+    let sync_state = state::update(|sync_state| {
+      sync_state.revoked_at = Some(revoked_at);
+      sync_state.last_token.clear();
+      sync_state.profile_tokens.clear();
+      sync_state.clone()
+    })?;
+    warn!("Sync disarmed, revoking all synced secrets");
+
+    // The configuration file alone, as for pruning. A broken or missing one must not stop
+    // a revoke: the sync state records the provider and options of every target
+    let config_path = self.config_manager.config_path().to_path_buf();
+    let config = if config_path.is_file() {
+      self.config_manager.load_file().await
+    } else {
+      Err(ClaudeCodeError::InvalidConfig(format!("{:?} does not exist", config_path)))
+    };
+    let config = config.unwrap_or_else(|e| {
+      warn!("Revoking with the targets recorded in the sync state only: {}", e);
+      Config::default()
+    });
+    let plan = revoke_plan(&config, &sync_state);
+    let untracked = untracked_targets(&config, &sync_state);
+    for target in &untracked {
+      warn!("No synced secrets are recorded for {}; revoke its secrets by hand", target);
+    }
+
+    let mut providers: Vec<&str> = plan
+      .iter()
+      .map(|(t, _)| t.provider.as_str())
+      .collect();
+    providers.sort();
+    providers.dedup();
+    for provider in providers {
+      let provider_config = config.providers.get(provider).cloned().unwrap_or_default();
+      if let Err(e) = self.provider_registry.initialize_provider(provider, provider_config).await {
+        warn!("Failed to initialize {} provider: {}", provider, e);
+      }
+    }
+
+    let mut report = RevokeReport {
+      revoked_at,
+      tombstone,
+      targets: Vec::new(),
+      untracked,
+    };
+    for (target, names) in &plan {
+      let mut revoked = RevokedTarget {
+        target: target.to_string(),
+        removed: Vec::new(),
+        failed: Vec::new(),
+        errors: Vec::new(),
+      };
+
+      // One secret at a time, so the report says exactly what is gone
+      for name in names {
+        let result = if tombstone {
+          self.tombstone_secret(name, target).await
+        } else {
+          self.provider_registry.delete_secrets_from_targets(
+            std::slice::from_ref(name),
+            std::slice::from_ref(target)
+          ).await
+        };
+
+        match result {
+          Ok(result) if result.failed == 0 && result.succeeded > 0 => {
+            revoked.removed.push(name.clone());
+          }
+          Ok(result) => {
+            revoked.failed.push(name.clone());
+            revoked.errors.extend(result.errors);
+          }
+          Err(e) => {
+            revoked.failed.push(name.clone());
+            revoked.errors.push(e.to_string());
+          }
+        }
+      }
+
+      report.targets.push(revoked);
+    }
+
+    // Deleted secrets are no longer ours; tombstones still are
+    if !tombstone {
+      state::update(|sync_state| {
+        for ((target, _), revoked) in plan.iter().zip(&report.targets) {
+          let status = sync_state.target_mut(&target.provider, &target.target_type, &target.name);
+          status.secrets.retain(|name| !revoked.removed.contains(name));
+        }
+      })?;
+    }
+    info!(
+      "Revoke completed: {} removed, {} failed",
+      report.removed_count(),
+      report.failed_count()
+    );
+
+    Ok(report)
+  }
+
+  async fn tombstone_secret(&self, name: &str, target: &Target) -> Result<SyncResult> {
+    let Some(provider) = self.provider_registry.get_provider(&target.provider) else {
+      return Err(
+        ClaudeCodeError::Provider(
+          format!("{} - provider {} is not initialized", target, target.provider)
+        )
+      );
+    };
+
+    let secret = Secret {
+      name: name.to_string(),
+      value: TOMBSTONE.to_string(),
+      description: Some("Revoked".to_string()),
+    };
+    provider.sync_secrets(std::slice::from_ref(&secret), std::slice::from_ref(target)).await
+  }
+
+  /// Re-enable syncing after a revoke; returns false if syncing was not disarmed
+  pub fn rearm() -> Result<bool> {
+    let mut sync_state = state::load();
+    if sync_state.revoked_at.take().is_none() {
+      return Ok(false);
+    }
+
+    state::save(&sync_state)?;
+    info!("Sync re-armed");
+    Ok(true)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::types::TargetConfig;
+
+  #[test]
+  fn test_revoke_plan_covers_tracked_secrets_only() {
+    let mut config = Config::default();
+    config.credentials.field_mappings.insert("accessToken".to_string(), "TOKEN".to_string());
+    let mut target = TargetConfig::new("github", "organization", "org");
+    target.options.insert("visibility".to_string(), "private".to_string());
+    config.targets.push(target);
+    config.targets.push(TargetConfig::new("github", "repository", "owner/untouched"));
+
+    let mut sync_state = SyncState::default();
+    sync_state.target_mut("github", "organization", "org").secrets.push("OLD_TOKEN".to_string());
+    sync_state.target_mut("aws", "secret", "gone").secrets.push("token".to_string());
+    sync_state.target_mut("webhook", "endpoint", "never-synced");
+
+    let plan = revoke_plan(&config, &sync_state);
+    assert_eq!(plan[0].0.config["visibility"], "private");
+    assert_eq!(untracked_targets(&config, &sync_state), vec!["github:repository:owner/untouched"]);
+    let plan: Vec<(String, Vec<String>)> = plan
+      .into_iter()
+      .map(|(t, names)| (t.to_string(), names))
+      .collect();
+    assert_eq!(
+      plan,
+      vec![
+        ("github:organization:org".to_string(), vec!["OLD_TOKEN".to_string()]),
+        ("aws:secret:gone".to_string(), vec!["token".to_string()])
+      ]
+    );
+  }
+}
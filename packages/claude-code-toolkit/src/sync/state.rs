@@ -0,0 +1,78 @@
+//! Persistent sync state (`sync-state.json` in the state directory, see
+//! [`crate::utils::paths`])
+//!
+//! Records the access token of every profile at the last sync and, per target, the
+//! secrets this toolkit has written. Pruning only ever deletes secrets listed here.
+
+use crate::error::{ ClaudeCodeError, Result };
+use crate::types::SyncState;
+use std::path::PathBuf;
+use tracing::{ info, warn };
+
+/// Location of the sync state file
+pub fn state_path() -> PathBuf {
+  crate::utils::paths::get().state_file()
+}
+
+/// Load the sync state, or an empty state if there is none yet
+pub fn load() -> SyncState {
+  let path = state_path();
+  let Ok(data) = std::fs::read_to_string(&path) else {
+    return SyncState::default();
+  };
+
+  serde_json::from_str(&data).unwrap_or_else(|e| {
+    warn!("Ignoring invalid sync state {}: {}", path.display(), e);
+    SyncState::default()
+  })
+}
+
+/// Write the sync state
+pub fn save(state: &SyncState) -> Result<()> {
+  let path = state_path();
+  if let Some(dir) = path.parent() {
+    std::fs
+      ::create_dir_all(dir)
+      .map_err(|e| ClaudeCodeError::Generic(format!("Failed to create state directory: {}", e)))?;
+  }
+
+  let state_json = serde_json::to_string_pretty(state)?;
+  std::fs
+    ::write(&path, state_json)
+    .map_err(|e| ClaudeCodeError::Generic(format!("Failed to save sync state: {}", e)))?;
+
+  info!("Saved sync state to {:?}", path);
+  Ok(())
+}
+
+/// Apply `change` to the sync state as it is on disk now, and save it
+///
+/// Operations that talk to targets record their results this way instead of saving a
+/// state loaded before they started, which would undo a `revoke` that ran in between.
+pub fn update<T>(change: impl FnOnce(&mut SyncState) -> T) -> Result<T> {
+  let mut state = load();
+  let value = change(&mut state);
+  save(&state)?;
+  Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_legacy_state_gains_tracked_secrets() {
+    let legacy =
+      r#"{"last_sync":1,"last_token":"t","targets":[{"target_type":"organization","name":"org",
+      "last_sync_time":1,"last_sync_status":"Success","last_error":null}]}"#;
+    let mut state: SyncState = serde_json::from_str(legacy).unwrap();
+
+    let status = state.target_mut("github", "organization", "org");
+    assert!(status.secrets.is_empty());
+    status.secrets.push("CLAUDE_ACCESS_TOKEN".to_string());
+
+    state.target_mut("aws", "secret", "claude").secrets.push("token".to_string());
+    assert_eq!(state.targets.len(), 2);
+    assert_eq!(state.targets[0].secrets, vec!["CLAUDE_ACCESS_TOKEN"]);
+  }
+}
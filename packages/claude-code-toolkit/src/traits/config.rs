@@ -40,8 +40,9 @@ pub trait ConfigManager: Send + Sync {
   /// Save configuration
   async fn save(&self, config: &Config) -> Result<()>;
 
-  /// Update specific configuration section
-  async fn update_section<T>(&self, section: &str, data: T) -> Result<()> where T: Send + Sync;
+  /// Set the value at a dotted path (`daemon.log_level`), validating before saving
+  async fn update_section<T>(&self, section: &str, data: T) -> Result<()>
+    where T: serde::Serialize + Send + Sync;
 
   /// Backup current configuration
   async fn backup(&self) -> Result<String>;
@@ -11,6 +11,7 @@ pub use secrets::{ SecretManager, SecretMapping, SecretProvider };
 pub use setup::{ SetupContext, SetupStep, SetupWizard };
 pub use validation::{ ValidationError, ValidationRule, ValidationService };
 
+use serde::{ Deserialize, Serialize };
 use std::collections::HashMap;
 
 /// Core credential types that all providers work with
@@ -20,10 +21,92 @@ pub struct Credentials {
   pub refresh_token: Option<String>,
   pub expires_at: Option<i64>,
   pub metadata: HashMap<String, String>,
+  /// The raw credential object, used to render secret templates
+  pub source: serde_json::Value,
+}
+
+impl Credentials {
+  /// Build credentials from a raw credential object
+  ///
+  /// The common OAuth fields are read in camelCase or snake_case; `fields` are the
+  /// additional credential fields to collect into `metadata`.
+  pub fn from_source<'a>(
+    source: serde_json::Value,
+    fields: impl IntoIterator<Item = &'a String>
+  ) -> Self {
+    let mut credentials = Self {
+      access_token: String::new(),
+      refresh_token: None,
+      expires_at: None,
+      metadata: HashMap::new(),
+      source,
+    };
+
+    credentials.access_token = credentials.field("accessToken").unwrap_or_default();
+    credentials.refresh_token = credentials.field("refreshToken");
+    credentials.expires_at = credentials
+      .field("expiresAt")
+      .and_then(|s| s.parse::<i64>().ok());
+    credentials.metadata = fields
+      .into_iter()
+      .filter_map(|f| credentials.field(f).map(|value| (f.clone(), value)))
+      .collect();
+
+    credentials
+  }
+
+  /// Serialized value of a credential field
+  ///
+  /// `field` is a dotted path into the credential object (`a.b`, `scopes.0`). A snake_case
+  /// field falls back to its camelCase spelling and vice versa. Strings are returned
+  /// verbatim, numbers and booleans in their JSON form, arrays and objects as compact
+  /// JSON; missing and null fields yield `None`.
+  pub fn field(&self, field: &str) -> Option<String> {
+    let value = [field.to_string(), to_camel_case(field), to_snake_case(field)]
+      .iter()
+      .find_map(|path| crate::utils::template::lookup(&self.source, path));
+
+    match value {
+      Some(serde_json::Value::Null) => None,
+      Some(value) => Some(crate::utils::template::value_to_string(value)),
+      None => self.metadata.get(field).cloned(),
+    }
+  }
+}
+
+fn to_camel_case(field: &str) -> String {
+  let mut camel = String::with_capacity(field.len());
+  let mut upper = false;
+  for c in field.chars() {
+    match c {
+      '_' => {
+        upper = true;
+      }
+      c if upper => {
+        camel.extend(c.to_uppercase());
+        upper = false;
+      }
+      c => camel.push(c),
+    }
+  }
+  camel
+}
+
+fn to_snake_case(field: &str) -> String {
+  let mut snake = String::with_capacity(field.len() + 4);
+  for c in field.chars() {
+    if c.is_ascii_uppercase() {
+      snake.push('_');
+      snake.push(c.to_ascii_lowercase());
+    } else {
+      snake.push(c);
+    }
+  }
+  snake
 }
 
 /// Generic target for secret synchronization
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Target {
   pub provider: String,
   pub target_type: String,
@@ -31,8 +114,14 @@ pub struct Target {
   pub config: HashMap<String, String>,
 }
 
+impl std::fmt::Display for Target {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}:{}:{}", self.provider, self.target_type, self.name)
+  }
+}
+
 /// Result of a sync operation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyncResult {
   pub succeeded: usize,
   pub failed: usize,
@@ -40,7 +129,7 @@ pub struct SyncResult {
 }
 
 /// Generic secret definition
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Secret {
   pub name: String,
   pub value: String,
@@ -1,7 +1,8 @@
 //! Secret management traits
 
 use super::{ Credentials, Secret, SyncResult, Target };
-use crate::error::Result;
+use crate::error::{ ClaudeCodeError, Result };
+use crate::types::{ CredentialsConfig, MappingOverrides };
 use async_trait::async_trait;
 use std::collections::HashMap;
 
@@ -14,6 +15,23 @@ pub trait SecretProvider: Send + Sync {
   /// Sync secrets to targets
   async fn sync_secrets(&self, secrets: &[Secret], targets: &[Target]) -> Result<SyncResult>;
 
+  /// Delete previously synced secrets from targets
+  ///
+  /// Secrets that no longer exist count as deleted. Providers that cannot delete keep
+  /// this default, which fails.
+  async fn delete_secrets(
+    &self,
+    secret_names: &[String],
+    targets: &[Target]
+  ) -> Result<SyncResult> {
+    let _ = (secret_names, targets);
+    Err(
+      ClaudeCodeError::Provider(
+        format!("Provider {} does not support deleting secrets", self.provider_name())
+      )
+    )
+  }
+
   /// Validate access to targets
   async fn validate_access(&self, targets: &[Target]) -> Result<HashMap<String, bool>>;
 
@@ -48,6 +66,13 @@ pub trait SecretManager: Send + Sync {
     targets: &[Target]
   ) -> Result<SyncResult>;
 
+  /// Delete previously synced secrets from specific targets
+  async fn delete_secrets_from_targets(
+    &self,
+    secret_names: &[String],
+    targets: &[Target]
+  ) -> Result<SyncResult>;
+
   /// Validate all configured targets
   async fn validate_targets(&self) -> Result<HashMap<String, bool>>;
 
@@ -56,7 +81,7 @@ pub trait SecretManager: Send + Sync {
 }
 
 /// Mapping between credential fields and secret names
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SecretMapping {
   pub schema_name: String,
   pub mappings: HashMap<String, String>,
@@ -72,6 +97,16 @@ impl SecretMapping {
     }
   }
 
+  /// Mapping described by a credential profile's field mappings and templates
+  pub fn from_config(config: &CredentialsConfig) -> Self {
+    let mut mapping = Self::new("claude");
+    for (field, secret_name) in &config.field_mappings {
+      mapping.add_mapping(field, secret_name);
+    }
+    mapping.templates = config.templates.clone();
+    mapping
+  }
+
   pub fn add_mapping(&mut self, field: &str, secret_name: &str) -> &mut Self {
     self.mappings.insert(field.to_string(), secret_name.to_string());
     self
@@ -81,56 +116,220 @@ impl SecretMapping {
     self.mappings.get(field)
   }
 
-  pub fn to_secrets(&self, credentials: &Credentials) -> Vec<Secret> {
+  /// Every secret name this mapping can produce, sorted
+  pub fn secret_names(&self) -> Vec<String> {
+    let mut names: Vec<String> = self.mappings
+      .values()
+      .chain(self.templates.keys())
+      .cloned()
+      .collect();
+    names.sort();
+    names.dedup();
+    names
+  }
+
+  /// Layer per-target overrides over this mapping
+  ///
+  /// Override entries replace or extend the field mappings and templates, `include`/`exclude`
+  /// then filter fields and templated secret names, and `prefix`/`suffix` are applied to
+  /// every resulting secret name.
+  pub fn with_overrides(&self, overrides: &MappingOverrides) -> Self {
+    let mut mappings = self.mappings.clone();
+    mappings.extend(overrides.field_mappings.clone());
+    let mut templates = self.templates.clone();
+    templates.extend(overrides.templates.clone());
+
+    if !overrides.include.is_empty() {
+      mappings.retain(|field, _| overrides.include.contains(field));
+      templates.retain(|secret_name, _| overrides.include.contains(secret_name));
+    }
+    mappings.retain(|field, _| !overrides.exclude.contains(field));
+    templates.retain(|secret_name, _| !overrides.exclude.contains(secret_name));
+
+    let prefix = overrides.prefix.as_deref().unwrap_or("");
+    let suffix = overrides.suffix.as_deref().unwrap_or("");
+    for secret_name in mappings.values_mut() {
+      *secret_name = format!("{}{}{}", prefix, secret_name, suffix);
+    }
+    let templates = templates
+      .into_iter()
+      .map(|(secret_name, template)| (format!("{}{}{}", prefix, secret_name, suffix), template))
+      .collect();
+
+    Self {
+      schema_name: self.schema_name.clone(),
+      mappings,
+      templates,
+    }
+  }
+
+  /// Turn credentials into secrets
+  ///
+  /// Every mapped field is looked up in the credential object (see [`Credentials::field`]);
+  /// fields that are missing or null are skipped. Templated secrets are rendered after the
+  /// mapped ones; a template that fails to render is an error, so the secret is never
+  /// silently left out of a sync.
+  pub fn to_secrets(&self, credentials: &Credentials) -> Result<Vec<Secret>> {
     let mut secrets = Vec::new();
 
     // Debug the mappings
     tracing::debug!("SecretMapping has {} mappings: {:?}", self.mappings.len(), self.mappings);
 
-    // Try both camelCase and snake_case field names for flexibility
-    let access_token_name = self
-      .get_secret_name("accessToken")
-      .or_else(|| self.get_secret_name("access_token"));
-    if let Some(name) = access_token_name {
-      tracing::debug!("Found mapping for access token: {}", name);
-      secrets.push(Secret {
-        name: name.clone(),
-        value: credentials.access_token.clone(),
-        description: Some("Claude AI access token".to_string()),
-      });
-    } else {
-      tracing::debug!("No mapping found for access token");
-    }
+    let mut mappings: Vec<_> = self.mappings.iter().collect();
+    mappings.sort();
+    for (field, name) in mappings {
+      let Some(value) = credentials.field(field) else {
+        tracing::debug!("No value for mapped field {} ({})", field, name);
+        continue;
+      };
 
-    let refresh_token_name = self
-      .get_secret_name("refreshToken")
-      .or_else(|| self.get_secret_name("refresh_token"));
-    if let (Some(token), Some(name)) = (&credentials.refresh_token, refresh_token_name) {
-      tracing::debug!("Found mapping for refresh token: {}", name);
+      tracing::debug!("Found mapping for {}: {}", field, name);
       secrets.push(Secret {
         name: name.clone(),
-        value: token.clone(),
-        description: Some("Claude AI refresh token".to_string()),
+        value,
+        description: Some(field_description(field)),
       });
-    } else {
-      tracing::debug!("No mapping found for refresh token or token is None");
     }
 
-    let expires_at_name = self
-      .get_secret_name("expiresAt")
-      .or_else(|| self.get_secret_name("expires_at"));
-    if let (Some(expires), Some(name)) = (credentials.expires_at, expires_at_name) {
-      tracing::debug!("Found mapping for expires at: {}", name);
+    // Templated secrets are rendered from the raw credential object
+    let mut templates: Vec<_> = self.templates.iter().collect();
+    templates.sort();
+    for (name, template) in templates {
+      let value = crate::utils::template
+        ::render(template, &credentials.source)
+        .map_err(|e| {
+          ClaudeCodeError::Validation(format!("Failed to render template for {}: {}", name, e))
+        })?;
       secrets.push(Secret {
         name: name.clone(),
-        value: expires.to_string(),
-        description: Some("Claude AI token expiry timestamp".to_string()),
+        value,
+        description: Some("Claude AI credentials (templated)".to_string()),
       });
-    } else {
-      tracing::debug!("No mapping found for expires at or expires_at is None");
     }
 
     tracing::debug!("Generated {} secrets from credentials", secrets.len());
-    secrets
+    Ok(secrets)
+  }
+}
+
+/// Human-readable description of the secret holding a credential field
+fn field_description(field: &str) -> String {
+  match field {
+    "accessToken" | "access_token" => "Claude AI access token".to_string(),
+    "refreshToken" | "refresh_token" => "Claude AI refresh token".to_string(),
+    "expiresAt" | "expires_at" => "Claude AI token expiry timestamp".to_string(),
+    _ => format!("Claude AI credential field {}", field),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn credentials() -> Credentials {
+    let source = serde_json::json!({
+      "accessToken": "access",
+      "refreshToken": "refresh",
+      "expiresAt": 1750255977327i64,
+      "scopes": ["user:inference", "user:profile"],
+      "subscriptionType": "max",
+      "account": { "email": "dev@example.com", "verified": true },
+      "rateLimitTier": null,
+    });
+    Credentials::from_source(source, &[])
+  }
+
+  fn global_mapping() -> SecretMapping {
+    let mut mapping = SecretMapping::new("claude");
+    mapping
+      .add_mapping("accessToken", "CLAUDE_ACCESS_TOKEN")
+      .add_mapping("refreshToken", "CLAUDE_REFRESH_TOKEN")
+      .add_mapping("expiresAt", "CLAUDE_EXPIRES_AT");
+    mapping
+  }
+
+  fn names(mapping: &SecretMapping) -> Vec<String> {
+    let mut names: Vec<String> = mapping
+      .to_secrets(&credentials())
+      .unwrap()
+      .into_iter()
+      .map(|s| s.name)
+      .collect();
+    names.sort();
+    names
+  }
+
+  #[test]
+  fn test_overrides_rename_and_filter() {
+    let mut overrides = MappingOverrides::default();
+    overrides.field_mappings.insert("accessToken".to_string(), "ANTHROPIC_OAUTH_TOKEN".to_string());
+    overrides.exclude.push("refreshToken".to_string());
+
+    let mapping = global_mapping().with_overrides(&overrides);
+    assert_eq!(names(&mapping), vec!["ANTHROPIC_OAUTH_TOKEN", "CLAUDE_EXPIRES_AT"]);
+  }
+
+  #[test]
+  fn test_overrides_include_with_prefix_and_suffix() {
+    let overrides = MappingOverrides {
+      include: vec!["accessToken".to_string()],
+      prefix: Some("CI_".to_string()),
+      suffix: Some("_STAGING".to_string()),
+      ..Default::default()
+    };
+
+    let mapping = global_mapping().with_overrides(&overrides);
+    assert_eq!(names(&mapping), vec!["CI_CLAUDE_ACCESS_TOKEN_STAGING"]);
+    assert_eq!(global_mapping().with_overrides(&MappingOverrides::default()), global_mapping());
+  }
+
+  #[test]
+  fn test_every_mapped_field_becomes_a_secret() {
+    let mut mapping = SecretMapping::new("claude");
+    mapping
+      .add_mapping("access_token", "TOKEN")
+      .add_mapping("subscriptionType", "CLAUDE_SUBSCRIPTION")
+      .add_mapping("scopes", "CLAUDE_SCOPES")
+      .add_mapping("scopes.1", "CLAUDE_SECOND_SCOPE")
+      .add_mapping("account.email", "CLAUDE_EMAIL")
+      .add_mapping("account.verified", "CLAUDE_VERIFIED")
+      .add_mapping("account", "CLAUDE_ACCOUNT")
+      .add_mapping("rateLimitTier", "CLAUDE_TIER")
+      .add_mapping("missing", "CLAUDE_MISSING");
+
+    let secrets: HashMap<String, String> = mapping
+      .to_secrets(&credentials())
+      .unwrap()
+      .into_iter()
+      .map(|s| (s.name, s.value))
+      .collect();
+
+    assert_eq!(secrets.len(), 7);
+    assert_eq!(secrets["TOKEN"], "access");
+    assert_eq!(secrets["CLAUDE_SUBSCRIPTION"], "max");
+    assert_eq!(secrets["CLAUDE_SCOPES"], r#"["user:inference","user:profile"]"#);
+    assert_eq!(secrets["CLAUDE_SECOND_SCOPE"], "user:profile");
+    assert_eq!(secrets["CLAUDE_EMAIL"], "dev@example.com");
+    assert_eq!(secrets["CLAUDE_VERIFIED"], "true");
+    assert_eq!(secrets["CLAUDE_ACCOUNT"], r#"{"email":"dev@example.com","verified":true}"#);
+  }
+
+  #[test]
+  fn test_templates_render_with_overrides() {
+    let mut mapping = SecretMapping::new("claude");
+    mapping.templates.insert("EXPIRES_ISO".to_string(), "{{ expiresAt | iso8601 }}".to_string());
+
+    let overrides = MappingOverrides {
+      prefix: Some("CI_".to_string()),
+      ..Default::default()
+    };
+    let secrets = mapping.with_overrides(&overrides).to_secrets(&credentials()).unwrap();
+    assert_eq!(secrets.len(), 1);
+    assert_eq!(secrets[0].name, "CI_EXPIRES_ISO");
+    assert_eq!(secrets[0].value, "2025-06-18T14:12:57Z");
+
+    mapping.templates.insert("BROKEN".to_string(), "{{ missing }}".to_string());
+    let error = mapping.with_overrides(&overrides).to_secrets(&credentials()).unwrap_err();
+    assert!(error.to_string().contains("CI_BROKEN"), "{}", error);
   }
 }
@@ -4,17 +4,21 @@ use super::Credentials;
 use crate::error::Result;
 use crate::types::Config;
 use async_trait::async_trait;
+use serde::Serialize;
 
 /// Validation error details
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ValidationError {
   pub field: String,
   pub message: String,
   pub severity: ValidationSeverity,
   pub suggestion: Option<String>,
+  /// The rule's `auto_fix` resolves this issue
+  pub auto_fixable: bool,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ValidationSeverity {
   Error,
   Warning,
@@ -28,6 +32,7 @@ impl ValidationError {
       message: message.to_string(),
       severity,
       suggestion: None,
+      auto_fixable: false,
     }
   }
 
@@ -36,6 +41,12 @@ impl ValidationError {
     self
   }
 
+  /// Mark the issue as fixable by `validate --fix`
+  pub fn fixable(mut self) -> Self {
+    self.auto_fixable = true;
+    self
+  }
+
   pub fn error(field: &str, message: &str) -> Self {
     Self::new(field, message, ValidationSeverity::Error)
   }
@@ -64,6 +75,21 @@ pub trait ValidationRule: Send + Sync {
   /// Validate credentials
   async fn validate_credentials(&self, credentials: &Credentials) -> Result<Vec<ValidationError>>;
 
+  /// Validate a credential profile's mappings against the credentials it reads
+  async fn validate_profile(
+    &self,
+    _config: &Config,
+    _profile: &str,
+    _credentials: &Credentials
+  ) -> Result<Vec<ValidationError>> {
+    Ok(Vec::new())
+  }
+
+  /// Fix the issues this rule reports as auto-fixable, describing each change
+  async fn auto_fix(&self, _config: &mut Config) -> Result<Vec<String>> {
+    Ok(Vec::new())
+  }
+
   /// Check if rule applies to config
   fn applies_to(&self, _config: &Config) -> bool {
     true
@@ -94,7 +120,7 @@ pub trait ValidationService: Send + Sync {
 }
 
 /// Summary of validation results
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ValidationSummary {
   pub total_issues: usize,
   pub errors: usize,
@@ -118,13 +144,17 @@ impl ValidationSummary {
       .iter()
       .filter(|e| e.severity == ValidationSeverity::Info)
       .count();
+    let auto_fixable = errors
+      .iter()
+      .filter(|e| e.auto_fixable)
+      .count();
 
     Self {
       total_issues: errors.len(),
       errors: errors_count,
       warnings: warnings_count,
       info: info_count,
-      auto_fixable: 0, // Would need to implement auto-fix detection
+      auto_fixable,
       is_valid: errors_count == 0,
     }
   }
@@ -1,4 +1,8 @@
 use serde::{ Deserialize, Serialize };
+use std::collections::HashMap;
+
+/// Name of the credential profile described by the top-level `credentials` section
+pub const DEFAULT_PROFILE: &str = "default";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClaudeCredentials {
@@ -19,75 +23,355 @@ pub struct ClaudeOAuth {
   pub subscription_type: String,
 }
 
+/// Contents of `config.yml`
+///
+/// Every field has a default, so that files written before a field existed still load.
+/// Changes to the shape of existing fields need a migration (see `config::migrations`).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
+  /// Schema version of the file
+  pub version: u32,
+
   pub daemon: DaemonConfig,
-  pub github: GitHubConfig,
   pub notifications: NotificationConfig,
   pub credentials: CredentialsConfig,
+
+  /// Additional named credential profiles, each with its own source and mapping
+  #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+  pub profiles: HashMap<String, CredentialsConfig>,
+
+  /// Provider-level settings, keyed by provider name (e.g. `aws: { region: eu-west-1 }`)
+  #[serde(
+    default,
+    skip_serializing_if = "HashMap::is_empty",
+    deserialize_with = "deserialize_provider_settings"
+  )]
+  pub providers: HashMap<String, HashMap<String, String>>,
+
+  /// Sync targets for any registered provider
+  #[serde(default)]
+  pub targets: Vec<TargetConfig>,
+
+  /// Retention of the configuration snapshots taken before every change
+  #[serde(default, skip_serializing_if = "BackupConfig::is_default")]
+  pub backups: BackupConfig,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+impl Config {
+  /// Targets configured for one provider
+  pub fn targets_for<'a>(&'a self, provider: &'a str) -> impl Iterator<Item = &'a TargetConfig> {
+    self.targets.iter().filter(move |t| t.provider == provider)
+  }
+
+  /// Credential settings of a profile (`default` is the `credentials` section)
+  pub fn profile(&self, name: &str) -> Option<&CredentialsConfig> {
+    if name == DEFAULT_PROFILE {
+      Some(&self.credentials)
+    } else {
+      self.profiles.get(name)
+    }
+  }
+
+  /// All profile names, `default` first and the rest sorted
+  pub fn profile_names(&self) -> Vec<&str> {
+    let mut names: Vec<&str> = self.profiles
+      .keys()
+      .map(|s| s.as_str())
+      .filter(|name| *name != DEFAULT_PROFILE)
+      .collect();
+    names.sort();
+    names.insert(0, DEFAULT_PROFILE);
+    names
+  }
+}
+
+/// A single sync target, e.g. a GitHub organization or an AWS secret
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TargetConfig {
+  /// Registered provider name (github, bitbucket, aws, webhook, a plugin, ...)
+  pub provider: String,
+
+  /// Provider-specific target kind (organization, repository, secret, endpoint, ...)
+  pub kind: String,
+
+  pub name: String,
+
+  /// Credential profile synced to this target (the `default` profile when unset)
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub profile: Option<String>,
+
+  /// Provider-specific per-target options
+  #[serde(
+    default,
+    skip_serializing_if = "HashMap::is_empty",
+    deserialize_with = "deserialize_scalar_map"
+  )]
+  pub options: HashMap<String, String>,
+
+  /// Secret mapping overrides layered over `credentials.field_mappings`
+  #[serde(flatten)]
+  pub mapping: MappingOverrides,
+}
+
+/// Per-target adjustments to the global field mappings
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct MappingOverrides {
+  /// credential_field -> secret_name entries that replace or extend the global mappings
+  #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+  pub field_mappings: HashMap<String, String>,
+
+  /// secret_name -> value template entries that replace or extend the global templates
+  #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+  pub templates: HashMap<String, String>,
+
+  /// Only sync these credential fields or templated secrets (all when empty)
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub include: Vec<String>,
+
+  /// Never sync these credential fields or templated secrets
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub exclude: Vec<String>,
+
+  /// Prepended to every secret name, e.g. "CI_"
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub prefix: Option<String>,
+
+  /// Appended to every secret name, e.g. "_STAGING"
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub suffix: Option<String>,
+}
+
+impl MappingOverrides {
+  pub fn is_empty(&self) -> bool {
+    *self == Self::default()
+  }
+}
+
+impl TargetConfig {
+  pub fn new(provider: &str, kind: &str, name: &str) -> Self {
+    Self {
+      provider: provider.to_string(),
+      kind: kind.to_string(),
+      name: name.to_string(),
+      profile: None,
+      options: HashMap::new(),
+      mapping: MappingOverrides::default(),
+    }
+  }
+
+  /// Credential profile this target receives
+  pub fn profile_name(&self) -> &str {
+    self.profile.as_deref().unwrap_or(DEFAULT_PROFILE)
+  }
+
+  /// Whether this entry refers to the given provider, kind and name
+  pub fn matches(&self, provider: &str, kind: &str, name: &str) -> bool {
+    self.provider == provider && self.kind == kind && self.name == name
+  }
+}
+
+impl std::fmt::Display for TargetConfig {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}:{}:{}", self.provider, self.kind, self.name)
+  }
+}
+
+/// Accept any YAML scalar (string, number, bool) as a string value
+fn scalar_to_string<E: serde::de::Error>(value: serde_yaml::Value) -> Result<String, E> {
+  match value {
+    serde_yaml::Value::String(s) => Ok(s),
+    serde_yaml::Value::Number(n) => Ok(n.to_string()),
+    serde_yaml::Value::Bool(b) => Ok(b.to_string()),
+    other => Err(E::custom(format!("expected a scalar value, found {:?}", other))),
+  }
+}
+
+fn deserialize_scalar_map<'de, D>(deserializer: D) -> Result<HashMap<String, String>, D::Error>
+  where D: serde::Deserializer<'de>
+{
+  HashMap::<String, serde_yaml::Value>
+    ::deserialize(deserializer)?
+    .into_iter()
+    .map(|(key, value)| Ok((key, scalar_to_string(value)?)))
+    .collect()
+}
+
+fn deserialize_provider_settings<'de, D>(
+  deserializer: D
+) -> Result<HashMap<String, HashMap<String, String>>, D::Error>
+  where D: serde::Deserializer<'de>
+{
+  HashMap::<String, HashMap<String, serde_yaml::Value>>
+    ::deserialize(deserializer)?
+    .into_iter()
+    .map(|(provider, settings)| {
+      let settings = settings
+        .into_iter()
+        .map(|(key, value)| Ok((key, scalar_to_string(value)?)))
+        .collect::<Result<_, D::Error>>()?;
+      Ok((provider, settings))
+    })
+    .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct DaemonConfig {
   pub log_level: String,
   pub sync_delay_after_expiry: u64, // seconds
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GitHubConfig {
-  pub organizations: Vec<GitHubOrganization>,
-  pub repositories: Vec<GitHubRepository>,
+impl Default for DaemonConfig {
+  fn default() -> Self {
+    Self {
+      log_level: "info".to_string(),
+      sync_delay_after_expiry: 60,
+    }
+  }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GitHubOrganization {
-  pub name: String,
+/// Retention of configuration snapshots (see `config::backup`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupConfig {
+  /// Number of snapshots to keep; 0 disables snapshots
+  #[serde(default = "default_backups_keep")]
+  pub keep: usize,
+
+  /// Delete snapshots older than this many days; 0 keeps them regardless of age
+  #[serde(default)]
+  pub max_age_days: u64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GitHubRepository {
-  pub repo: String,
+fn default_backups_keep() -> usize {
+  20
+}
+
+impl BackupConfig {
+  pub fn is_default(&self) -> bool {
+    *self == Self::default()
+  }
+}
+
+impl Default for BackupConfig {
+  fn default() -> Self {
+    Self {
+      keep: default_backups_keep(),
+      max_age_days: 0,
+    }
+  }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct NotificationConfig {
   pub session_warnings: Vec<u64>, // minutes before expiry
   pub sync_failures: bool,
 }
 
+impl Default for NotificationConfig {
+  fn default() -> Self {
+    Self {
+      session_warnings: vec![30, 15, 5],
+      sync_failures: true,
+    }
+  }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct CredentialsConfig {
   /// Path to credential file (supports ~ for home directory)
   pub file_path: String,
 
   /// JSON path to the credential object within the file
-  /// For Claude Code: "claudeAiOauth"
+  /// For Claude Code: "claudeAiOauth"; dotted paths and RFC 6901 pointers are supported
   pub json_path: String,
 
   /// Field mappings: credential_field -> github_secret_name
-  pub field_mappings: std::collections::HashMap<String, String>,
+  pub field_mappings: HashMap<String, String>,
+
+  /// Templated secrets: secret_name -> value template (see `utils::template`)
+  #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+  pub templates: HashMap<String, String>,
+}
+
+impl Default for CredentialsConfig {
+  fn default() -> Self {
+    Self {
+      file_path: "~/.claude/.credentials.json".to_string(),
+      json_path: "claudeAiOauth".to_string(),
+      field_mappings: HashMap::new(),
+      templates: HashMap::new(),
+    }
+  }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct SyncState {
   pub last_sync: i64,
+  /// Access token of the default profile at the last sync
   pub last_token: String,
+  /// Access tokens of named profiles at the last sync
+  #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+  pub profile_tokens: HashMap<String, String>,
   pub targets: Vec<TargetStatus>,
+  /// Set by `revoke`: syncing stays disarmed until `rearm`
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub revoked_at: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TargetStatus {
-  pub target_type: TargetType,
+  #[serde(default = "default_status_provider")]
+  pub provider: String,
+  pub target_type: String,
   pub name: String,
   pub last_sync_time: i64,
   pub last_sync_status: SyncStatus,
   pub last_error: Option<String>,
+
+  /// Target options at the last sync, so secrets can be deleted after the target is removed
+  #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+  pub options: HashMap<String, String>,
+
+  /// Names of the secrets this toolkit has written to the target
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub secrets: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub enum TargetType {
-  Organization,
-  Repository,
+impl TargetStatus {
+  /// Whether this entry tracks the given provider, kind and name
+  pub fn matches(&self, provider: &str, kind: &str, name: &str) -> bool {
+    self.provider == provider && self.target_type == kind && self.name == name
+  }
+}
+
+impl SyncState {
+  /// Status entry of a target, created on first use
+  pub fn target_mut(&mut self, provider: &str, kind: &str, name: &str) -> &mut TargetStatus {
+    match self.targets.iter().position(|t| t.matches(provider, kind, name)) {
+      Some(index) => &mut self.targets[index],
+      None => {
+        self.targets.push(TargetStatus {
+          provider: provider.to_string(),
+          target_type: kind.to_string(),
+          name: name.to_string(),
+          last_sync_time: 0,
+          last_sync_status: SyncStatus::Failure,
+          last_error: None,
+          options: HashMap::new(),
+          secrets: Vec::new(),
+        });
+        self.targets.last_mut().unwrap()
+      }
+    }
+  }
+}
+
+/// State files written before provider-agnostic targets only tracked GitHub
+fn default_status_provider() -> String {
+  "github".to_string()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -104,41 +388,17 @@ pub struct SessionInfo {
   pub subscription_type: String,
 }
 
-#[derive(Debug, Clone)]
-pub struct GitHubTarget {
-  pub target_type: TargetType,
-  pub name: String,
-}
-
 impl Default for Config {
   fn default() -> Self {
     Self {
-      daemon: DaemonConfig {
-        log_level: "info".to_string(),
-        sync_delay_after_expiry: 60,
-      },
-      github: GitHubConfig {
-        organizations: vec![],
-        repositories: vec![],
-      },
-      notifications: NotificationConfig {
-        session_warnings: vec![30, 15, 5],
-        sync_failures: true,
-      },
-      credentials: CredentialsConfig {
-        file_path: "~/.claude/.credentials.json".to_string(),
-        json_path: "claudeAiOauth".to_string(),
-        field_mappings: std::collections::HashMap::new(),
-      },
-    }
-  }
-}
-
-impl std::fmt::Display for TargetType {
-  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    match self {
-      TargetType::Organization => write!(f, "organization"),
-      TargetType::Repository => write!(f, "repository"),
+      version: crate::config::migrations::CURRENT_VERSION,
+      daemon: DaemonConfig::default(),
+      notifications: NotificationConfig::default(),
+      credentials: CredentialsConfig::default(),
+      profiles: HashMap::new(),
+      providers: HashMap::new(),
+      targets: vec![],
+      backups: BackupConfig::default(),
     }
   }
 }
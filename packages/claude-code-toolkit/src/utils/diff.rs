@@ -0,0 +1,142 @@
+//! Line diffs for showing configuration changes
+
+/// One line of a diff
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLine<'a> {
+  Same(&'a str),
+  Removed(&'a str),
+  Added(&'a str),
+}
+
+/// Line-by-line diff of `old` and `new`, based on their longest common subsequence
+pub fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<DiffLine<'a>> {
+  let old: Vec<&str> = old.lines().collect();
+  let new: Vec<&str> = new.lines().collect();
+
+  // lengths[i][j] is the length of the common subsequence of old[i..] and new[j..]
+  let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+  for i in (0..old.len()).rev() {
+    for j in (0..new.len()).rev() {
+      lengths[i][j] = if old[i] == new[j] {
+        lengths[i + 1][j + 1] + 1
+      } else {
+        lengths[i + 1][j].max(lengths[i][j + 1])
+      };
+    }
+  }
+
+  let mut lines = Vec::with_capacity(old.len().max(new.len()));
+  let (mut i, mut j) = (0, 0);
+  while i < old.len() && j < new.len() {
+    if old[i] == new[j] {
+      lines.push(DiffLine::Same(old[i]));
+      i += 1;
+      j += 1;
+    } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+      lines.push(DiffLine::Removed(old[i]));
+      i += 1;
+    } else {
+      lines.push(DiffLine::Added(new[j]));
+      j += 1;
+    }
+  }
+  lines.extend(old[i..].iter().map(|line| DiffLine::Removed(line)));
+  lines.extend(new[j..].iter().map(|line| DiffLine::Added(line)));
+  lines
+}
+
+/// Unified diff of `old` and `new` with `context` lines around changes; empty if equal
+pub fn unified_diff(
+  old: &str,
+  new: &str,
+  old_name: &str,
+  new_name: &str,
+  context: usize
+) -> String {
+  let lines = diff_lines(old, new);
+  let changes: Vec<usize> = lines
+    .iter()
+    .enumerate()
+    .filter(|(_, line)| !matches!(line, DiffLine::Same(_)))
+    .map(|(i, _)| i)
+    .collect();
+  if changes.is_empty() {
+    return String::new();
+  }
+
+  // Line numbers in `old` and `new` at each diff line
+  let mut positions = Vec::with_capacity(lines.len());
+  let (mut old_line, mut new_line) = (0, 0);
+  for line in &lines {
+    positions.push((old_line, new_line));
+    match line {
+      DiffLine::Same(_) => {
+        old_line += 1;
+        new_line += 1;
+      }
+      DiffLine::Removed(_) => {
+        old_line += 1;
+      }
+      DiffLine::Added(_) => {
+        new_line += 1;
+      }
+    }
+  }
+
+  let mut out = format!("--- {}\n+++ {}\n", old_name, new_name);
+  let mut k = 0;
+  while k < changes.len() {
+    let start = changes[k].saturating_sub(context);
+    let mut last = changes[k];
+    // Changes separated by at most twice the context share a hunk
+    while k + 1 < changes.len() && changes[k + 1] <= last + 2 * context + 1 {
+      k += 1;
+      last = changes[k];
+    }
+    k += 1;
+
+    let hunk = &lines[start..(last + context + 1).min(lines.len())];
+    let old_count = hunk
+      .iter()
+      .filter(|line| !matches!(line, DiffLine::Added(_)))
+      .count();
+    let new_count = hunk
+      .iter()
+      .filter(|line| !matches!(line, DiffLine::Removed(_)))
+      .count();
+    let (old_start, new_start) = positions[start];
+    out.push_str(
+      &format!("@@ -{},{} +{},{} @@\n", old_start + 1, old_count, new_start + 1, new_count)
+    );
+    for line in hunk {
+      let (marker, text) = match line {
+        DiffLine::Same(text) => (' ', text),
+        DiffLine::Removed(text) => ('-', text),
+        DiffLine::Added(text) => ('+', text),
+      };
+      out.push(marker);
+      out.push_str(text);
+      out.push('\n');
+    }
+  }
+
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_unified_diff_hunks() {
+    let old = "a\nb\nc\nd\ne\nf\ng\nh\n";
+    let new = "a\nB\nc\nd\ne\nf\ng\nh\ni\n";
+
+    assert_eq!(
+      unified_diff(old, new, "old", "new", 1),
+      "--- old\n+++ new\n@@ -1,3 +1,3 @@\n a\n-b\n+B\n c\n@@ -8,1 +8,2 @@\n h\n+i\n"
+    );
+    assert_eq!(unified_diff(old, new, "old", "new", 3).matches("@@ -").count(), 1);
+    assert!(unified_diff(old, old, "old", "new", 3).is_empty());
+  }
+}
@@ -0,0 +1,54 @@
+//! Log output, with a level that can change while running
+//!
+//! Logs go to stderr at `info` for this crate. `RUST_LOG` replaces the filter, and then
+//! [`set_level`] leaves it alone; otherwise the daemon applies `daemon.log_level` with it
+//! on start and on every configuration reload.
+
+use crate::error::{ ClaudeCodeError, Result };
+use std::sync::OnceLock;
+use tracing_subscriber::{ EnvFilter, Registry, reload, prelude::* };
+
+const DEFAULT_LEVEL: &str = "info";
+
+/// Filter handle, unset when `RUST_LOG` chose the filter
+static HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+fn filter(level: &str) -> Result<EnvFilter> {
+  EnvFilter::try_new(format!("claude_code_toolkit={}", level)).map_err(|e| {
+    ClaudeCodeError::InvalidConfig(format!("Invalid log level '{}': {}", level, e))
+  })
+}
+
+/// Install the global subscriber
+pub fn init() {
+  let from_env = EnvFilter::try_from_default_env().ok();
+  let env_override = from_env.is_some();
+  let filter = from_env.unwrap_or_else(|| {
+    EnvFilter::new(format!("claude_code_toolkit={}", DEFAULT_LEVEL))
+  });
+  let (filter, handle) = reload::Layer::new(filter);
+
+  // Logs go to stderr so `--output json|yaml` keeps stdout parseable
+  tracing_subscriber
+    ::registry()
+    .with(filter)
+    .with(tracing_subscriber::fmt::layer().with_target(false).with_writer(std::io::stderr))
+    .init();
+
+  if !env_override {
+    let _ = HANDLE.set(handle);
+  }
+}
+
+/// Log at `level` (`error`, `warn`, `info`, `debug` or `trace`) from now on
+///
+/// Does nothing when `RUST_LOG` is set or [`init`] was not called.
+pub fn set_level(level: &str) -> Result<()> {
+  let filter = filter(level)?;
+  if let Some(handle) = HANDLE.get() {
+    handle
+      .reload(filter)
+      .map_err(|e| ClaudeCodeError::Generic(format!("Could not change the log level: {}", e)))?;
+  }
+  Ok(())
+}
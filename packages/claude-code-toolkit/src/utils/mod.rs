@@ -1,4 +1,8 @@
+pub mod diff;
+pub mod logging;
+pub mod paths;
 pub mod systemd;
+pub mod template;
 
 #[cfg(feature = "notifications")]
 pub mod notifications {
@@ -6,9 +10,13 @@ pub mod notifications {
   use notify_rust::Notification;
   use tracing::warn;
 
-  pub fn send_session_warning(minutes_remaining: u64) -> Result<()> {
+  pub fn send_session_warning(profile: &str, minutes_remaining: u64) -> Result<()> {
     let title = "Claude Code Session Warning";
-    let body = format!("Your Claude session expires in {} minutes", minutes_remaining);
+    let body = if profile == crate::types::DEFAULT_PROFILE {
+      format!("Your Claude session expires in {} minutes", minutes_remaining)
+    } else {
+      format!("Your Claude session ({}) expires in {} minutes", profile, minutes_remaining)
+    };
 
     match
       Notification::new()
@@ -46,6 +54,26 @@ pub mod notifications {
     }
   }
 
+  pub fn send_config_rejected(error: &str) -> Result<()> {
+    let title = "Claude Code Configuration Rejected";
+    let body = format!("The daemon keeps its running configuration: {}", error);
+
+    match
+      Notification::new()
+        .summary(title)
+        .body(&body)
+        .icon("dialog-error")
+        .timeout(10000) // 10 seconds
+        .show()
+    {
+      Ok(_) => Ok(()),
+      Err(e) => {
+        warn!("Failed to send notification: {}", e);
+        Err(ClaudeCodeError::Notification(e.to_string()))
+      }
+    }
+  }
+
   pub fn send_sync_success(count: usize) -> Result<()> {
     let title = "Claude Code Sync Success";
     let body = format!("Successfully synced credentials to {} targets", count);
@@ -71,7 +99,7 @@ pub mod notifications {
 pub mod notifications {
   use crate::error::*;
 
-  pub fn send_session_warning(_minutes_remaining: u64) -> Result<()> {
+  pub fn send_session_warning(_profile: &str, _minutes_remaining: u64) -> Result<()> {
     Ok(())
   }
 
@@ -79,6 +107,10 @@ pub mod notifications {
     Ok(())
   }
 
+  pub fn send_config_rejected(_error: &str) -> Result<()> {
+    Ok(())
+  }
+
   pub fn send_sync_success(_count: usize) -> Result<()> {
     Ok(())
   }
@@ -0,0 +1,248 @@
+//! Where the toolkit keeps its files
+//!
+//! | File               | Default                                                  |
+//! |--------------------|----------------------------------------------------------|
+//! | Configuration      | `$XDG_CONFIG_HOME/claude-code-toolkit/config.yml`        |
+//! | Sync state         | `$XDG_STATE_HOME/claude-code-toolkit/sync-state.json`    |
+//! | Claude credentials | `~/.claude/.credentials.json`                            |
+//! | systemd unit       | `$XDG_CONFIG_HOME/systemd/user/claude-code-sync.service` |
+//!
+//! `XDG_CONFIG_HOME` defaults to `~/.config` and `XDG_STATE_HOME` to `~/.local/state`.
+//! Backups, plugins and wizard progress live next to the configuration file. The global
+//! `--config` and `--state-dir` flags (or `CCT_CONFIG` and `CCT_STATE_DIR`) replace the
+//! configuration file and state directory, so that instances with different flags share
+//! nothing but the Claude credentials.
+//!
+//! Files in the locations used before, `~/.goodiebag/claude-code/` and
+//! `~/.goodiebag/sync-state.json`, are moved by [`Paths::migrate_legacy`].
+
+use crate::error::Result;
+use std::path::{ Path, PathBuf };
+use std::sync::OnceLock;
+use tracing::info;
+
+/// Directory name below the XDG base directories
+pub const APP_DIR: &str = "claude-code-toolkit";
+
+const CONFIG_FILE: &str = "config.yml";
+const STATE_FILE: &str = "sync-state.json";
+const PLUGINS_DIR: &str = "plugins";
+
+static PATHS: OnceLock<Paths> = OnceLock::new();
+
+/// Resolved file locations
+#[derive(Debug, Clone, PartialEq)]
+pub struct Paths {
+  home: PathBuf,
+  config_file: PathBuf,
+  state_dir: PathBuf,
+  systemd_dir: PathBuf,
+  config_overridden: bool,
+  state_overridden: bool,
+}
+
+impl Paths {
+  /// Paths from the XDG environment, with the command-line overrides
+  pub fn resolve(config_file: Option<PathBuf>, state_dir: Option<PathBuf>) -> Self {
+    let xdg = |var: &str| {
+      std::env
+        ::var_os(var)
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+    };
+    Self::under_home(
+      dirs::home_dir().unwrap_or_default(),
+      xdg("XDG_CONFIG_HOME"),
+      xdg("XDG_STATE_HOME")
+    ).with_overrides(config_file, state_dir)
+  }
+
+  /// Default paths below `home`, or below the given XDG base directories
+  pub fn under_home(
+    home: PathBuf,
+    config_home: Option<PathBuf>,
+    state_home: Option<PathBuf>
+  ) -> Self {
+    let config_home = config_home.unwrap_or_else(|| home.join(".config"));
+    let state_home = state_home.unwrap_or_else(|| home.join(".local").join("state"));
+    Self {
+      config_file: config_home.join(APP_DIR).join(CONFIG_FILE),
+      state_dir: state_home.join(APP_DIR),
+      systemd_dir: config_home.join("systemd").join("user"),
+      home,
+      config_overridden: false,
+      state_overridden: false,
+    }
+  }
+
+  /// Replace the configuration file and state directory
+  pub fn with_overrides(
+    mut self,
+    config_file: Option<PathBuf>,
+    state_dir: Option<PathBuf>
+  ) -> Self {
+    // Absolute, so that the daemon resolves them the same way
+    let absolute = |path: PathBuf| std::path::absolute(&path).unwrap_or(path);
+    if let Some(config_file) = config_file {
+      self.config_file = absolute(config_file);
+      self.config_overridden = true;
+    }
+    if let Some(state_dir) = state_dir {
+      self.state_dir = absolute(state_dir);
+      self.state_overridden = true;
+    }
+    self
+  }
+
+  pub fn config_file(&self) -> &Path {
+    &self.config_file
+  }
+
+  /// Directory of the configuration file, holding backups and plugins too
+  pub fn config_dir(&self) -> &Path {
+    self.config_file.parent().unwrap_or_else(|| Path::new("."))
+  }
+
+  pub fn state_dir(&self) -> &Path {
+    &self.state_dir
+  }
+
+  pub fn state_file(&self) -> PathBuf {
+    self.state_dir.join(STATE_FILE)
+  }
+
+  pub fn plugins_dir(&self) -> PathBuf {
+    self.config_dir().join(PLUGINS_DIR)
+  }
+
+  /// Claude Code's own credentials file
+  pub fn credentials_file(&self) -> PathBuf {
+    self.home.join(".claude").join(".credentials.json")
+  }
+
+  pub fn systemd_dir(&self) -> &Path {
+    &self.systemd_dir
+  }
+
+  /// Command-line flags reproducing the overrides, e.g. for the daemon's unit file
+  pub fn override_args(&self) -> Vec<String> {
+    let mut args = Vec::new();
+    if self.config_overridden {
+      args.push(format!("--config={}", self.config_file.display()));
+    }
+    if self.state_overridden {
+      args.push(format!("--state-dir={}", self.state_dir.display()));
+    }
+    args
+  }
+
+  /// Directory that held the configuration and state before XDG support
+  pub fn legacy_root(&self) -> PathBuf {
+    self.home.join(".goodiebag")
+  }
+
+  /// Move files from the locations used before XDG support
+  ///
+  /// Only default locations are migrated, and only when nothing exists there yet.
+  /// Returns the new locations of moved files.
+  pub fn migrate_legacy(&self) -> Result<Vec<PathBuf>> {
+    let legacy_root = self.legacy_root();
+    let legacy_config_dir = legacy_root.join("claude-code");
+    let legacy_state = legacy_root.join(STATE_FILE);
+    let mut moved = Vec::new();
+
+    let config_dir = self.config_dir().to_path_buf();
+    if
+      !self.config_overridden &&
+      legacy_config_dir.join(CONFIG_FILE).is_file() &&
+      !self.config_file.exists()
+    {
+      std::fs::create_dir_all(&config_dir)?;
+      for entry in std::fs::read_dir(&legacy_config_dir)? {
+        let entry = entry?;
+        let target = config_dir.join(entry.file_name());
+        if !target.exists() {
+          move_path(&entry.path(), &target)?;
+          moved.push(target);
+        }
+      }
+      // Leave the directory if anything could not be moved
+      let _ = std::fs::remove_dir(&legacy_config_dir);
+    }
+
+    let state_file = self.state_file();
+    if !self.state_overridden && legacy_state.is_file() && !state_file.exists() {
+      std::fs::create_dir_all(&self.state_dir)?;
+      move_path(&legacy_state, &state_file)?;
+      moved.push(state_file);
+    }
+
+    if !moved.is_empty() {
+      let _ = std::fs::remove_dir(&legacy_root);
+      info!("Moved {} files from {:?} to the XDG directories", moved.len(), legacy_root);
+    }
+    Ok(moved)
+  }
+}
+
+/// Rename, or copy and delete across file systems
+fn move_path(from: &Path, to: &Path) -> Result<()> {
+  if std::fs::rename(from, to).is_ok() {
+    return Ok(());
+  }
+
+  if from.is_dir() {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+      let entry = entry?;
+      move_path(&entry.path(), &to.join(entry.file_name()))?;
+    }
+    std::fs::remove_dir(from)?;
+  } else {
+    std::fs::copy(from, to)?;
+    std::fs::remove_file(from)?;
+  }
+  Ok(())
+}
+
+/// Set the process-wide paths, returning false if they were already in use
+pub fn init(paths: Paths) -> bool {
+  PATHS.set(paths).is_ok()
+}
+
+/// Process-wide paths: those given to [`init`], or the defaults
+pub fn get() -> &'static Paths {
+  PATHS.get_or_init(|| Paths::resolve(None, None))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use tempfile::TempDir;
+
+  #[test]
+  fn test_xdg_paths_and_legacy_migration() {
+    let home = TempDir::new().unwrap();
+    let legacy = home.path().join(".goodiebag");
+    std::fs::create_dir_all(legacy.join("claude-code").join("backups")).unwrap();
+    std::fs::write(legacy.join("claude-code").join(CONFIG_FILE), "targets: []\n").unwrap();
+    std::fs::write(legacy.join(STATE_FILE), "{}").unwrap();
+
+    let paths = Paths::under_home(home.path().to_path_buf(), None, None);
+    assert_eq!(paths.config_file(), home.path().join(".config/claude-code-toolkit/config.yml"));
+    assert_eq!(paths.state_dir(), home.path().join(".local/state/claude-code-toolkit"));
+    assert!(paths.override_args().is_empty());
+
+    // Overridden locations are left alone
+    let custom = paths.clone().with_overrides(Some(home.path().join("custom.yml")), None);
+    assert_eq!(custom.override_args().len(), 1);
+    assert_eq!(custom.migrate_legacy().unwrap(), vec![paths.state_file()]);
+    assert!(!paths.config_file().exists());
+
+    assert_eq!(paths.migrate_legacy().unwrap().len(), 2);
+    assert_eq!(std::fs::read_to_string(paths.config_file()).unwrap(), "targets: []\n");
+    assert!(paths.config_dir().join("backups").is_dir());
+    assert!(!legacy.exists());
+    assert!(paths.migrate_legacy().unwrap().is_empty());
+  }
+}
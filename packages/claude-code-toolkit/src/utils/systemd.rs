@@ -1,5 +1,5 @@
 use crate::error::*;
-use dirs::home_dir;
+use crate::utils::paths;
 use std::path::PathBuf;
 use std::process::Stdio;
 use tokio::fs;
@@ -15,11 +15,7 @@ pub struct SystemdManager {
 
 impl SystemdManager {
   pub fn new() -> Result<Self> {
-    let systemd_dir = home_dir()
-      .ok_or("Could not determine home directory")?
-      .join(".config")
-      .join("systemd")
-      .join("user");
+    let systemd_dir = paths::get().systemd_dir().to_path_buf();
 
     let service_file = systemd_dir.join(SERVICE_NAME);
 
@@ -37,6 +33,30 @@ impl SystemdManager {
         ClaudeCodeError::Systemd(format!("Failed to get current executable: {}", e))
       })?;
 
+    // The daemon must see the files this instance uses
+    let paths = paths::get();
+    let mut exec_start = vec![current_exe.display().to_string()];
+    exec_start.extend(paths.override_args());
+    exec_start.push("daemon".to_string());
+    // `$` would expand an environment variable in ExecStart
+    let exec_start: Vec<String> = exec_start
+      .iter()
+      .map(|arg| quote(&arg.replace('$', "$$")))
+      .collect();
+    let credentials_dir = paths
+      .credentials_file()
+      .parent()
+      .map(|dir| dir.display().to_string())
+      .unwrap_or_default();
+    let read_write_paths: Vec<String> = [
+      paths.config_dir().display().to_string(),
+      paths.state_dir().display().to_string(),
+      credentials_dir,
+    ]
+      .iter()
+      .map(|path| quote(path))
+      .collect();
+
     let service_content = format!(
       r#"[Unit]
 Description=Claude Code Credential Sync Daemon
@@ -44,7 +64,8 @@ After=network.target
 
 [Service]
 Type=simple
-ExecStart={} daemon
+ExecStart={}
+ExecReload=/bin/kill -HUP $MAINPID
 Restart=on-failure
 RestartSec=10
 StandardOutput=journal
@@ -56,19 +77,47 @@ PrivateTmp=true
 NoNewPrivileges=true
 ProtectSystem=strict
 ProtectHome=read-only
-ReadWritePaths=%h/.goodiebag/claude-code %h/.claude
+ReadWritePaths={}
 
 [Install]
 WantedBy=default.target"#,
-      current_exe.display()
+      exec_start.join(" "),
+      read_write_paths.join(" ")
     );
 
     Ok(service_content)
   }
 
+  /// Content of the installed unit file, if there is one
+  pub async fn installed_unit(&self) -> Option<String> {
+    fs::read_to_string(&self.service_file).await.ok()
+  }
+
+  /// Whether an installed unit differs from the one `install` would write now
+  ///
+  /// True after upgrades that changed the unit, or after the binary was moved.
+  pub async fn is_outdated(&self) -> Result<bool> {
+    match self.installed_unit().await {
+      Some(installed) => Ok(installed != self.generate_service_file().await?),
+      None => Ok(false),
+    }
+  }
+
+  /// Whether an installed unit still grants access to the pre-XDG `~/.goodiebag` directory
+  ///
+  /// Such a unit keeps the new file locations read-only, so the daemon cannot write them.
+  pub async fn uses_legacy_paths(&self) -> bool {
+    let legacy_root = paths::get().legacy_root().display().to_string();
+    self
+      .installed_unit().await
+      .is_some_and(|unit| unit.contains(&legacy_root))
+  }
+
   pub async fn install(&self) -> Result<()> {
     // Ensure systemd user directory exists
     fs::create_dir_all(&self.systemd_dir).await?;
+    // ReadWritePaths must exist when the service starts
+    fs::create_dir_all(paths::get().state_dir()).await?;
 
     // Generate and write service file
     let service_content = self.generate_service_file().await?;
@@ -77,9 +126,9 @@ WantedBy=default.target"#,
     // Reload systemd
     self.run_systemctl(&["daemon-reload"]).await?;
 
-    // Enable and start the service
+    // Enable and (re)start the service, so a running daemon picks up the new unit
     self.run_systemctl(&["enable", SERVICE_NAME]).await?;
-    self.run_systemctl(&["start", SERVICE_NAME]).await?;
+    self.run_systemctl(&["restart", SERVICE_NAME]).await?;
 
     info!("Successfully installed and started systemd service");
     Ok(())
@@ -184,3 +233,27 @@ WantedBy=default.target"#,
     }
   }
 }
+
+/// Quote one word of a unit file setting
+///
+/// Quoted words may contain spaces; backslashes and double quotes are escaped, and `%`
+/// is doubled so it is not taken for a specifier.
+fn quote(word: &str) -> String {
+  let escaped = word
+    .replace('\\', "\\\\")
+    .replace('"', "\\\"")
+    .replace('%', "%%");
+  format!("\"{}\"", escaped)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_quote_escapes_unit_file_words() {
+    assert_eq!(quote("/usr/bin/claude-code-toolkit"), r#""/usr/bin/claude-code-toolkit""#);
+    assert_eq!(quote("/home/me/My Files/100%"), r#""/home/me/My Files/100%%""#);
+    assert_eq!(quote(r#"C:\odd "name""#), r#""C:\\odd \"name\"""#);
+  }
+}
@@ -0,0 +1,201 @@
+//! Secret value templates
+//!
+//! Templates interpolate credential fields into secret values:
+//!
+//! - `{{ accessToken }}` - a field of the credential object (`a.b` or `/a/b` for nested fields)
+//! - `{{ . }}` - the whole credential object
+//! - `{{ field | filter | ... }}` - filters applied left to right
+//!
+//! Filters: `json` (JSON encoding), `base64`, `iso8601` (epoch seconds or milliseconds
+//! to an RFC 3339 UTC timestamp), `upper` and `lower`. Strings render verbatim, other
+//! values as JSON, and missing fields are an error.
+
+use crate::error::{ ClaudeCodeError, Result };
+use base64::Engine;
+use serde_json::Value;
+
+const FILTERS: [&str; 5] = ["json", "base64", "iso8601", "upper", "lower"];
+
+/// One `{{ ... }}` expression
+struct Expression<'a> {
+  path: &'a str,
+  filters: Vec<&'a str>,
+}
+
+/// Template pieces in order: literal text or an expression
+enum Piece<'a> {
+  Text(&'a str),
+  Expression(Expression<'a>),
+}
+
+fn parse(template: &str) -> Result<Vec<Piece<'_>>> {
+  let mut pieces = Vec::new();
+  let mut rest = template;
+
+  while let Some(start) = rest.find("{{") {
+    if start > 0 {
+      pieces.push(Piece::Text(&rest[..start]));
+    }
+    let end = rest[start..]
+      .find("}}")
+      .ok_or_else(|| ClaudeCodeError::InvalidConfig("Unclosed '{{' in template".to_string()))?;
+
+    let mut parts = rest[start + 2..start + end].split('|').map(|p| p.trim());
+    let path = parts.next().unwrap_or("");
+    if path.is_empty() {
+      return Err(ClaudeCodeError::InvalidConfig("Empty '{{ }}' in template".to_string()));
+    }
+
+    let filters: Vec<&str> = parts.collect();
+    if let Some(unknown) = filters.iter().find(|f| !FILTERS.contains(f)) {
+      return Err(
+        ClaudeCodeError::InvalidConfig(
+          format!("Unknown template filter '{}' (available: {})", unknown, FILTERS.join(", "))
+        )
+      );
+    }
+
+    pieces.push(Piece::Expression(Expression { path, filters }));
+    rest = &rest[start + end + 2..];
+  }
+
+  if !rest.is_empty() {
+    pieces.push(Piece::Text(rest));
+  }
+
+  Ok(pieces)
+}
+
+/// Check template syntax and filter names without rendering
+pub fn validate(template: &str) -> Result<()> {
+  parse(template).map(|_| ())
+}
+
+/// Resolve a dotted field path (`.` is the whole object, `/a/b` an RFC 6901 pointer)
+pub fn lookup<'a>(context: &'a Value, path: &str) -> Option<&'a Value> {
+  if path == "." || path.is_empty() {
+    return Some(context);
+  }
+  if path.starts_with('/') {
+    return context.pointer(path);
+  }
+
+  path.split('.').try_fold(context, |value, segment| {
+    match value {
+      Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+      _ => value.get(segment),
+    }
+  })
+}
+
+/// Render a value the way it appears in a secret: strings verbatim, the rest as JSON
+pub fn value_to_string(value: &Value) -> String {
+  match value {
+    Value::String(s) => s.clone(),
+    Value::Null => String::new(),
+    other => other.to_string(),
+  }
+}
+
+fn iso8601(value: &Value) -> Result<String> {
+  let timestamp = match value {
+    Value::Number(n) => n.as_i64(),
+    Value::String(s) => s.trim().parse::<i64>().ok(),
+    _ => None,
+  }.ok_or_else(|| {
+    ClaudeCodeError::Validation(format!("iso8601 expects an epoch timestamp, got {}", value))
+  })?;
+
+  // Claude Code stores milliseconds; anything this large is not a plausible second count
+  let datetime = if timestamp.abs() >= 100_000_000_000 {
+    chrono::DateTime::from_timestamp_millis(timestamp)
+  } else {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+  };
+
+  datetime
+    .map(|dt| dt.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+    .ok_or_else(|| ClaudeCodeError::Validation(format!("Timestamp out of range: {}", timestamp)))
+}
+
+fn apply_filter(filter: &str, value: Value) -> Result<Value> {
+  let filtered = match filter {
+    "json" => value.to_string(),
+    "base64" => base64::engine::general_purpose::STANDARD.encode(value_to_string(&value)),
+    "iso8601" => iso8601(&value)?,
+    "upper" => value_to_string(&value).to_uppercase(),
+    "lower" => value_to_string(&value).to_lowercase(),
+    other => {
+      return Err(ClaudeCodeError::InvalidConfig(format!("Unknown template filter '{}'", other)));
+    }
+  };
+  Ok(Value::String(filtered))
+}
+
+/// Render a template against a credential object
+pub fn render(template: &str, context: &Value) -> Result<String> {
+  let mut rendered = String::with_capacity(template.len());
+
+  for piece in parse(template)? {
+    match piece {
+      Piece::Text(text) => rendered.push_str(text),
+      Piece::Expression(expression) => {
+        let mut value = lookup(context, expression.path)
+          .cloned()
+          .ok_or_else(|| {
+            ClaudeCodeError::Validation(
+              format!("Template field '{}' not found in credentials", expression.path)
+            )
+          })?;
+        for filter in expression.filters {
+          value = apply_filter(filter, value)?;
+        }
+        rendered.push_str(&value_to_string(&value));
+      }
+    }
+  }
+
+  Ok(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn context() -> Value {
+    serde_json::json!({
+      "accessToken": "sk-ant-oat01",
+      "refreshToken": "sk-ant-ort01",
+      "expiresAt": 1750255977327i64,
+      "scopes": ["user:inference", "user:profile"],
+    })
+  }
+
+  #[test]
+  fn test_render_fields_and_filters() {
+    assert_eq!(render("Bearer {{ accessToken }}", &context()).unwrap(), "Bearer sk-ant-oat01");
+    assert_eq!(render("{{expiresAt|iso8601}}", &context()).unwrap(), "2025-06-18T14:12:57Z");
+    assert_eq!(render("{{ scopes.1 | upper }}", &context()).unwrap(), "USER:PROFILE");
+    assert_eq!(render("{{ accessToken | base64 }}", &context()).unwrap(), "c2stYW50LW9hdDAx");
+    assert_eq!(
+      render(r#"{"token": {{ accessToken | json }}}"#, &context()).unwrap(),
+      r#"{"token": "sk-ant-oat01"}"#
+    );
+  }
+
+  #[test]
+  fn test_render_whole_object() {
+    let rendered = render(r#"{"claudeAiOauth": {{ . }}}"#, &context()).unwrap();
+    let parsed: Value = serde_json::from_str(&rendered).unwrap();
+    assert_eq!(parsed["claudeAiOauth"], context());
+  }
+
+  #[test]
+  fn test_template_errors() {
+    assert!(validate("{{ accessToken | rot13 }}").is_err());
+    assert!(validate("{{ accessToken").is_err());
+    assert!(validate("{{ }}").is_err());
+    assert!(render("{{ missing }}", &context()).is_err());
+    assert!(render("{{ accessToken | iso8601 }}", &context()).is_err());
+  }
+}
@@ -0,0 +1,242 @@
+//! Configuration validation behind `validate`.
+//!
+//! [`ConfigValidator`] implements [`ValidationService`] over a set of
+//! [`ValidationRule`]s. The default rules, the first three of which
+//! ([`ConfigValidator::with_config_rules`]) only look at the configuration:
+//!
+//! | Rule                     | Checks                                                | Fix |
+//! |--------------------------|-------------------------------------------------------|-----|
+//! | `log_level`              | `daemon.log_level` is a known level                   | yes |
+//! | `duplicate_secret_names` | No two fields or templates share a secret name        | no  |
+//! | `secret_names`           | Names follow the naming rules of each target provider | yes |
+//! | `mapping_fields`         | Mapped fields and templates resolve                   | no  |
+//! | `reachable_targets`      | Every target is reachable by its provider             | no  |
+//! | `expired_credentials`    | The session has not expired                           | no  |
+//! | `credential_scopes`      | The session carries the required scopes               | no  |
+//!
+//! [`ConfigValidator::validate`] runs the configuration rules and, for every credential
+//! profile, the credential rules against the credentials that profile reads:
+//!
+//! ```rust,no_run
+//! use claude_code_toolkit::validation::ConfigValidator;
+//! use claude_code_toolkit::traits::validation::ValidationSummary;
+//! use claude_code_toolkit::types::Config;
+//!
+//! #[tokio::main]
+//! async fn main() -> claude_code_toolkit::Result<()> {
+//!     let validator = ConfigValidator::with_default_rules();
+//!     let issues = validator.validate(&Config::default()).await?;
+//!     println!("{} errors", ValidationSummary::from_errors(&issues).errors);
+//!     Ok(())
+//! }
+//! ```
+
+pub mod rules;
+
+pub use rules::{ LOG_LEVELS, SecretNameProblem, check_secret_name };
+
+use crate::config::credentials::CredentialsManager;
+use crate::error::Result;
+use crate::traits::validation::ValidationSummary;
+use crate::traits::{ Credentials, ValidationError, ValidationRule, ValidationService };
+use crate::types::Config;
+use async_trait::async_trait;
+use rules::*;
+
+/// Rule-based [`ValidationService`]
+pub struct ConfigValidator {
+  rules: Vec<Box<dyn ValidationRule>>,
+}
+
+impl ConfigValidator {
+  /// Validator without rules
+  pub fn new() -> Self {
+    Self { rules: Vec::new() }
+  }
+
+  /// Validator with the built-in rules that only look at the configuration itself
+  ///
+  /// These read no credentials and make no network calls.
+  pub fn with_config_rules() -> Self {
+    let mut validator = Self::new();
+    validator.register_rule(Box::new(LogLevelRule));
+    validator.register_rule(Box::new(DuplicateSecretNamesRule));
+    validator.register_rule(Box::new(SecretNamesRule));
+    validator
+  }
+
+  /// Validator with every built-in rule
+  pub fn with_default_rules() -> Self {
+    let mut validator = Self::with_config_rules();
+    validator.register_rule(Box::new(MappingFieldsRule));
+    validator.register_rule(Box::new(TargetReachabilityRule));
+    validator.register_rule(Box::new(ExpiredCredentialsRule));
+    validator.register_rule(Box::new(CredentialScopesRule));
+    validator
+  }
+
+  pub fn rule_ids(&self) -> Vec<&str> {
+    self.rules
+      .iter()
+      .map(|r| r.rule_id())
+      .collect()
+  }
+
+  /// Validate the configuration and the credentials of every profile
+  ///
+  /// Unreadable credentials are reported as an error when a target uses the profile and
+  /// as a warning otherwise.
+  pub async fn validate(&self, config: &Config) -> Result<Vec<ValidationError>> {
+    let mut errors = self.validate_config(config).await?;
+
+    for profile in config.profile_names() {
+      let Some(profile_config) = config.profile(profile) else {
+        continue;
+      };
+      let field = profile_field(profile);
+
+      let credentials_manager = CredentialsManager::from_config(profile_config);
+      let credentials = match
+        credentials_manager.read_credential_object(&profile_config.json_path).await
+      {
+        Ok(object) => Credentials::from_source(object, profile_config.field_mappings.keys()),
+        Err(e) => {
+          let message = format!("Cannot read credentials: {}", e);
+          let used = config.targets.iter().any(|t| t.profile_name() == profile);
+          let error = if used {
+            ValidationError::error(&field, &message)
+          } else {
+            ValidationError::warning(&field, &message)
+          };
+          errors.push(error.with_suggestion("Check file_path and json_path of the profile"));
+          continue;
+        }
+      };
+
+      // Credential rules report against `credentials`; point them at the profile instead
+      for mut error in self.validate_credentials(&credentials).await? {
+        if let Some(rest) = error.field.strip_prefix("credentials") {
+          error.field = format!("{}{}", field, rest);
+        }
+        errors.push(error);
+      }
+
+      for rule in self.rules.iter().filter(|r| r.applies_to(config)) {
+        errors.extend(rule.validate_profile(config, profile, &credentials).await?);
+      }
+    }
+
+    Ok(errors)
+  }
+}
+
+impl Default for ConfigValidator {
+  fn default() -> Self {
+    Self::with_default_rules()
+  }
+}
+
+#[async_trait]
+impl ValidationService for ConfigValidator {
+  fn register_rule(&mut self, rule: Box<dyn ValidationRule>) {
+    self.rules.push(rule);
+  }
+
+  async fn validate_config(&self, config: &Config) -> Result<Vec<ValidationError>> {
+    let mut errors = Vec::new();
+    for rule in self.rules.iter().filter(|r| r.applies_to(config)) {
+      errors.extend(rule.validate_config(config).await?);
+    }
+    Ok(errors)
+  }
+
+  async fn validate_credentials(&self, credentials: &Credentials) -> Result<Vec<ValidationError>> {
+    let mut errors = Vec::new();
+    for rule in &self.rules {
+      errors.extend(rule.validate_credentials(credentials).await?);
+    }
+    Ok(errors)
+  }
+
+  async fn get_validation_summary(
+    &self,
+    config: &Config,
+    credentials: Option<&Credentials>
+  ) -> Result<ValidationSummary> {
+    let mut errors = self.validate_config(config).await?;
+    if let Some(credentials) = credentials {
+      errors.extend(self.validate_credentials(credentials).await?);
+    }
+    Ok(ValidationSummary::from_errors(&errors))
+  }
+
+  async fn auto_fix(&self, config: &mut Config) -> Result<Vec<String>> {
+    let mut changes = Vec::new();
+    for rule in &self.rules {
+      if rule.applies_to(config) {
+        changes.extend(rule.auto_fix(config).await?);
+      }
+    }
+    Ok(changes)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::types::{ DEFAULT_PROFILE, TargetConfig };
+
+  #[tokio::test]
+  async fn test_config_rules_and_auto_fix() {
+    let mut config = Config::default();
+    config.daemon.log_level = "WARNING".to_string();
+    config.credentials.field_mappings.clear();
+    config.credentials.field_mappings.insert("accessToken".into(), "claude-token".into());
+    config.credentials.field_mappings.insert("refreshToken".into(), "CLAUDE_TOKEN".into());
+
+    let mut target = TargetConfig::new("github", "repository", "owner/repo");
+    target.mapping.prefix = Some("ci.".to_string());
+    target.mapping.templates.insert("GITHUB_TOKEN".into(), "{{ accessToken }}".into());
+    config.targets.push(target);
+
+    let validator = ConfigValidator::with_config_rules();
+    let errors = validator.validate_config(&config).await.unwrap();
+    let fields: Vec<&str> = errors
+      .iter()
+      .map(|e| e.field.as_str())
+      .collect();
+    assert_eq!(fields, vec![
+      "daemon.log_level",
+      "credentials.field_mappings.accessToken",
+      "targets[0].mapping.prefix"
+    ]);
+    let summary = ValidationSummary::from_errors(&errors);
+    assert_eq!((summary.errors, summary.auto_fixable), (3, 3));
+
+    let changes = validator.auto_fix(&mut config).await.unwrap();
+    assert_eq!(changes.len(), 3);
+    assert_eq!(config.daemon.log_level, "warn");
+    assert_eq!(config.targets[0].mapping.prefix.as_deref(), Some("CI_"));
+
+    // The renamed secret now collides with another, which needs a human
+    let errors = validator.validate_config(&config).await.unwrap();
+    let messages: Vec<&str> = errors
+      .iter()
+      .map(|e| e.message.as_str())
+      .collect();
+    assert_eq!(messages, vec![
+      "Secret CLAUDE_TOKEN is produced more than once, one value overwrites the other"
+    ]);
+    assert!(errors.iter().all(|e| !e.auto_fixable));
+    assert!(validator.auto_fix(&mut config).await.unwrap().is_empty());
+
+    // Names entered by hand are checked with the target's prefix applied
+    let problem = check_secret_name(&config, DEFAULT_PROFILE, "my-secret.name").unwrap();
+    assert_eq!(problem.suggestion, "MY_SECRET_NAME");
+    assert!(check_secret_name(&config, DEFAULT_PROFILE, "GITHUB_TOKEN").is_none());
+    config.targets[0].mapping.prefix = None;
+    let problem = check_secret_name(&config, DEFAULT_PROFILE, "GITHUB_TOKEN").unwrap();
+    assert_eq!(problem.suggestion, "TOKEN");
+    assert!(problem.message.ends_with("uses the reserved GITHUB_ prefix"), "{}", problem);
+  }
+}
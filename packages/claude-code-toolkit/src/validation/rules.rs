@@ -0,0 +1,793 @@
+//! Built-in validation rules
+
+use crate::error::Result;
+use crate::providers::{ ProviderFactory, SecretNameRules };
+use crate::traits::{ Credentials, SecretMapping, Target, ValidationError, ValidationRule };
+use crate::types::{ Config, DEFAULT_PROFILE, MappingOverrides };
+use async_trait::async_trait;
+use std::collections::{ BTreeMap, HashMap };
+
+/// Log levels the daemon understands
+pub const LOG_LEVELS: &[&str] = &["trace", "debug", "info", "warn", "error"];
+
+/// Scopes a Claude session needs for its token to be usable elsewhere
+pub const REQUIRED_SCOPES: &[&str] = &["user:inference"];
+
+/// Configuration key of a credential profile
+pub fn profile_field(profile: &str) -> String {
+  if profile == DEFAULT_PROFILE {
+    "credentials".to_string()
+  } else {
+    format!("profiles.{}", profile)
+  }
+}
+
+/// `daemon.log_level` is one of [`LOG_LEVELS`]
+pub struct LogLevelRule;
+
+impl LogLevelRule {
+  /// Closest known level, e.g. `WARNING` -> `warn`
+  fn normalize(level: &str) -> Option<&'static str> {
+    let level = level.trim().to_lowercase();
+    match level.as_str() {
+      "warning" => Some("warn"),
+      "err" => Some("error"),
+      _ =>
+        LOG_LEVELS.iter()
+          .find(|known| **known == level)
+          .copied(),
+    }
+  }
+}
+
+#[async_trait]
+impl ValidationRule for LogLevelRule {
+  fn rule_id(&self) -> &str {
+    "log_level"
+  }
+
+  fn description(&self) -> &str {
+    "The daemon log level is known"
+  }
+
+  async fn validate_config(&self, config: &Config) -> Result<Vec<ValidationError>> {
+    let level = &config.daemon.log_level;
+    if LOG_LEVELS.contains(&level.as_str()) {
+      return Ok(Vec::new());
+    }
+
+    let suggestion = match Self::normalize(level) {
+      Some(known) => format!("Use '{}'", known),
+      None => format!("Use one of {}", LOG_LEVELS.join(", ")),
+    };
+    Ok(
+      vec![
+        ValidationError::error("daemon.log_level", &format!("Unknown log level '{}'", level))
+          .with_suggestion(&suggestion)
+          .fixable()
+      ]
+    )
+  }
+
+  async fn validate_credentials(&self, _credentials: &Credentials) -> Result<Vec<ValidationError>> {
+    Ok(Vec::new())
+  }
+
+  async fn auto_fix(&self, config: &mut Config) -> Result<Vec<String>> {
+    let level = &config.daemon.log_level;
+    if LOG_LEVELS.contains(&level.as_str()) {
+      return Ok(Vec::new());
+    }
+
+    let fixed = Self::normalize(level).unwrap_or("info");
+    let change = format!("Set daemon.log_level to {} (was '{}')", fixed, level);
+    config.daemon.log_level = fixed.to_string();
+    Ok(vec![change])
+  }
+}
+
+/// Mapped credential fields and templates resolve against the credentials
+pub struct MappingFieldsRule;
+
+#[async_trait]
+impl ValidationRule for MappingFieldsRule {
+  fn rule_id(&self) -> &str {
+    "mapping_fields"
+  }
+
+  fn description(&self) -> &str {
+    "Mapped credential fields exist in the credentials"
+  }
+
+  async fn validate_config(&self, _config: &Config) -> Result<Vec<ValidationError>> {
+    Ok(Vec::new())
+  }
+
+  async fn validate_credentials(&self, _credentials: &Credentials) -> Result<Vec<ValidationError>> {
+    Ok(Vec::new())
+  }
+
+  async fn validate_profile(
+    &self,
+    config: &Config,
+    profile: &str,
+    credentials: &Credentials
+  ) -> Result<Vec<ValidationError>> {
+    let Some(profile_config) = config.profile(profile) else {
+      return Ok(Vec::new());
+    };
+
+    let mut available: Vec<&String> = credentials.source
+      .as_object()
+      .map(|object| object.keys().collect())
+      .unwrap_or_default();
+    available.sort();
+    let suggestion = format!(
+      "Available fields: {}",
+      available
+        .iter()
+        .map(|s| s.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+    );
+
+    // Profile mappings first, then the overrides of every target reading this profile
+    let mut sources = vec![(profile_field(profile), &profile_config.field_mappings)];
+    let mut templates = vec![(profile_field(profile), &profile_config.templates)];
+    for (i, target) in config.targets.iter().enumerate() {
+      if target.profile_name() == profile {
+        sources.push((format!("targets[{}].mapping", i), &target.mapping.field_mappings));
+        templates.push((format!("targets[{}].mapping", i), &target.mapping.templates));
+      }
+    }
+
+    let mut errors = Vec::new();
+    for (prefix, mappings) in sources {
+      let mut mappings: Vec<_> = mappings.iter().collect();
+      mappings.sort();
+      for (field, secret_name) in mappings {
+        if credentials.field(field).is_none() {
+          errors.push(
+            ValidationError::warning(
+              &format!("{}.field_mappings.{}", prefix, field),
+              &format!(
+                "Field '{}' has no value in the credentials, {} is never synced",
+                field,
+                secret_name
+              )
+            ).with_suggestion(&suggestion)
+          );
+        }
+      }
+    }
+
+    for (prefix, templates) in templates {
+      let mut templates: Vec<_> = templates.iter().collect();
+      templates.sort();
+      for (secret_name, template) in templates {
+        if let Err(e) = crate::utils::template::render(template, &credentials.source) {
+          errors.push(
+            ValidationError::warning(
+              &format!("{}.templates.{}", prefix, secret_name),
+              &format!("Template does not render: {}", e)
+            ).with_suggestion(&suggestion)
+          );
+        }
+      }
+    }
+
+    Ok(errors)
+  }
+}
+
+/// No two fields or templates of a mapping produce the same secret
+pub struct DuplicateSecretNamesRule;
+
+impl DuplicateSecretNamesRule {
+  /// Secret names produced more than once, compared case-insensitively
+  fn duplicates(mapping: &SecretMapping) -> Vec<String> {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for name in mapping.mappings.values().chain(mapping.templates.keys()) {
+      *counts.entry(name.to_uppercase()).or_default() += 1;
+    }
+
+    counts
+      .into_iter()
+      .filter(|(_, count)| *count > 1)
+      .map(|(name, _)| name)
+      .collect()
+  }
+}
+
+#[async_trait]
+impl ValidationRule for DuplicateSecretNamesRule {
+  fn rule_id(&self) -> &str {
+    "duplicate_secret_names"
+  }
+
+  fn description(&self) -> &str {
+    "Every secret name is produced by a single field or template"
+  }
+
+  async fn validate_config(&self, config: &Config) -> Result<Vec<ValidationError>> {
+    let suggestion = "Map each credential field and template to its own secret name";
+    let mut errors = Vec::new();
+
+    for profile in config.profile_names() {
+      let Some(profile_config) = config.profile(profile) else {
+        continue;
+      };
+      for name in Self::duplicates(&SecretMapping::from_config(profile_config)) {
+        errors.push(
+          ValidationError::error(
+            &profile_field(profile),
+            &format!("Secret {} is produced more than once, one value overwrites the other", name)
+          ).with_suggestion(suggestion)
+        );
+      }
+    }
+
+    for (i, target) in config.targets.iter().enumerate() {
+      let Some(profile_config) = config.profile(target.profile_name()) else {
+        continue;
+      };
+      if target.mapping.is_empty() {
+        continue;
+      }
+
+      // A prefix or suffix applies to every name alike and cannot introduce duplicates
+      let overrides = MappingOverrides { prefix: None, suffix: None, ..target.mapping.clone() };
+      let mapping = SecretMapping::from_config(profile_config);
+      let inherited = Self::duplicates(&mapping);
+      for name in Self::duplicates(&mapping.with_overrides(&overrides)) {
+        if !inherited.contains(&name) {
+          errors.push(
+            ValidationError::error(
+              &format!("targets[{}].mapping", i),
+              &format!("Secret {} is produced more than once for {}", name, target)
+            ).with_suggestion(suggestion)
+          );
+        }
+      }
+    }
+
+    Ok(errors)
+  }
+
+  async fn validate_credentials(&self, _credentials: &Credentials) -> Result<Vec<ValidationError>> {
+    Ok(Vec::new())
+  }
+}
+
+/// Secret names follow the naming rules of every provider they are synced to
+///
+/// Providers declare their rules through [`ProviderCreator::secret_name_rules`]; GitHub
+/// for instance only allows `A-Z`, `0-9` and `_` and reserves `GITHUB_`. Invalid
+/// characters are fixed by normalising them, names starting with a digit or a reserved
+/// prefix have to be renamed.
+///
+/// [`ProviderCreator::secret_name_rules`]: crate::providers::ProviderCreator::secret_name_rules
+pub struct SecretNamesRule;
+
+/// Provider and naming rules a secret name has to satisfy
+type NameRules = (String, SecretNameRules);
+
+impl SecretNamesRule {
+  /// Naming rules of each target, leaving out providers that accept any name
+  fn target_rules(config: &Config) -> Vec<(usize, SecretNameRules)> {
+    let factory = ProviderFactory::new();
+    config.targets
+      .iter()
+      .enumerate()
+      .filter_map(|(i, target)| {
+        factory
+          .secret_name_rules(&target.provider, &target.kind)
+          .filter(|rules| !rules.is_any())
+          .map(|rules| (i, rules))
+      })
+      .collect()
+  }
+
+  /// Rules of the targets a profile's secrets are synced to
+  fn profile_rules(
+    config: &Config,
+    target_rules: &[(usize, SecretNameRules)],
+    profile: &str
+  ) -> Vec<NameRules> {
+    let mut found: Vec<NameRules> = Vec::new();
+    for (i, rules) in target_rules {
+      let target = &config.targets[*i];
+      let entry = (target.provider.clone(), *rules);
+      if target.profile_name() == profile && !found.contains(&entry) {
+        found.push(entry);
+      }
+    }
+    found
+  }
+
+  /// Profiles whose secrets are synced to at least one target with naming rules
+  fn profiles(config: &Config, target_rules: &[(usize, SecretNameRules)]) -> Vec<String> {
+    let mut profiles: Vec<String> = target_rules
+      .iter()
+      .map(|(i, _)| config.targets[*i].profile_name().to_string())
+      .collect();
+    profiles.sort();
+    profiles.dedup();
+    profiles
+  }
+
+  /// `name` with the characters normalised for every set of rules
+  fn normalize_chars(rules: &[NameRules], name: &str) -> String {
+    rules.iter().fold(name.to_string(), |name, (_, rules)| rules.normalize_chars(&name))
+  }
+
+  /// Configured secret names (or name parts) by configuration key, with their rules
+  fn name_sources(
+    config: &Config,
+    target_rules: &[(usize, SecretNameRules)]
+  ) -> Vec<(String, String, Vec<NameRules>)> {
+    let mut sources = Vec::new();
+    let mut add_mapping = |
+      prefix: String,
+      mappings: &HashMap<String, String>,
+      templates: &HashMap<String, String>,
+      rules: &[NameRules]
+    | {
+      for (field, secret_name) in mappings {
+        sources.push((
+          format!("{}.field_mappings.{}", prefix, field),
+          secret_name.clone(),
+          rules.to_vec(),
+        ));
+      }
+      for secret_name in templates.keys() {
+        sources.push((
+          format!("{}.templates.{}", prefix, secret_name),
+          secret_name.clone(),
+          rules.to_vec(),
+        ));
+      }
+    };
+
+    for profile in Self::profiles(config, target_rules) {
+      if let Some(profile_config) = config.profile(&profile) {
+        add_mapping(
+          profile_field(&profile),
+          &profile_config.field_mappings,
+          &profile_config.templates,
+          &Self::profile_rules(config, target_rules, &profile)
+        );
+      }
+    }
+    for (i, rules) in target_rules {
+      let target = &config.targets[*i];
+      add_mapping(
+        format!("targets[{}].mapping", i),
+        &target.mapping.field_mappings,
+        &target.mapping.templates,
+        &[(target.provider.clone(), *rules)]
+      );
+    }
+
+    for (i, rules) in target_rules {
+      let target = &config.targets[*i];
+      let rules = vec![(target.provider.clone(), *rules)];
+      if let Some(prefix) = &target.mapping.prefix {
+        sources.push((format!("targets[{}].mapping.prefix", i), prefix.clone(), rules.clone()));
+      }
+      if let Some(suffix) = &target.mapping.suffix {
+        sources.push((format!("targets[{}].mapping.suffix", i), suffix.clone(), rules));
+      }
+    }
+
+    sources.sort_by(|a, b| a.0.cmp(&b.0));
+    sources
+  }
+
+  /// Rename template secrets, keeping include/exclude lists that refer to them in step
+  fn rename_templates(
+    templates: &mut HashMap<String, String>,
+    config_targets: &mut [crate::types::TargetConfig],
+    rules: &[NameRules],
+    prefix: &str,
+    changes: &mut Vec<String>
+  ) {
+    let invalid: Vec<String> = templates
+      .keys()
+      .filter(|name| rules.iter().any(|(_, r)| r.has_invalid_chars(name)))
+      .cloned()
+      .collect();
+    for name in invalid {
+      let fixed = Self::normalize_chars(rules, &name);
+      if let Some(template) = templates.remove(&name) {
+        templates.insert(fixed.clone(), template);
+      }
+      for target in config_targets.iter_mut() {
+        for entry in target.mapping.include.iter_mut().chain(target.mapping.exclude.iter_mut()) {
+          if *entry == name {
+            *entry = fixed.clone();
+          }
+        }
+      }
+      changes.push(format!("Renamed secret '{}' to '{}' in {}.templates", name, fixed, prefix));
+    }
+  }
+
+  fn rename_mappings(
+    mappings: &mut HashMap<String, String>,
+    rules: &[NameRules],
+    prefix: &str,
+    changes: &mut Vec<String>
+  ) {
+    let mut fields: Vec<&String> = mappings.keys().collect();
+    fields.sort();
+    let fields: Vec<String> = fields.into_iter().cloned().collect();
+    for field in fields {
+      let Some(secret_name) = mappings.get_mut(&field) else {
+        continue;
+      };
+      if rules.iter().any(|(_, r)| r.has_invalid_chars(secret_name)) {
+        let fixed = Self::normalize_chars(rules, secret_name);
+        changes.push(
+          format!(
+            "Renamed secret '{}' to '{}' in {}.field_mappings.{}",
+            secret_name,
+            fixed,
+            prefix,
+            field
+          )
+        );
+        *secret_name = fixed;
+      }
+    }
+  }
+}
+
+#[async_trait]
+impl ValidationRule for SecretNamesRule {
+  fn rule_id(&self) -> &str {
+    "secret_names"
+  }
+
+  fn description(&self) -> &str {
+    "Secret names are valid for the providers they are synced to"
+  }
+
+  fn applies_to(&self, config: &Config) -> bool {
+    !config.targets.is_empty()
+  }
+
+  async fn validate_config(&self, config: &Config) -> Result<Vec<ValidationError>> {
+    let target_rules = Self::target_rules(config);
+    let mut errors = Vec::new();
+
+    for (field, name, rules) in Self::name_sources(config, &target_rules) {
+      for (provider, provider_rules) in &rules {
+        if provider_rules.has_invalid_chars(&name) {
+          errors.push(
+            ValidationError::error(
+              &field,
+              &format!("'{}' is not a valid {} secret name", name, provider)
+            )
+              .with_suggestion(
+                &format!(
+                  "{} secret names may only contain {}, e.g. {}",
+                  provider,
+                  provider_rules.describe_chars(),
+                  Self::normalize_chars(&rules, &name)
+                )
+              )
+              .fixable()
+          );
+        }
+      }
+    }
+
+    // Whole names, once prefix and suffix are applied
+    for (i, rules) in &target_rules {
+      let target = &config.targets[*i];
+      let Some(profile_config) = config.profile(target.profile_name()) else {
+        continue;
+      };
+
+      let mapping = SecretMapping::from_config(profile_config).with_overrides(&target.mapping);
+      for name in mapping.secret_names() {
+        // Characters are reported for the configured parts above
+        if rules.has_invalid_chars(&name) {
+          continue;
+        }
+        let Some(problem) = rules.check(&name) else {
+          continue;
+        };
+        errors.push(
+          ValidationError::error(
+            &format!("targets[{}]", i),
+            &format!("Secret {} for {} {}", name, target, problem)
+          ).with_suggestion(
+            &format!(
+              "Rename the secret, e.g. to {}, or set a mapping prefix such as CLAUDE_",
+              rules.normalize(&name)
+            )
+          )
+        );
+      }
+    }
+
+    Ok(errors)
+  }
+
+  async fn validate_credentials(&self, _credentials: &Credentials) -> Result<Vec<ValidationError>> {
+    Ok(Vec::new())
+  }
+
+  async fn auto_fix(&self, config: &mut Config) -> Result<Vec<String>> {
+    let target_rules = Self::target_rules(config);
+    let mut changes = Vec::new();
+
+    for profile in Self::profiles(config, &target_rules) {
+      let rules = Self::profile_rules(config, &target_rules, &profile);
+      let prefix = profile_field(&profile);
+      let Config { credentials, profiles, targets, .. } = config;
+      let profile_config = if profile == DEFAULT_PROFILE {
+        credentials
+      } else if let Some(profile_config) = profiles.get_mut(&profile) {
+        profile_config
+      } else {
+        continue;
+      };
+      Self::rename_mappings(&mut profile_config.field_mappings, &rules, &prefix, &mut changes);
+      Self::rename_templates(&mut profile_config.templates, targets, &rules, &prefix, &mut changes);
+    }
+
+    for (i, target_rules) in target_rules {
+      let rules = [(config.targets[i].provider.clone(), target_rules)];
+      let prefix = format!("targets[{}].mapping", i);
+      let mut templates = std::mem::take(&mut config.targets[i].mapping.templates);
+      Self::rename_templates(&mut templates, &mut config.targets, &rules, &prefix, &mut changes);
+
+      let mapping = &mut config.targets[i].mapping;
+      mapping.templates = templates;
+      Self::rename_mappings(&mut mapping.field_mappings, &rules, &prefix, &mut changes);
+      for (part, value) in [("prefix", &mut mapping.prefix), ("suffix", &mut mapping.suffix)] {
+        if let Some(value) = value.as_mut().filter(|v| target_rules.has_invalid_chars(v)) {
+          let fixed = target_rules.normalize_chars(value);
+          changes.push(format!("Changed {}.{} from '{}' to '{}'", prefix, part, value, fixed));
+          *value = fixed;
+        }
+      }
+    }
+
+    Ok(changes)
+  }
+}
+
+/// A secret name a provider rejects, see [`check_secret_name`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecretNameProblem {
+  pub message: String,
+  /// A name every target of the profile accepts
+  pub suggestion: String,
+}
+
+impl std::fmt::Display for SecretNameProblem {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}; try {}", self.message, self.suggestion)
+  }
+}
+
+/// Check a secret name mapped in `profile` against the targets it is synced to
+///
+/// Each target's mapping prefix and suffix are applied before checking. Meant for names
+/// entered by hand, before they reach the configuration.
+pub fn check_secret_name(config: &Config, profile: &str, name: &str) -> Option<SecretNameProblem> {
+  let target_rules = SecretNamesRule::target_rules(config);
+  let rules = SecretNamesRule::profile_rules(config, &target_rules, profile);
+  let suggestion = rules.iter().fold(name.to_string(), |name, (_, rules)| rules.normalize(&name));
+
+  target_rules.iter().find_map(|(i, rules)| {
+    let target = &config.targets[*i];
+    if target.profile_name() != profile {
+      return None;
+    }
+    let full = format!(
+      "{}{}{}",
+      target.mapping.prefix.as_deref().unwrap_or_default(),
+      name,
+      target.mapping.suffix.as_deref().unwrap_or_default()
+    );
+    rules.check(&full).map(|problem| SecretNameProblem {
+      message: format!("'{}' is not a valid secret name for {}: it {}", full, target, problem),
+      suggestion: suggestion.clone(),
+    })
+  })
+}
+
+/// Every target can be reached with its provider's current credentials
+///
+/// This asks each provider for access, so it needs the network and the provider tools.
+pub struct TargetReachabilityRule;
+
+#[async_trait]
+impl ValidationRule for TargetReachabilityRule {
+  fn rule_id(&self) -> &str {
+    "reachable_targets"
+  }
+
+  fn description(&self) -> &str {
+    "Every target is reachable"
+  }
+
+  fn applies_to(&self, config: &Config) -> bool {
+    !config.targets.is_empty()
+  }
+
+  async fn validate_config(&self, config: &Config) -> Result<Vec<ValidationError>> {
+    let factory = ProviderFactory::new();
+    let mut errors = Vec::new();
+
+    let mut providers: Vec<&str> = config.targets
+      .iter()
+      .map(|t| t.provider.as_str())
+      .collect();
+    providers.sort();
+    providers.dedup();
+
+    for provider_name in providers {
+      let indexed: Vec<(usize, Target)> = config.targets
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| t.provider == provider_name)
+        .map(|(i, t)| {
+          (i, Target {
+            provider: t.provider.clone(),
+            target_type: t.kind.clone(),
+            name: t.name.clone(),
+            config: t.options.clone(),
+          })
+        })
+        .collect();
+
+      let settings = config.providers.get(provider_name).cloned().unwrap_or_default();
+      let provider = match factory.create(provider_name, &settings) {
+        Ok(provider) => provider,
+        Err(e) => {
+          for (i, _) in &indexed {
+            errors.push(
+              ValidationError::error(&format!("targets[{}].provider", i), &e.to_string())
+                .with_suggestion(
+                  &format!("Available providers: {}", {
+                    let mut available = factory.available_providers();
+                    available.sort();
+                    available.join(", ")
+                  })
+                )
+            );
+          }
+          continue;
+        }
+      };
+
+      if !provider.is_configured().await.unwrap_or(false) {
+        errors.push(
+          ValidationError::error(
+            &format!("providers.{}", provider_name),
+            &format!("Provider {} is not configured", provider_name)
+          ).with_suggestion(
+            if provider_name == "github" {
+              "Install the GitHub CLI and run: gh auth login"
+            } else {
+              "Check the provider settings and its credentials"
+            }
+          )
+        );
+        continue;
+      }
+
+      let targets: Vec<Target> = indexed
+        .iter()
+        .map(|(_, t)| t.clone())
+        .collect();
+      let access = provider.validate_access(&targets).await?;
+      for (i, target) in &indexed {
+        let key = format!("{}:{}", target.target_type, target.name);
+        if access.get(&key) == Some(&false) {
+          errors.push(
+            ValidationError::error(
+              &format!("targets[{}]", i),
+              &format!("Cannot reach {}", target)
+            ).with_suggestion("Check that the target exists and that you may manage its secrets")
+          );
+        }
+      }
+    }
+
+    Ok(errors)
+  }
+
+  async fn validate_credentials(&self, _credentials: &Credentials) -> Result<Vec<ValidationError>> {
+    Ok(Vec::new())
+  }
+}
+
+/// The Claude session has not expired
+pub struct ExpiredCredentialsRule;
+
+#[async_trait]
+impl ValidationRule for ExpiredCredentialsRule {
+  fn rule_id(&self) -> &str {
+    "expired_credentials"
+  }
+
+  fn description(&self) -> &str {
+    "The credentials have not expired"
+  }
+
+  async fn validate_config(&self, _config: &Config) -> Result<Vec<ValidationError>> {
+    Ok(Vec::new())
+  }
+
+  async fn validate_credentials(&self, credentials: &Credentials) -> Result<Vec<ValidationError>> {
+    let Some(expires_at) = credentials.expires_at else {
+      return Ok(Vec::new());
+    };
+    if expires_at > chrono::Utc::now().timestamp_millis() {
+      return Ok(Vec::new());
+    }
+
+    let expired_at = chrono::DateTime
+      ::from_timestamp_millis(expires_at)
+      .unwrap_or_default()
+      .format("%Y-%m-%d %H:%M:%S UTC");
+    Ok(
+      vec![
+        ValidationError::warning(
+          "credentials",
+          &format!("The session expired at {}", expired_at)
+        ).with_suggestion("Start Claude Code to refresh the session, then run 'sync'")
+      ]
+    )
+  }
+}
+
+/// The Claude session carries the [`REQUIRED_SCOPES`]
+///
+/// Credentials without a `scopes` field (other CLIs' credential files) are not checked.
+pub struct CredentialScopesRule;
+
+#[async_trait]
+impl ValidationRule for CredentialScopesRule {
+  fn rule_id(&self) -> &str {
+    "credential_scopes"
+  }
+
+  fn description(&self) -> &str {
+    "The credentials carry the required scopes"
+  }
+
+  async fn validate_config(&self, _config: &Config) -> Result<Vec<ValidationError>> {
+    Ok(Vec::new())
+  }
+
+  async fn validate_credentials(&self, credentials: &Credentials) -> Result<Vec<ValidationError>> {
+    let Some(scopes) = credentials.source.get("scopes").and_then(|s| s.as_array()) else {
+      return Ok(Vec::new());
+    };
+
+    let missing: Vec<&str> = REQUIRED_SCOPES.iter()
+      .filter(|required| !scopes.iter().any(|s| s.as_str() == Some(**required)))
+      .copied()
+      .collect();
+    if missing.is_empty() {
+      return Ok(Vec::new());
+    }
+
+    Ok(
+      vec![
+        ValidationError::error(
+          "credentials",
+          &format!("The session lacks the {} scope", missing.join(", "))
+        ).with_suggestion("Log in to Claude Code again to grant it")
+      ]
+    )
+  }
+}